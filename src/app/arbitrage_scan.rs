@@ -0,0 +1,235 @@
+//! Arbitrage dashboard: rescans the arbitrage detector's events on a timer and keeps only the crossed ones
+
+use cli_log::*;
+use polymarket_rs_client::ClobClient;
+use std::{
+    env,
+    sync::{mpsc::{self, Receiver, TryRecvError}, Arc},
+    thread,
+    time::{Duration, Instant},
+};
+
+use super::core::{best_bid_ask, App};
+use crate::bot::{strategy::{sum_above_one, sum_below_one}, StrategyType};
+use crate::config::{ARBITRAGE_SCAN_INTERVAL_SECS, POLYGON_CHAIN_ID};
+use crate::utils::RateLimiter;
+
+/// An event whose YES asks sum below 1.0 or whose YES bids sum above it
+#[derive(Debug, Clone)]
+pub struct ArbitrageOpportunity {
+    pub event_title: String,
+    pub markets: usize,
+    pub bid_sum: Option<f64>, // Sum of best YES bids; either sum is `None` while a market has no price on that side
+    pub ask_sum: Option<f64>, // Sum of best YES asks
+    pub edge: f64, // Larger of the ask shortfall below 1.0 (buy every YES) and the bid excess above it (sell every YES)
+}
+
+impl ArbitrageOpportunity {
+    /// `None` unless buying or selling every market's YES locks in a profit
+    pub fn from_quotes(event_title: String, bids: &[Option<f64>], asks: &[Option<f64>]) -> Option<Self> {
+        let buy_edge = sum_below_one(asks).map(|(_, edge)| edge);
+        let sell_edge = sum_above_one(bids).map(|(_, edge)| edge);
+        let edge = buy_edge.into_iter().chain(sell_edge).reduce(f64::max)?;
+        Some(Self {
+            event_title,
+            markets: bids.len(),
+            bid_sum: bids.iter().copied().sum(),
+            ask_sum: asks.iter().copied().sum(),
+            edge,
+        })
+    }
+}
+
+/// Best YES bid and ask of one market, in the order the scan thread walks the events
+struct ScanQuote {
+    event: usize,
+    best_bid: Option<f64>,
+    best_ask: Option<f64>,
+}
+
+/// A scan running on its own thread; the quotes arrive over `quotes` and the sender drops when it is done
+pub struct ArbitrageScan {
+    titles: Vec<String>,
+    markets: Vec<usize>,
+    bids: Vec<Vec<Option<f64>>>,
+    asks: Vec<Vec<Option<f64>>>,
+    quotes: Receiver<ScanQuote>,
+}
+
+impl App {
+    pub fn open_arbitrage_dashboard(&mut self) {
+        self.show_market_selector = false;
+        self.show_arbitrage_dashboard = true;
+        self.needs_redraw = true;
+        self.start_arbitrage_scan();
+    }
+
+    pub fn close_arbitrage_dashboard(&mut self) {
+        self.show_arbitrage_dashboard = false;
+        self.show_market_selector = true;
+        self.needs_redraw = true;
+    }
+
+    /// Collects a finished scan and starts the next one when the dashboard is open and the last is old enough
+    pub fn scan_arbitrage_dashboard_if_due(&mut self) {
+        self.collect_arbitrage_scan();
+        let due = self.last_arbitrage_scan
+            .is_none_or(|scanned| scanned.elapsed() >= Duration::from_secs(ARBITRAGE_SCAN_INTERVAL_SECS));
+        if self.show_arbitrage_dashboard && due {
+            self.start_arbitrage_scan();
+        }
+    }
+
+    /// Fetches the YES book of every market in the arbitrage detector's multi-market events on a background
+    /// thread, through the shared rate limiter, so a long scan never holds up the UI or the health heartbeat
+    pub fn start_arbitrage_scan(&mut self) {
+        if self.arbitrage_scan.is_some() {
+            return;
+        }
+        let event_ids = self.bot_engine.get_strategy(&StrategyType::ArbitrageDetector)
+            .map(|strategy| strategy.selected_event_ids.clone())
+            .unwrap_or_default();
+        let events: Vec<_> = self.events.iter()
+            .filter(|event| event_ids.contains(&event.id))
+            .filter_map(|event| event.markets.as_ref().filter(|markets| markets.len() >= 2).map(|markets| (event, markets)))
+            .collect();
+
+        // The first token is the YES outcome; a market without one leaves its event's sums incomplete
+        let tokens: Vec<(usize, Option<String>)> = events.iter()
+            .enumerate()
+            .flat_map(|(index, (_, markets))| markets.iter().map(move |market| (index, market.token_ids.first().cloned())))
+            .collect();
+        let (sender, quotes) = mpsc::channel();
+        let rate_limiter = Arc::clone(&self.rate_limiter);
+        let endpoints = self.endpoints.clone();
+        let private_key_env = self.private_key_env.clone();
+        thread::spawn(move || {
+            let Ok(private_key) = env::var(&private_key_env) else {
+                warn!("Arbitrage scan skipped: no private key in '{private_key_env}' to build a client with");
+                return;
+            };
+            let client = ClobClient::with_l1_headers(&endpoints.clob_host, &endpoints.gamma_host, &private_key, POLYGON_CHAIN_ID);
+            let runtime = match tokio::runtime::Runtime::new() {
+                Ok(runtime) => runtime,
+                Err(e) => {
+                    warn!("Arbitrage scan skipped: could not start a runtime: {e}");
+                    return;
+                }
+            };
+            runtime.block_on(scan_quotes(&client, &rate_limiter, tokens, sender));
+        });
+
+        self.arbitrage_scan = Some(ArbitrageScan {
+            titles: events.iter().map(|(event, _)| event.title.clone()).collect(),
+            markets: events.iter().map(|(_, markets)| markets.len()).collect(),
+            bids: events.iter().map(|(_, markets)| Vec::with_capacity(markets.len())).collect(),
+            asks: events.iter().map(|(_, markets)| Vec::with_capacity(markets.len())).collect(),
+            quotes,
+        });
+        self.needs_redraw = true;
+    }
+
+    /// Takes whatever quotes have arrived and, once the scan thread is done, publishes the crossed events
+    fn collect_arbitrage_scan(&mut self) {
+        let Some(scan) = self.arbitrage_scan.as_mut() else { return };
+        loop {
+            match scan.quotes.try_recv() {
+                Ok(quote) => {
+                    scan.bids[quote.event].push(quote.best_bid);
+                    scan.asks[quote.event].push(quote.best_ask);
+                }
+                Err(TryRecvError::Empty) => return,
+                Err(TryRecvError::Disconnected) => break,
+            }
+        }
+
+        let Some(scan) = self.arbitrage_scan.take() else { return };
+        let events_scanned = scan.titles.len();
+        // An event the thread didn't finish has too few quotes, and a partial sum would look like an edge
+        let mut opportunities: Vec<_> = scan.titles.into_iter()
+            .zip(scan.markets.iter().zip(scan.bids.iter().zip(&scan.asks)))
+            .filter(|(_, (markets, (bids, _)))| bids.len() == **markets)
+            .filter_map(|(title, (_, (bids, asks)))| ArbitrageOpportunity::from_quotes(title, bids, asks))
+            .collect();
+        opportunities.sort_by(|a, b| b.edge.total_cmp(&a.edge));
+        info!("Arbitrage scan: {} of {events_scanned} events crossed", opportunities.len());
+        self.arbitrage_opportunities = opportunities;
+        self.arbitrage_events_scanned = events_scanned;
+        self.last_arbitrage_scan = Some(Instant::now());
+        self.needs_redraw = true;
+    }
+}
+
+/// Runs on the scan thread; stops early when the app has dropped the receiving end
+async fn scan_quotes(client: &ClobClient, rate_limiter: &RateLimiter, tokens: Vec<(usize, Option<String>)>, sender: mpsc::Sender<ScanQuote>) {
+    for (event, token_id) in tokens {
+        let (best_bid, best_ask) = match token_id {
+            Some(token_id) => match rate_limiter.run(client.get_order_book(&token_id)).await {
+                Ok(book) => best_bid_ask(&book),
+                Err(e) => {
+                    warn!("Arbitrage scan failed to load orderbook for token {token_id}: {e}");
+                    (0.0, 0.0)
+                }
+            },
+            None => (0.0, 0.0),
+        };
+        let quote = ScanQuote {
+            event,
+            best_bid: Some(best_bid).filter(|&bid| bid > 0.0),
+            best_ask: Some(best_ask).filter(|&ask| ask > 0.0),
+        };
+        if sender.send(quote).is_err() {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn opportunity(bids: &[Option<f64>], asks: &[Option<f64>]) -> Option<ArbitrageOpportunity> {
+        ArbitrageOpportunity::from_quotes("Event".to_string(), bids, asks)
+    }
+
+    #[test]
+    fn asks_below_one_are_a_buy_edge() {
+        let found = opportunity(&[Some(0.30), Some(0.40)], &[Some(0.35), Some(0.45)]).unwrap();
+        assert!((found.edge - 0.20).abs() < 1e-9);
+        assert!((found.ask_sum.unwrap() - 0.80).abs() < 1e-9);
+        assert!((found.bid_sum.unwrap() - 0.70).abs() < 1e-9);
+        assert_eq!(found.markets, 2);
+    }
+
+    #[test]
+    fn bids_above_one_are_a_sell_edge() {
+        let found = opportunity(&[Some(0.55), Some(0.60)], &[Some(0.57), Some(0.62)]).unwrap();
+        assert!((found.edge - 0.15).abs() < 1e-9);
+    }
+
+    #[test]
+    fn bids_below_one_are_not_an_edge() {
+        // Selling below 1.0 loses money, so a low bid sum alone is no opportunity
+        assert!(opportunity(&[Some(0.30), Some(0.40)], &[Some(0.55), Some(0.50)]).is_none());
+    }
+
+    #[test]
+    fn asks_above_one_are_not_an_edge() {
+        assert!(opportunity(&[Some(0.45), Some(0.50)], &[Some(0.55), Some(0.60)]).is_none());
+    }
+
+    #[test]
+    fn the_larger_edge_wins() {
+        // A crossed book can show both at once; the dashboard ranks by the better trade
+        let found = opportunity(&[Some(0.70), Some(0.50)], &[Some(0.40), Some(0.50)]).unwrap();
+        assert!((found.edge - 0.20).abs() < 1e-9);
+    }
+
+    #[test]
+    fn a_missing_price_leaves_that_side_out() {
+        let found = opportunity(&[None, Some(0.40)], &[Some(0.35), Some(0.45)]).unwrap();
+        assert!(found.bid_sum.is_none());
+        assert!((found.edge - 0.20).abs() < 1e-9);
+        assert!(opportunity(&[None, Some(0.40)], &[None, Some(0.45)]).is_none());
+    }
+}
@@ -0,0 +1,122 @@
+//! Categories tab: the filtered markets grouped by category, browsed category first, then market
+
+use std::collections::HashMap;
+
+use super::core::App;
+
+/// Bucket for markets that neither they nor their event put in a category
+pub const OTHER_CATEGORY: &str = "Other";
+
+impl App {
+    /// Regroups `filtered_markets`, so the tab honours the same search and filters as All Markets.
+    /// Categories are listed largest first, with "Other" last.
+    pub fn rebuild_market_categories(&mut self) {
+        let non_empty = |value: &Option<String>| value.as_deref().map(str::trim).filter(|v| !v.is_empty()).map(str::to_string);
+
+        // Markets usually leave the category to their event
+        let mut event_categories: HashMap<&str, String> = HashMap::new();
+        for event in &self.events {
+            let Some(category) = non_empty(&event.category) else { continue };
+            for market in event.markets.iter().flatten() {
+                if let Some(token_id) = market.token_ids.first() {
+                    event_categories.insert(token_id, category.clone());
+                }
+            }
+        }
+
+        let mut categories: HashMap<String, Vec<usize>> = HashMap::new();
+        for &market_idx in &self.filtered_markets {
+            let market = &self.markets[market_idx];
+            let category = non_empty(&market.category)
+                .or_else(|| market.token_ids.first().and_then(|token_id| event_categories.get(token_id.as_str()).cloned()))
+                .unwrap_or_else(|| OTHER_CATEGORY.to_string());
+            categories.entry(category).or_default().push(market_idx);
+        }
+
+        let open_category = self.selected_category_name().map(str::to_string);
+        let mut names: Vec<String> = categories.keys().cloned().collect();
+        names.sort_by(|a, b| {
+            (a == OTHER_CATEGORY).cmp(&(b == OTHER_CATEGORY))
+                .then(categories[b].len().cmp(&categories[a].len()))
+                .then(a.cmp(b))
+        });
+
+        self.category_names = names;
+        self.market_categories = categories;
+        // Stay on the same category when it survives the regroup
+        match open_category.and_then(|open| self.category_names.iter().position(|name| *name == open)) {
+            Some(position) => self.selected_category = position,
+            None => {
+                self.selected_category = 0;
+                self.show_category_markets = false;
+            }
+        }
+        self.selected_category_market = self.selected_category_market.min(self.category_markets().len().saturating_sub(1));
+    }
+
+    pub fn selected_category_name(&self) -> Option<&str> {
+        self.category_names.get(self.selected_category).map(String::as_str)
+    }
+
+    /// Market indices of the selected category, in the All Markets order
+    pub fn category_markets(&self) -> &[usize] {
+        self.selected_category_name()
+            .and_then(|name| self.market_categories.get(name))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// The cursor Up/Down move and the length of the list it moves in
+    fn category_cursor(&mut self) -> (&mut usize, usize) {
+        if self.show_category_markets {
+            let len = self.category_markets().len();
+            (&mut self.selected_category_market, len)
+        } else {
+            (&mut self.selected_category, self.category_names.len())
+        }
+    }
+
+    /// Moves through the categories, or the markets of the open one, wrapping at either end
+    pub fn move_category_selection(&mut self, step: isize) {
+        let (selected, len) = self.category_cursor();
+        if len == 0 {
+            return;
+        }
+        *selected = (*selected as isize + step).rem_euclid(len as isize) as usize;
+        self.needs_redraw = true;
+    }
+
+    /// Clamped rather than wrapping, like the other lists' page keys
+    pub fn page_category_selection(&mut self, down: bool) {
+        let page_size = 10;
+        let (selected, len) = self.category_cursor();
+        *selected = if down {
+            (*selected + page_size).min(len.saturating_sub(1))
+        } else {
+            selected.saturating_sub(page_size)
+        };
+        self.needs_redraw = true;
+    }
+
+    /// Opens the highlighted category, or hands the highlighted market to the All Markets selection path
+    pub fn select_category(&mut self) {
+        if !self.show_category_markets {
+            if self.selected_category_name().is_some() {
+                self.show_category_markets = true;
+                self.selected_category_market = 0;
+                self.needs_redraw = true;
+            }
+            return;
+        }
+
+        let Some(&market_idx) = self.category_markets().get(self.selected_category_market) else { return };
+        if let Some(position) = self.filtered_markets.iter().position(|&idx| idx == market_idx) {
+            self.selected_market = position;
+            self.select_market();
+        }
+    }
+
+    pub fn close_category(&mut self) {
+        self.show_category_markets = false;
+        self.needs_redraw = true;
+    }
+}
@@ -0,0 +1,201 @@
+//! SVG export of the charts on screen, drawn with plotters instead of the terminal widgets
+
+use anyhow::Result;
+use cli_log::*;
+use std::{fs, path::PathBuf};
+
+use super::{core::App, types::SelectedTab};
+use crate::config::{CHART_EXPORT_DIR, TWAP_WINDOW_SECS};
+use crate::ui::charts::{build_depth_chart_data, DepthChartData};
+use crate::utils::{short_id, twap_series};
+
+/// A line on an exported chart
+pub struct ExportSeries {
+    pub label: String,
+    pub points: Vec<(f64, f64)>,
+    pub color: (u8, u8, u8),
+}
+
+impl App {
+    /// Orderbook tab: session price and depth charts. Price History tab: the historical chart.
+    pub fn export_current_chart(&mut self) {
+        match self.write_chart_svg() {
+            Ok(Some(path)) => {
+                info!("Exported chart to {}", path.display());
+                self.set_status_message(format!("Chart saved to {}", path.display()));
+            }
+            Ok(None) => self.set_status_message("Not enough data to export a chart yet".to_string()),
+            Err(e) => {
+                warn!("Chart export failed: {e}");
+                self.set_status_message(format!("Chart export failed: {e}"));
+            }
+        }
+    }
+
+    fn write_chart_svg(&mut self) -> Result<Option<PathBuf>> {
+        let show_notional = self.show_notional;
+        let aggregation_ticks = self.aggregation_ticks;
+        let depth_chart_ticks = self.depth_chart_ticks;
+        let depth_chart_mode = self.depth_chart_mode;
+        let show_twap = self.show_twap;
+        let selected_tab = self.selected_tab;
+        let historical: Vec<(f64, f64)> = self.market_price_history.as_ref()
+            .map(|history| history.history.iter().map(|point| (point.t as f64, point.p)).collect())
+            .unwrap_or_default();
+        let Some(orderbook) = self.orderbook.as_mut() else {
+            return Ok(None);
+        };
+
+        fs::create_dir_all(CHART_EXPORT_DIR)?;
+        let kind = if selected_tab == SelectedTab::PriceHistory { "history" } else { "book" };
+        let path = PathBuf::from(CHART_EXPORT_DIR).join(format!(
+            "{}-{kind}-{}.svg",
+            short_id(&orderbook.token_id, 12),
+            chrono::Utc::now().format("%Y%m%d-%H%M%S"),
+        ));
+        let title = orderbook.market_question.clone();
+
+        if selected_tab == SelectedTab::PriceHistory {
+            if historical.len() < 2 {
+                return Ok(None);
+            }
+            let series = vec![ExportSeries { label: "Price".to_string(), points: historical, color: (0, 150, 200) }];
+            render::write_price_chart(&path, &title, &series)?;
+            return Ok(Some(path));
+        }
+
+        // Same curves as the terminal charts, so the file matches what's on screen
+        let mut series = vec![ExportSeries {
+            label: "Price".to_string(),
+            points: orderbook.price_history.points.iter().map(|p| (p.timestamp.timestamp() as f64, p.price)).collect(),
+            color: (0, 150, 200),
+        }];
+        if show_twap {
+            series.push(ExportSeries {
+                label: format!("TWAP {}m", TWAP_WINDOW_SECS / 60),
+                points: twap_series(&orderbook.price_history.points, TWAP_WINDOW_SECS as f64),
+                color: (220, 160, 0),
+            });
+        }
+        let Some(depth) = build_depth_chart_data(orderbook, show_notional, aggregation_ticks, depth_chart_ticks, depth_chart_mode) else {
+            return Ok(None);
+        };
+        render::write_orderbook_charts(&path, &title, &series, &depth, show_notional)?;
+        Ok(Some(path))
+    }
+}
+
+#[cfg(feature = "chart-export")]
+mod render {
+    use anyhow::Result;
+    use plotters::{coord::Shift, prelude::*};
+    use std::path::Path;
+
+    use super::{DepthChartData, ExportSeries};
+    use crate::config::{CHART_EXPORT_HEIGHT, CHART_EXPORT_WIDTH};
+
+    type Area<'a> = DrawingArea<SVGBackend<'a>, Shift>;
+
+    pub fn write_price_chart(path: &Path, title: &str, series: &[ExportSeries]) -> Result<()> {
+        let root = SVGBackend::new(path, (CHART_EXPORT_WIDTH, CHART_EXPORT_HEIGHT)).into_drawing_area();
+        root.fill(&WHITE)?;
+        let root = root.titled(title, ("sans-serif", 20))?;
+        draw_price_chart(&root, series)?;
+        root.present()?;
+        Ok(())
+    }
+
+    pub fn write_orderbook_charts(path: &Path, title: &str, series: &[ExportSeries], depth: &DepthChartData, show_notional: bool) -> Result<()> {
+        let root = SVGBackend::new(path, (CHART_EXPORT_WIDTH, CHART_EXPORT_HEIGHT)).into_drawing_area();
+        root.fill(&WHITE)?;
+        let root = root.titled(title, ("sans-serif", 20))?;
+        let (top, bottom) = root.split_vertically(root.dim_in_pixel().1 / 2);
+        if series.first().is_some_and(|price| price.points.len() >= 2) {
+            draw_price_chart(&top, series)?;
+        }
+        draw_depth_chart(&bottom, depth, show_notional)?;
+        root.present()?;
+        Ok(())
+    }
+
+    fn draw_price_chart(area: &Area, series: &[ExportSeries]) -> Result<()> {
+        let all_points = || series.iter().flat_map(|s| s.points.iter());
+        let (min_time, max_time) = all_points().fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), (t, _)| (lo.min(*t), hi.max(*t)));
+        let (min_price, max_price) = all_points().fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), (_, p)| (lo.min(*p), hi.max(*p)));
+        let padding = ((max_price - min_price) * 0.1).max(0.005);
+
+        let mut chart = ChartBuilder::on(area)
+            .caption("Price", ("sans-serif", 16))
+            .margin(10)
+            .x_label_area_size(30)
+            .y_label_area_size(50)
+            .build_cartesian_2d(min_time..max_time.max(min_time + 1.0), (min_price - padding)..(max_price + padding))?;
+        chart.configure_mesh()
+            .x_label_formatter(&|t| chrono::DateTime::from_timestamp(*t as i64, 0)
+                .map(|time| time.format("%m-%d %H:%M").to_string())
+                .unwrap_or_default())
+            .y_label_formatter(&|p| format!("{p:.3}"))
+            .draw()?;
+
+        for line in series {
+            let color = RGBColor(line.color.0, line.color.1, line.color.2);
+            chart.draw_series(LineSeries::new(line.points.iter().copied(), color.stroke_width(2)))?
+                .label(line.label.as_str())
+                .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color));
+        }
+        chart.configure_series_labels().background_style(WHITE.mix(0.8)).border_style(BLACK).draw()?;
+        Ok(())
+    }
+
+    fn draw_depth_chart(area: &Area, depth: &DepthChartData, show_notional: bool) -> Result<()> {
+        let mut chart = ChartBuilder::on(area)
+            .caption(format!("Depth - Spread {:.4}", depth.best_ask - depth.best_bid), ("sans-serif", 16))
+            .margin(10)
+            .x_label_area_size(30)
+            .y_label_area_size(50)
+            .build_cartesian_2d(depth.min_price..depth.max_price, 0.0..depth.max_depth * 1.05)?;
+        chart.configure_mesh()
+            .x_desc("Price")
+            .y_desc(if show_notional { "Depth ($)" } else { "Depth (shares)" })
+            .x_label_formatter(&|p| format!("{p:.3}"))
+            .draw()?;
+
+        let green = RGBColor(0, 160, 60);
+        let red = RGBColor(200, 40, 40);
+        chart.draw_series(LineSeries::new(depth.bid_data.iter().copied(), green.stroke_width(2)))?
+            .label("Bids")
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], green));
+        chart.draw_series(LineSeries::new(depth.ask_data.iter().copied(), red.stroke_width(2)))?
+            .label("Asks")
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], red));
+
+        // Touch markers, as in the terminal chart's legend
+        for ((price, size), color, side) in [(depth.best_bid_level, green, "Bid"), (depth.best_ask_level, red, "Ask")]
+            .into_iter()
+            .filter_map(|(level, color, side)| level.map(|level| (level, color, side)))
+            .filter(|((price, _), _, _)| (depth.min_price..=depth.max_price).contains(price))
+        {
+            chart.draw_series(LineSeries::new([(price, 0.0), (price, depth.max_depth)], color.mix(0.5)))?
+                .label(format!("{side} {price:.3} · {size:.0}"))
+                .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color.mix(0.5)));
+        }
+        chart.configure_series_labels().background_style(WHITE.mix(0.8)).border_style(BLACK).draw()?;
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "chart-export"))]
+mod render {
+    use anyhow::{anyhow, Result};
+    use std::path::Path;
+
+    use super::{DepthChartData, ExportSeries};
+
+    pub fn write_price_chart(_path: &Path, _title: &str, _series: &[ExportSeries]) -> Result<()> {
+        Err(anyhow!("built without the `chart-export` feature"))
+    }
+
+    pub fn write_orderbook_charts(_path: &Path, _title: &str, _series: &[ExportSeries], _depth: &DepthChartData, _show_notional: bool) -> Result<()> {
+        Err(anyhow!("built without the `chart-export` feature"))
+    }
+}
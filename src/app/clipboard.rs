@@ -0,0 +1,39 @@
+//! Clipboard integration for sharing the current token id and book exports
+
+use anyhow::Result;
+use cli_log::*;
+
+use super::core::App;
+
+impl App {
+    pub fn copy_current_token_id(&mut self) {
+        let token_id = match self.orderbook {
+            Some(ref orderbook) => orderbook.token_id.clone(),
+            None => return,
+        };
+
+        match copy_to_clipboard(&token_id) {
+            Ok(()) => {
+                info!("Copied token ID to clipboard: {token_id}");
+                self.set_status_message(format!("Copied token ID: {token_id}"));
+            }
+            Err(e) => {
+                // Headless or minimal builds: show the id so it can still be copied by hand
+                warn!("Clipboard unavailable: {e}");
+                self.set_status_message(format!("Token ID: {token_id}"));
+            }
+        }
+    }
+}
+
+#[cfg(feature = "clipboard")]
+pub(super) fn copy_to_clipboard(text: &str) -> Result<()> {
+    let mut clipboard = arboard::Clipboard::new()?;
+    clipboard.set_text(text.to_string())?;
+    Ok(())
+}
+
+#[cfg(not(feature = "clipboard"))]
+pub(super) fn copy_to_clipboard(_text: &str) -> Result<()> {
+    Err(anyhow::anyhow!("built without the `clipboard` feature"))
+}
@@ -1,29 +1,51 @@
 //! Core application logic and initialization
 
 use anyhow::Result;
-use polymarket_rs_client::{ClobClient, Event, GammaMarket};
+use polymarket_rs_client::{ApiCreds, ClobClient, Event, GammaMarket, OrderBookSummary};
 use rust_decimal::prelude::*;
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet, VecDeque},
     env,
+    path::PathBuf,
     sync::{Arc, Mutex},
     time::{Duration, Instant},
 };
 use cli_log::*;
 
 use crate::{
-    bot::BotEngine,
-    config::{POLYMARKET_HOST, POLYMARKET_GAMMA_HOST, POLYGON_CHAIN_ID, MAX_EVENTS, PRICE_HISTORY_UPDATE_INTERVAL_MS},
-    data::{OrderBookData, PriceHistory, SimpleOrder}, 
-    get_midpoint_from_slices
+    bot::{BotEngine, StrategyType},
+    config::{POLYGON_CHAIN_ID, DEFAULT_REQUESTS_PER_SECOND, MAX_EVENTS, EVENTS_PAGE_SIZE, BOOK_SNAPSHOT_STALE_SECS, BOOK_RESYNC_COOLDOWN_SECS, DEFAULT_BOOK_JUMP_TICKS, DEFAULT_LARGE_TRADE_SIZE, DEFAULT_HIGHLIGHT_DURATION_MS, DEFAULT_TRADE_ACTIVITY_WINDOW_SECS, DEFAULT_DEPTH_BPS, PRICE_HISTORY_UPDATE_INTERVAL_MS, DEFAULT_PRICE_HISTORY_FIDELITY, DEFAULT_HISTORY_REFRESH_SECS, CANDLE_INTERVALS_SECS, DEFAULT_MIN_LIQUIDITY, DEFAULT_RESOLUTION_WARNING_MINS, DEFAULT_DEPTH_CHART_TICKS, DEFAULT_SETTINGS_PATH, DEFAULT_KEYMAP_PATH, DEFAULT_LOG_FILE, DEFAULT_PRIVATE_KEY_ENV, DEFAULT_IDLE_TIMEOUT_SECS, DEFAULT_WS_STALE_TIMEOUT_SECS, ORDERBOOK_PRICE_HISTORY_POINTS, DEFAULT_PRICE_EPSILON, MIN_TICK_SIZE, DEFAULT_PRICE_HISTORY_WINDOW_MINS},
+    data::{diff_levels, merge_duplicate_levels, sanitize_tick_size, TokenQuote, OutcomePair, OrderBookData, PriceHistory, SimpleOrder, price_epsilon}, 
+    utils::{retry_with_backoff, RateLimiter},
 };
-use crate::websocket::{PolymarketWebSocket, PolymarketWebSocketMessage};
-use super::types::{SelectedTab, MarketSelectorTab};
+use crate::websocket::{PolymarketWebSocket, PolymarketWebSocketMessage, UserOrderMessage, UserTradeMessage};
+use super::keymap::Keymap;
+use super::market_row::MarketRowFormat;
+use super::arbitrage_scan::{ArbitrageOpportunity, ArbitrageScan};
+use super::mid_log::MidpointCsvLogger;
+use super::health::HealthState;
+use crate::ui::components::NumericInput;
+use super::market_compare::MarketComparison;
+use super::search::SearchResult;
+use crate::data_source::MarketDataSource;
+use super::types::{AppOptions, ChartKind, DepthChartMode, Endpoints, SelectedTab, MarketSelectorTab, NumberFormat, NumericInputTarget, PriceFormat, PriceHistoryRange};
+use super::search::SelectorCursor;
 
 pub struct App {
     // Core client and data
-    pub client: ClobClient,
+    pub client: Box<dyn MarketDataSource>,
+    pub endpoints: Endpoints,
+    pub rate_limiter: Arc<RateLimiter>, // Every `client` call goes through this, and so does the background arbitrage scan
+    pub api_creds: Option<ApiCreds>,
+    pub mid_logger: Option<MidpointCsvLogger>, // When set, every recomputed midpoint of the loaded book is appended
     pub orderbook: Option<OrderBookData>,
+    pub event_overview: Vec<TokenQuote>,
+    pub arbitrage_opportunities: Vec<ArbitrageOpportunity>, // Crossed events from the last dashboard scan, largest edge first
+    pub arbitrage_events_scanned: usize,
+    pub last_arbitrage_scan: Option<Instant>,
+    pub arbitrage_scan: Option<ArbitrageScan>, // Running background scan, picked up by `update` once it finishes
+    pub outcome_pair: Option<OutcomePair>,
+    pub sibling_orderbook: Option<OrderBookData>, // Other outcome of the loaded binary market, kept live alongside it
     pub markets: Vec<GammaMarket>,
     pub events: Vec<Event>,
     
@@ -40,14 +62,57 @@ pub struct App {
     pub token_scroll_offset: usize,
     pub show_market_selector: bool,
     pub show_event_market_selector: bool, // When true, shows markets within selected event
+    pub market_list_position: usize, // The market list's selection, put back when leaving an event's markets
     pub show_token_selector: bool,
+    pub show_event_overview: bool, // When true, shows top-of-book for every market in the selected event
+    pub show_arbitrage_dashboard: bool, // Periodically rescanned list of the arbitrage detector's crossed events
+    pub show_market_compare: bool, // Two markets' price histories overlaid on one chart
+    pub compare_pick: Option<(String, String)>, // (token_id, question) of the first market picked for a comparison
+    pub market_comparison: Option<MarketComparison>,
+    pub compare_normalized: bool, // Index both compared series to 100 at the start of the overlap
+    pub show_outcome_pair: bool, // When true, shows both outcomes of a binary market in the orderbook header
+    pub show_market_info: bool, // Metadata panel over the orderbook view
+    pub show_settings: bool, // Settings overlay over any view, saved when it closes
+    pub screen_dump_requested: bool, // Write the next drawn frame out as text
+    pub session_export_requested: bool, // Write a bug report bundle, screen included, after the next draw
+    pub last_input: Instant,
+    pub idle_timeout: Option<Duration>, // None keeps the full refresh rate forever
+    pub idle_dim: bool,
+    pub selected_setting: usize,
+    pub sibling_overlay: Option<(String, String)>, // (token id, sibling outcome) the implied price overlay was enabled for
     pub market_selector_tab: MarketSelectorTab, // Tracks which tab is active in market selector
+    pub market_categories: HashMap<String, Vec<usize>>, // Category -> indices into `markets`, from the filtered markets
+    pub category_names: Vec<String>, // Categories in display order, largest first and "Other" last
+    pub selected_category: usize,
+    pub show_category_markets: bool, // Categories tab lists the selected category's markets instead of the categories
+    pub selected_category_market: usize, // Position within the selected category
     pub needs_redraw: bool,
     pub selected_tab: SelectedTab,
+    pub price_format: PriceFormat,
+    pub number_format: NumberFormat, // Sizes, totals and depths: plain, comma-grouped or k/M
+    pub show_notional: bool, // Show order sizes in USDC instead of shares
+    pub show_level_age: bool, // Age column on the order tables, dimming long-resting levels
+    pub show_imbalance_sparkline: bool, // Recent top-of-book size imbalance in the market header
+    pub show_twap: bool, // Overlay a rolling TWAP on the price chart
+    pub freeze_frame: Option<OrderBookData>, // Book snapshot the order tables can be compared against
+    pub show_freeze_diff: bool, // Order tables show the change since the freeze frame instead of plain sizes
+    pub pin_price_axis: bool, // Both price charts span 0..1 instead of fitting the data
+    pub show_candles: bool, // Realtime price chart draws OHLC candles instead of the midpoint line
+    pub hidden_charts: HashSet<ChartKind>, // Left out of the orderbook view, the rest reflow into the space
+    pub candle_interval_secs: u64,
+    pub aggregation_ticks: u32, // Price bucket size in ticks for the book display, 1 = raw levels
+    pub depth_chart_ticks: u32, // Ticks spanned by the depth chart, centered on the spread
+    pub depth_chart_mode: DepthChartMode, // Cumulative envelope or the size resting at each level
     
     // Search functionality
     pub search_query: String,
+    pub unified_search: bool, // Selector search lists matching events and markets together instead of per tab
+    pub unified_results: Vec<SearchResult>, // Ranked matches while unified search is active
+    pub selected_unified_result: usize,
+    pub unified_scroll_offset: usize,
     pub search_mode: bool,
+    pub quick_search_mode: bool, // Quick-switch overlay opened from the orderbook view
+    pub quick_search_return: Option<SelectorCursor>, // Selector cursors to restore when the overlay closes
     pub error_message: Option<String>,
     pub status_message: Option<String>, // For success/info messages
     pub status_message_time: Option<Instant>, // When the status message was set
@@ -57,50 +122,156 @@ pub struct App {
     pub last_orderbook_update: Instant,
     pub update_interval: Duration,
     pub depth: usize,
+    pub max_events: usize, // Stop paginating once this many events are loaded
+    pub events_page_size: u32,
+    pub include_closed_markets: bool, // Keep closed, inactive and resolving markets when loading
+    pub keymap: Keymap,
+    pub keymap_path: PathBuf,
+    pub settings_path: PathBuf,
+    pub private_key_env: String, // Background clients like the arbitrage scan build their own from the key it holds; session exports redact it
+    pub log_file: PathBuf, // Tail included in session exports
+    pub liquid_only: bool, // Hide markets below min_liquidity in the market list
+    pub resolving_soon: bool, // Only markets ending within RESOLVING_SOON_HOURS, soonest first
+    pub resolution_warning: chrono::Duration, // Warn once when a viewed or strategy market ends within this; zero disables
+    pub resolution_warned: HashSet<(Option<StrategyType>, String)>, // (strategy, token_id) already warned, `None` for the viewed market
+    pub last_resolution_check: Instant,
+    pub min_liquidity: u32, // USDC of gamma volume, or displayed book notional when volume is missing
+    pub probed_liquidity: HashMap<String, f64>, // First token id -> book notional, 0 when the book isn't tradable
+    pub probed_spreads: HashMap<String, f64>, // First token id -> spread of its probed two-sided book
+    pub market_row_format: MarketRowFormat,
+    pub outcome_prices: HashMap<String, (TokenQuote, Instant)>, // Token id -> quote shown in the token selector, and when it was fetched
+    pub last_liquidity_probe: Instant,
+    pub hidden_illiquid_markets: usize, // Matching markets removed by the tradable-only filter
     
     // Price history data from API
     pub market_price_history: Option<polymarket_rs_client::PriceHistoryResponse>,
+    pub price_history_max_age: Option<Duration>, // Realtime chart points older than this are evicted; None keeps only the count cap
+    pub market_price_history_token: Option<String>, // Token the API price history was last fetched for
+    pub last_market_price_history_load: Instant,
+    pub market_price_history_refresh: Option<Duration>, // Refetch interval for the loaded market's API price history; None never refetches
+    pub price_history_range: PriceHistoryRange,
+    pub price_history_fidelity: u32, // Minutes per point
     pub last_price_history_update: Instant,
+    pub last_outcome_pair_update: Instant,
     pub price_history_update_interval: Duration,
     
     // WebSocket integration for real-time updates
     pub current_websocket: Option<PolymarketWebSocket>,
+    pub prewarm: bool, // Stream the selector's highlighted market in the background once the highlight settles
+    pub prewarm_token: Option<String>, // Token the pre-warmed connection streams
+    pub prewarm_websocket: Option<PolymarketWebSocket>,
+    pub prewarm_orderbook: Option<OrderBookData>, // Built from the pre-warmed stream, adopted when that market opens
+    pub prewarm_candidate: Option<(String, Instant)>, // Highlighted token and when the highlight moved to it
     pub websocket_updates: Arc<Mutex<Vec<PolymarketWebSocketMessage>>>,
+    pub unknown_ws_payloads: Arc<Mutex<VecDeque<String>>>, // Latest payloads the market channel couldn't parse
     pub last_websocket_check: Instant,
     pub websocket_reconnect_attempts: u32,
     pub last_websocket_attempt: Instant,
+    pub last_book_snapshot: Instant, // Last full book from REST or a WebSocket `book` message
+    pub book_jump_ticks: f64, // Snapshot top-of-book moves beyond this are logged and flagged; 0 disables
+    pub large_trade_size: f64, // Trades of at least this many shares flash the header; 0 disables
+    pub trade_activity_window: chrono::Duration, // Rolling window for the header's trades/min and $/min
+    pub depth_bps: f64, // Distance from the midpoint, in basis points, of the header's near-mid depth
+    pub numeric_input: Option<(NumericInputTarget, NumericInput)>, // Open number entry overlay and the value it edits
+    pub highlight_duration: Duration, // How long changed levels stay green/red
+    pub websocket_reconnecting: bool, // Market channel dropped and no fresh snapshot has arrived since
+    pub ws_stale_timeout: Option<Duration>, // A market channel silent this long falls back to REST polling; None never does
+    pub websocket_stalled: bool, // Polling REST after the market channel went silent
+    pub recovery_websocket: Option<PolymarketWebSocket>, // Replaces the stalled connection once it hears from the server
+    pub pending_book_refresh: bool, // Fetch the book over REST on the next update
+    pub last_book_resync: Instant,
+    pub health: Arc<Mutex<HealthState>>, // Heartbeat and connection state for the /healthz thread
+    
+    // Authenticated user channel for own orders and fills
+    pub user_websocket: Option<PolymarketWebSocket>,
+    pub user_channel_enabled: bool, // Subscribe to the user channel alongside the market channel when authenticated
+    pub open_orders: Vec<UserOrderMessage>,
+    pub recent_fills: Vec<UserTradeMessage>, // Newest first
     
     // Multi-crypto price tracking
     pub crypto_prices: std::collections::HashMap<crate::websocket::CryptoSymbol, Arc<Mutex<crate::data::CryptoPrice>>>,
     pub crypto_websocket_active: std::collections::HashMap<crate::websocket::CryptoSymbol, bool>,
+    pub crypto_feed: crate::websocket::CryptoFeed,
     
     // Bot engine for strategy execution
     pub bot_engine: BotEngine,
     pub show_strategy_selector: bool,
     pub show_strategy_runner: bool,
     pub selected_strategy: usize,
+    pub selected_strategy_entry: usize, // Highlighted row of the runner's selected markets/events list
     pub strategy_selection_mode: bool, // True when we're picking markets/events for a strategy
+
+    // Outcome books of the arbitrage detector's categorical markets, all on one market channel subscription
+    pub strategy_websocket: Option<PolymarketWebSocket>,
+    pub strategy_websocket_updates: Arc<Mutex<Vec<PolymarketWebSocketMessage>>>,
+    pub strategy_orderbooks: HashMap<String, OrderBookData>,
+    pub last_strategy_websocket_attempt: Instant,
 }
 
 impl App {
-    pub async fn new(interval: f64, depth: usize, private_key_env: &str) -> Result<Self> {
+    /// With `options.allow_read_only`, failing to get API credentials leaves the app on public data instead of erroring
+    pub async fn new(interval: f64, depth: usize, private_key_env: &str, options: AppOptions) -> Result<Self> {
+        let AppOptions { endpoints, allow_read_only, requests_per_second, start } = options;
         let private_key = env::var(private_key_env)
             .map_err(|_| anyhow::anyhow!(
                 "Private key not found in environment variable '{}'. Please set it in your .env file or environment.", 
                 private_key_env
             ))?;
+        // The client panics on a key it can't parse, so catch the common mistakes here
+        check_private_key_format(&private_key)
+            .map_err(|e| anyhow::anyhow!("Invalid private key in environment variable '{private_key_env}': {e}"))?;
 
-        let mut client = ClobClient::with_l1_headers(POLYMARKET_HOST, POLYMARKET_GAMMA_HOST, &private_key, POLYGON_CHAIN_ID);
+        let mut client = ClobClient::with_l1_headers(&endpoints.clob_host, &endpoints.gamma_host, &private_key, POLYGON_CHAIN_ID);
+        let rate_limiter = RateLimiter::new(requests_per_second);
         
         // Create or derive API key
         let nonce = None;
-        let keys = client.create_or_derive_api_key(nonce).await.unwrap();
+        let keys = match rate_limiter.run(client.create_or_derive_api_key(nonce)).await {
+            Ok(keys) => Some(keys),
+            Err(e) if allow_read_only => {
+                warn!("Could not create or derive an API key, continuing read-only: {e}");
+                None
+            }
+            Err(e) => return Err(anyhow::anyhow!(
+                "Could not create or derive a CLOB API key with the key in '{private_key_env}': {e}. \
+                 Check that it is the wallet's private key and that {} is reachable, \
+                 or pass --allow-read-only to continue without account data",
+                endpoints.clob_host,
+            )),
+        };
         
-        client.set_api_creds(keys);
+        if let Some(ref keys) = keys {
+            client.set_api_creds(keys.clone());
+        }
         
-        Ok(Self {
+        let mut app = Self::with_data_source(interval, depth, Box::new(client), endpoints);
+        app.rate_limiter = Arc::new(rate_limiter);
+        if keys.is_none() {
+            app.set_status_message("Read-only: no API credentials, own orders and fills are unavailable".to_string());
+        }
+        app.api_creds = keys;
+        app.private_key_env = private_key_env.to_string();
+        app.market_selector_tab = start.market_tab;
+        app.selected_tab = start.tab;
+        Ok(app)
+    }
+
+    /// An app reading from `client` with no API credentials, e.g. `MockMarketData` in tests
+    pub fn with_data_source(interval: f64, depth: usize, client: Box<dyn MarketDataSource>, endpoints: Endpoints) -> Self {
+        Self {
             client,
+            endpoints,
+            rate_limiter: Arc::new(RateLimiter::new(DEFAULT_REQUESTS_PER_SECOND)),
+            api_creds: None,
+            mid_logger: None,
             orderbook: None,
+            event_overview: Vec::new(),
+            arbitrage_opportunities: Vec::new(),
+            arbitrage_events_scanned: 0,
+            last_arbitrage_scan: None,
+            arbitrage_scan: None,
+            outcome_pair: None,
+            sibling_orderbook: None,
             markets: Vec::new(),
             events: Vec::new(),
             filtered_markets: Vec::new(),
@@ -113,46 +284,152 @@ impl App {
             token_scroll_offset: 0,
             show_market_selector: true,
             show_event_market_selector: false,
+            market_list_position: 0,
             show_token_selector: false,
+            show_event_overview: false,
+            show_arbitrage_dashboard: false,
+            show_market_compare: false,
+            compare_pick: None,
+            market_comparison: None,
+            compare_normalized: false,
+            show_outcome_pair: false,
+            show_market_info: false,
+            show_settings: false,
+            screen_dump_requested: false,
+            session_export_requested: false,
+            last_input: Instant::now(),
+            idle_timeout: Some(Duration::from_secs(DEFAULT_IDLE_TIMEOUT_SECS)),
+            idle_dim: false,
+            selected_setting: 0,
+            sibling_overlay: None,
             market_selector_tab: MarketSelectorTab::AllMarkets,
+            market_categories: HashMap::new(),
+            category_names: Vec::new(),
+            selected_category: 0,
+            show_category_markets: false,
+            selected_category_market: 0,
             last_update: Instant::now(),
             last_orderbook_update: Instant::now(),
             update_interval: Duration::from_secs_f64(interval),
             depth,
+            max_events: MAX_EVENTS,
+            events_page_size: EVENTS_PAGE_SIZE,
+            include_closed_markets: false,
+            keymap: Keymap::default(),
+            keymap_path: PathBuf::from(DEFAULT_KEYMAP_PATH),
+            settings_path: PathBuf::from(DEFAULT_SETTINGS_PATH),
+            private_key_env: DEFAULT_PRIVATE_KEY_ENV.to_string(),
+            log_file: PathBuf::from(DEFAULT_LOG_FILE),
+            liquid_only: false,
+            resolving_soon: false,
+            resolution_warning: chrono::Duration::minutes(DEFAULT_RESOLUTION_WARNING_MINS as i64),
+            resolution_warned: HashSet::new(),
+            last_resolution_check: Instant::now(),
+            min_liquidity: DEFAULT_MIN_LIQUIDITY,
+            probed_liquidity: HashMap::new(),
+            probed_spreads: HashMap::new(),
+            outcome_prices: HashMap::new(),
+            market_row_format: MarketRowFormat::default(),
+            last_liquidity_probe: Instant::now(),
+            hidden_illiquid_markets: 0,
             error_message: None,
             status_message: None,
             status_message_time: None,
             search_query: String::new(),
+            unified_search: true,
+            unified_results: Vec::new(),
+            selected_unified_result: 0,
+            unified_scroll_offset: 0,
             search_mode: false,
+            quick_search_mode: false,
+            quick_search_return: None,
             needs_redraw: true,
             selected_tab: SelectedTab::Orderbook,
+            price_format: PriceFormat::default(),
+            number_format: NumberFormat::default(),
+            show_notional: false,
+            show_level_age: false,
+            show_imbalance_sparkline: false,
+            show_twap: false,
+            freeze_frame: None,
+            show_freeze_diff: false,
+            pin_price_axis: false,
+            show_candles: false,
+            hidden_charts: HashSet::new(),
+            candle_interval_secs: CANDLE_INTERVALS_SECS[0],
+            aggregation_ticks: 1,
+            depth_chart_ticks: DEFAULT_DEPTH_CHART_TICKS,
+            depth_chart_mode: DepthChartMode::default(),
             market_price_history: None,
+            price_history_max_age: Some(Duration::from_secs(DEFAULT_PRICE_HISTORY_WINDOW_MINS * 60)),
+            market_price_history_token: None,
+            last_market_price_history_load: Instant::now(),
+            market_price_history_refresh: Some(Duration::from_secs(DEFAULT_HISTORY_REFRESH_SECS)),
+            price_history_range: PriceHistoryRange::default(),
+            price_history_fidelity: DEFAULT_PRICE_HISTORY_FIDELITY,
             current_websocket: None,
+            prewarm: false,
+            prewarm_token: None,
+            prewarm_websocket: None,
+            prewarm_orderbook: None,
+            prewarm_candidate: None,
             websocket_updates: Arc::new(Mutex::new(Vec::new())),
+            unknown_ws_payloads: Arc::new(Mutex::new(VecDeque::new())),
             last_websocket_check: Instant::now(),
             websocket_reconnect_attempts: 0,
             last_websocket_attempt: Instant::now(),
+            last_book_snapshot: Instant::now(),
+            book_jump_ticks: DEFAULT_BOOK_JUMP_TICKS,
+            large_trade_size: DEFAULT_LARGE_TRADE_SIZE,
+            trade_activity_window: chrono::Duration::seconds(DEFAULT_TRADE_ACTIVITY_WINDOW_SECS as i64),
+            depth_bps: DEFAULT_DEPTH_BPS,
+            numeric_input: None,
+            highlight_duration: Duration::from_millis(DEFAULT_HIGHLIGHT_DURATION_MS),
+            websocket_reconnecting: false,
+            ws_stale_timeout: Some(Duration::from_secs(DEFAULT_WS_STALE_TIMEOUT_SECS)),
+            websocket_stalled: false,
+            recovery_websocket: None,
+            pending_book_refresh: false,
+            last_book_resync: Instant::now(),
+            health: Arc::new(Mutex::new(HealthState::default())),
+            user_websocket: None,
+            user_channel_enabled: true,
+            open_orders: Vec::new(),
+            recent_fills: Vec::new(),
             last_price_history_update: Instant::now(),
+            last_outcome_pair_update: Instant::now(),
             price_history_update_interval: Duration::from_millis(PRICE_HISTORY_UPDATE_INTERVAL_MS),
             crypto_prices: HashMap::new(),
             crypto_websocket_active: HashMap::new(),
+            crypto_feed: crate::websocket::CryptoFeed::default(),
             
             // Bot engine
             bot_engine: BotEngine::new(),
             show_strategy_selector: false,
             show_strategy_runner: false,
             selected_strategy: 0,
+            selected_strategy_entry: 0,
             strategy_selection_mode: false,
-        })
+            strategy_websocket: None,
+            strategy_websocket_updates: Arc::new(Mutex::new(Vec::new())),
+            strategy_orderbooks: HashMap::new(),
+            last_strategy_websocket_attempt: Instant::now(),
+        }
     }
 
     pub async fn load_markets(&mut self) -> Result<()> {
+        self.load_markets_with_progress(|_| {}).await
+    }
+
+    /// Loads all events page by page, calling `on_progress` after every page so the caller can redraw
+    pub async fn load_markets_with_progress(&mut self, mut on_progress: impl FnMut(&mut App)) -> Result<()> {
         self.events.clear();
         self.markets.clear();
 
+        let page_size = self.events_page_size;
         let mut index = 0;
         loop {
-            match self.client.get_gamma_events(Some(index), Some(500)).await {
+            match self.rate_limiter.run(self.client.get_gamma_events(Some(index), Some(page_size))).await {
                 Ok(events) => {
                     // Get the length before moving the events
                     let num_events = events.len();
@@ -160,14 +437,17 @@ impl App {
                     // Extend events with the new data
                     self.events.extend(events);
                     let tot_events = self.events.len();
+
+                    self.set_status_message(format!("Loading events... {tot_events} loaded"));
+                    on_progress(self);
                     
                     // Check if we have more events to load
-                    if num_events < 500 || tot_events >= MAX_EVENTS {
+                    if num_events < page_size as usize || tot_events >= self.max_events {
                         info!("Reached end of gamma events pagination or max limit reached: {tot_events} total events loaded");
                         break;
                     }
                     
-                    index += 500; // Increment index for next batch
+                    index += page_size; // Increment index for next batch
 
                 }
                 Err(e) => {
@@ -187,7 +467,13 @@ impl App {
         // Load all markets from the events
         for event in &self.events {
             if let Some(markets) = &event.markets {
-                self.markets.extend(markets.clone());
+                // Markets often leave the end date to their event
+                self.markets.extend(markets.iter().cloned().map(|mut market| {
+                    if market.end_date.as_ref().is_none_or(|end| end.trim().is_empty()) {
+                        market.end_date = event.end_date.clone();
+                    }
+                    market
+                }));
             }
         }
 
@@ -195,6 +481,12 @@ impl App {
         self.markets.sort_by(|a, b| b.volume.unwrap_or(Decimal::ZERO)
             .partial_cmp(&a.volume.unwrap_or(Decimal::ZERO))
             .unwrap_or(std::cmp::Ordering::Equal));
+
+        // After the sort, so the highest-volume copy is the one kept
+        let duplicates = dedupe_markets(&mut self.markets);
+        if duplicates > 0 {
+            info!("Dropped {duplicates} markets listed under more than one event");
+        }
         
         // Initialize filtered markets and events with all indices
         self.update_filtered_markets();
@@ -218,11 +510,14 @@ impl App {
 
         
     pub fn prune_empty_events(&mut self) {
+        let include_closed = self.include_closed_markets;
         // Remove markets inside events that are no longer active or are closed
         self.events.retain_mut(|event| {
             if let Some(markets) = &mut event.markets {
                 // Filter out markets that are closed or inactive
-                markets.retain(|m| m.active && !m.closed && m.uma_resolution_statuses.as_ref().unwrap_or(&Vec::new()).is_empty());
+                if !include_closed {
+                    markets.retain(|m| m.active && !m.closed && m.uma_resolution_statuses.as_ref().unwrap_or(&Vec::new()).is_empty());
+                }
                 
                 // Keep the event if it has any active markets left
                 !markets.is_empty()
@@ -234,18 +529,23 @@ impl App {
 
     pub fn update_filtered_markets(&mut self) {
         self.filtered_markets.clear();
+        self.hidden_illiquid_markets = 0;
         
-        if self.search_query.is_empty() {
-            // No filter, show all markets
-            self.filtered_markets = (0..self.markets.len()).collect();
-        } else {
-            // Filter markets based on search query
-            let query = self.search_query.to_lowercase();
-            for (i, market) in self.markets.iter().enumerate() {
-                if market.question.to_lowercase().contains(&query) {
-                    self.filtered_markets.push(i);
-                }
+        let query = self.search_query.to_lowercase();
+        for (i, market) in self.markets.iter().enumerate() {
+            if !query.is_empty() && !market.question.to_lowercase().contains(&query) {
+                continue;
+            }
+            if self.liquid_only && !self.is_market_liquid(market) {
+                self.hidden_illiquid_markets += 1;
+                continue;
             }
+            self.filtered_markets.push(i);
+        }
+        if self.resolving_soon {
+            let mut filtered = std::mem::take(&mut self.filtered_markets);
+            self.filter_resolving_soon(&mut filtered);
+            self.filtered_markets = filtered;
         }
         
         // Reset selection if it's out of bounds
@@ -253,6 +553,8 @@ impl App {
             self.selected_market = 0;
             self.market_scroll_offset = 0;
         }
+        self.rebuild_market_categories();
+        self.update_unified_results();
     }
 
     pub fn update_filtered_events(&mut self) {
@@ -284,63 +586,55 @@ impl App {
             self.selected_event = 0;
             self.event_scroll_offset = 0;
         }
+        self.update_unified_results();
     }
     
-    pub async fn load_orderbook(&mut self, token_id: &str) -> Result<()> {
-        // Fetch price history for the market
-        match self.client.get_price_history(token_id, "max", 60).await {
+    /// Fetches the historical chart for the selected range and fidelity
+    pub async fn load_market_price_history(&mut self, token_id: &str) {
+        let interval = self.price_history_range.interval();
+        let fidelity = self.price_history_fidelity;
+        // Recorded up front so a failing fetch is retried on the refresh interval, not on every update
+        self.market_price_history_token = Some(token_id.to_string());
+        self.last_market_price_history_load = Instant::now();
+        let result = retry_with_backoff("Price history fetch", || {
+            self.rate_limiter.run(self.client.get_price_history(token_id, interval, fidelity))
+        }).await;
+        match result {
             Ok(price_history) => {
+                info!("Loaded {} price history points ({interval} @ {}m) for token ID: {token_id}",
+                      price_history.history.len(), self.price_history_fidelity);
                 // Store the price history for the tab display
                 self.market_price_history = Some(price_history);
-                info!("Loaded price history for token ID: {token_id}");
             }
             Err(e) => {
                 warn!("Failed to load price history: {e}");
                 self.market_price_history = None;
-                // Don't return early - continue to load orderbook
             }
         }
+        self.needs_redraw = true;
+    }
 
-        match self.client.get_order_book(token_id).await {
+    pub async fn load_orderbook(&mut self, token_id: &str) -> Result<()> {
+        // Fetch price history for a newly opened market, failures don't stop the orderbook from loading.
+        // Reloads of the same book leave it to the refresh interval.
+        if self.market_price_history_token.as_deref() != Some(token_id) {
+            self.load_market_price_history(token_id).await;
+        }
+
+        // The other outcome of a binary market is already cached and streaming, so switching needs no fetch
+        if self.swap_in_sibling_orderbook(token_id) {
+            return Ok(());
+        }
+        // Likewise for a market the selector has been streaming while it was highlighted
+        if self.swap_in_prewarmed_orderbook(token_id) {
+            self.load_sibling_orderbook_if_needed(token_id).await;
+            return Ok(());
+        }
+
+        match retry_with_backoff("Orderbook fetch", || self.rate_limiter.run(self.client.get_order_book(token_id))).await {
             Ok(book) => {
-                // Find market details
-                let market_question = self.markets
-                    .iter()
-                    .find(|m| m.token_ids.iter().any(|t| t == token_id))
-                    .and_then(|m| {
-                        m.token_ids.iter()
-                            .position(|t| t == token_id)
-                            .and_then(|index| m.outcomes.get(index))
-                            .map(|outcome| format!("{} - {}", m.question, outcome))
-                    })
-                    .unwrap_or_else(|| token_id.to_string());
-
-                // Convert API orders to simple orders
-                let mut bids = Vec::new();
-                let mut asks = Vec::new();
-                
-                // Convert bids
-                for bid in &book.bids {
-                    bids.push(SimpleOrder::new(
-                        bid.price.to_f64().unwrap_or(0.0), 
-                        bid.size.to_f64().unwrap_or(0.0)
-                    ));
-                }
-                
-                // Convert asks
-                for ask in &book.asks {
-                    asks.push(SimpleOrder::new(
-                        ask.price.to_f64().unwrap_or(0.0), 
-                        ask.size.to_f64().unwrap_or(0.0)
-                    ));
-                }
-                
-                // Sort and limit orders
-                bids.sort_by(|a, b| b.price.partial_cmp(&a.price).unwrap_or(std::cmp::Ordering::Equal));
-                bids.truncate(self.depth);
-                
-                asks.sort_by(|a, b| a.price.partial_cmp(&b.price).unwrap_or(std::cmp::Ordering::Equal));
-                asks.truncate(self.depth);
+                let market_question = self.market_question_for_token(token_id);
+                let (mut bids, mut asks) = summary_levels(&book, self.depth);
 
                 // Get tick size from API
                 let tick_size = self.get_tick_size_for_token(token_id).await;
@@ -348,31 +642,67 @@ impl App {
                 // Preserve existing price history if updating the same token
                 let price_history = if let Some(ref existing_orderbook) = self.orderbook {
                     if existing_orderbook.token_id == token_id {
-                        // Keep existing price history and add new midpoint
-                        let mut history = existing_orderbook.price_history.clone();
-                        let mid_price = get_midpoint_from_slices(&bids, &asks);
-                        history.add_price(mid_price);
-                        history
+                        // Keep existing price history, the new midpoint is recorded once the book is built
+                        existing_orderbook.price_history.clone()
                     } else {
                         // Different token, start fresh
-                        PriceHistory::new(500)
+                        self.new_price_history()
                     }
                 } else {
                     // No existing orderbook, start fresh
-                    PriceHistory::new(500)
+                    self.new_price_history()
+                };
+
+                // Keep the opening midpoint, a locked chart view, the trade tape and imbalance history while we stay on the same token
+                let (open_midpoint, chart_center_price, chart_locked, recent_trades, tape_started, trade_activity, imbalance_history) = match self.orderbook {
+                    Some(ref existing_orderbook) if existing_orderbook.token_id == token_id => (
+                        existing_orderbook.open_midpoint,
+                        existing_orderbook.chart_center_price,
+                        existing_orderbook.chart_locked,
+                        existing_orderbook.recent_trades.clone(),
+                        existing_orderbook.tape_started,
+                        existing_orderbook.trade_activity,
+                        existing_orderbook.imbalance_history.clone(),
+                    ),
+                    _ => (0.0, None, false, VecDeque::new(), chrono::Utc::now(), None, VecDeque::new()),
+                };
+                // A first load diffs against nothing, so the new market opens without highlights
+                let (previous_bids, previous_asks) = match self.orderbook {
+                    Some(ref existing_orderbook) if existing_orderbook.token_id == token_id => (&existing_orderbook.bids[..], &existing_orderbook.asks[..]),
+                    _ => (&[][..], &[][..]),
                 };
+                let epsilon = price_epsilon(tick_size);
+                let now = Instant::now();
+                diff_levels(&mut bids, previous_bids, epsilon, now, self.highlight_duration);
+                diff_levels(&mut asks, previous_asks, epsilon, now, self.highlight_duration);
 
-                self.orderbook = Some(OrderBookData {
+                let mut orderbook = OrderBookData {
                     token_id: token_id.to_string(),
                     market_question,
                     bids,
                     asks,
                     tick_size,
                     last_updated: chrono::Utc::now(),
-                    chart_center_price: None,
-                    chart_needs_recentering: true,
+                    chart_center_price,
+                    chart_needs_recentering: !chart_locked,
+                    chart_locked,
                     price_history,
-                });
+                    open_midpoint,
+                    recent_trades,
+                    tape_started,
+                    trade_activity,
+                    imbalance_history,
+                    metadata: self.market_metadata(token_id),
+                    crossed: false,
+                    snapshot_jump_at: None,
+                    large_trade: None,
+                };
+                orderbook.record_open_midpoint_if_missing();
+                orderbook.record_midpoint();
+                self.orderbook = Some(orderbook);
+                self.log_midpoint();
+                self.last_book_snapshot = Instant::now();
+                self.websocket_reconnecting = false;
                 self.error_message = None;
                 self.last_update = Instant::now();
                 self.needs_redraw = true;
@@ -383,10 +713,40 @@ impl App {
             }
         }
 
+        self.load_sibling_orderbook_if_needed(token_id).await;
         Ok(())
     }
 
+    /// "Question - Outcome" for a token, or the bare token id when it isn't in the loaded markets
+    pub fn market_question_for_token(&self, token_id: &str) -> String {
+        self.markets
+            .iter()
+            .find(|m| m.token_ids.iter().any(|t| t == token_id))
+            .and_then(|m| outcome_for_token(m, token_id).map(|outcome| format!("{} - {}", m.question, outcome)))
+            .unwrap_or_else(|| token_id.to_string())
+    }
+
+    /// Lightweight best bid/ask fetch for a token without touching the active orderbook
+    pub async fn fetch_token_quote(&self, token_id: &str, label: String) -> TokenQuote {
+        let (best_bid, best_ask) = match self.rate_limiter.run(self.client.get_order_book(token_id)).await {
+            Ok(book) => best_bid_ask(&book),
+            Err(e) => {
+                warn!("Failed to load orderbook for '{label}': {e}");
+                (0.0, 0.0)
+            }
+        };
+
+        TokenQuote {
+            label,
+            token_id: token_id.to_string(),
+            best_bid,
+            best_ask,
+        }
+    }
+
     pub async fn update(&mut self) -> Result<()> {
+        self.report_health();
+
         // Clear old status messages
         self.clear_old_status_message();
         
@@ -394,6 +754,24 @@ impl App {
         if let Err(e) = self.process_websocket_updates() {
             warn!("WebSocket update failed: {e}");
         }
+        if let Err(e) = super::websocket::process_strategy_websocket_updates(self) {
+            warn!("Strategy WebSocket update failed: {e}");
+        }
+
+        // Refresh over REST right after a dropped connection instead of waiting for the first snapshot
+        let refresh_token_id = self.orderbook.as_ref()
+            .filter(|_| self.pending_book_refresh)
+            .map(|orderbook| orderbook.token_id.clone());
+        if let Some(token_id) = refresh_token_id {
+            self.pending_book_refresh = false;
+            if let Err(e) = self.load_orderbook(&token_id).await {
+                warn!("Failed to refresh orderbook after WebSocket drop: {e}");
+            }
+        }
+
+        self.resync_stale_book_if_needed().await;
+        self.check_upcoming_resolutions_if_due();
+        self.prewarm_highlighted_market_if_settled().await;
         
         // Clean up expired highlights
         if let Some(ref mut orderbook) = self.orderbook {
@@ -418,7 +796,23 @@ impl App {
         }
 
         super::price_history::update_price_history_if_needed(self);
+        self.refresh_market_price_history_if_due().await;
         super::price_history::update_crypto_prices_if_needed(self);
+
+        if self.show_outcome_pair {
+            self.update_outcome_pair_if_needed().await;
+        }
+
+        if self.show_market_selector && self.market_selector_tab == MarketSelectorTab::AllMarkets {
+            self.probe_visible_market_liquidity_if_needed().await;
+        }
+
+        if self.show_token_selector {
+            self.refresh_outcome_prices_if_due().await;
+        }
+
+        self.scan_arbitrage_dashboard_if_due();
+        self.flush_midpoint_log_if_due();
         
         // Process orderbook with bot engine
         if let Some(ref orderbook) = self.orderbook {
@@ -430,6 +824,14 @@ impl App {
         Ok(())
     }
     
+    /// An empty realtime history bounded by the configured retention window
+    pub fn new_price_history(&self) -> PriceHistory {
+        match self.price_history_max_age {
+            Some(max_age) => PriceHistory::with_max_age(ORDERBOOK_PRICE_HISTORY_POINTS, max_age),
+            None => PriceHistory::new(ORDERBOOK_PRICE_HISTORY_POINTS),
+        }
+    }
+
     fn should_update_orderbook_via_api(&self) -> bool {
         if self.last_orderbook_update.elapsed() < self.update_interval {
             return false;
@@ -439,11 +841,39 @@ impl App {
         self.current_websocket.is_none()
     }
 
+    /// Reloads the book over REST when WebSocket deltas may have been dropped:
+    /// no full snapshot for a while, or a crossed book
+    async fn resync_stale_book_if_needed(&mut self) {
+        if self.current_websocket.is_none()
+            || self.last_book_resync.elapsed() < Duration::from_secs(BOOK_RESYNC_COOLDOWN_SECS) {
+            return;
+        }
+
+        let (token_id, crossed) = match &self.orderbook {
+            Some(orderbook) => (orderbook.token_id.clone(), orderbook.is_crossed()),
+            None => return,
+        };
+        let stale = self.last_book_snapshot.elapsed() >= Duration::from_secs(BOOK_SNAPSHOT_STALE_SECS);
+        if !crossed && !stale {
+            return;
+        }
+
+        let reason = if crossed { "crossed book" } else { "no snapshot received" };
+        info!("Resyncing orderbook for {} via REST: {reason}", crate::utils::short_id(&token_id, 12));
+        self.last_book_resync = Instant::now();
+        match self.load_orderbook(&token_id).await {
+            // A stalled stream keeps tripping this; a fresh connection usually fixes it
+            Ok(()) if stale => self.set_status_message(format!("Orderbook resynced ({reason}), W reconnects the stream")),
+            Ok(()) => self.set_status_message(format!("Orderbook resynced ({reason})")),
+            Err(e) => warn!("Orderbook resync failed: {e}"),
+        }
+    }
+
     // Helper methods that will need to be implemented
-    async fn get_tick_size_for_token(&self, token_id: &str) -> f64 {
-        match self.client.get_tick_size(token_id).await {
-            Ok(tick_size) => tick_size.to_f64().unwrap_or(0.0001),
-            Err(_) => 0.0001, // Default tick size for prediction markets
+    pub(super) async fn get_tick_size_for_token(&self, token_id: &str) -> f64 {
+        match self.rate_limiter.run(self.client.get_tick_size(token_id)).await {
+            Ok(tick_size) => sanitize_tick_size(tick_size.to_f64().unwrap_or(MIN_TICK_SIZE)),
+            Err(_) => MIN_TICK_SIZE, // Default tick size for prediction markets
         }
     }
 
@@ -456,9 +886,9 @@ impl App {
         // This will be called periodically to update price history
         if super::price_history::should_update_price_history(self) {
             if let Some(ref mut orderbook) = self.orderbook {
-                let midpoint = orderbook.get_midpoint();
-                orderbook.price_history.add_price(midpoint);
+                orderbook.record_midpoint();
                 self.last_price_history_update = Instant::now();
+                self.log_midpoint();
             }
         }
     }
@@ -469,6 +899,27 @@ impl App {
         self.needs_redraw = true;
     }
     
+    /// Low-power mode: after `idle_timeout` without a key press only the once-per-second redraw runs
+    pub fn is_idle(&self) -> bool {
+        self.idle_timeout.is_some_and(|timeout| self.last_input.elapsed() >= timeout)
+    }
+
+    /// Leaving idle redraws straight away so the screen catches up before the key is handled
+    pub fn register_input(&mut self) {
+        if self.is_idle() {
+            self.needs_redraw = true;
+        }
+        self.last_input = Instant::now();
+    }
+
+    /// Ages the trades/min readout when no trade arrives to recompute it
+    pub fn refresh_trade_activity(&mut self) {
+        let window = self.trade_activity_window;
+        if let Some(ref mut orderbook) = self.orderbook {
+            orderbook.refresh_trade_activity(window, chrono::Utc::now());
+        }
+    }
+
     pub fn clear_old_status_message(&mut self) {
         if let Some(time) = self.status_message_time {
             if time.elapsed() > Duration::from_secs(3) { // Clear after 3 seconds
@@ -479,3 +930,182 @@ impl App {
         }
     }
 }
+
+/// Keeps the first market for each set of token ids and returns how many were dropped; markets without tokens are kept
+pub fn dedupe_markets(markets: &mut Vec<GammaMarket>) -> usize {
+    let before = markets.len();
+    let mut seen = std::collections::HashSet::new();
+    markets.retain(|market| {
+        if market.token_ids.is_empty() {
+            return true;
+        }
+        let mut token_ids = market.token_ids.clone();
+        token_ids.sort();
+        seen.insert(token_ids)
+    });
+    before - markets.len()
+}
+
+/// Outcome label of one of the market's tokens. Gamma occasionally lists a different number of outcomes
+/// than tokens, and then the labels can't be trusted to line up, so the token's position is used instead.
+pub fn outcome_for_token(market: &GammaMarket, token_id: &str) -> Option<String> {
+    let index = market.token_ids.iter().position(|t| t == token_id)?;
+    if market.outcomes.len() != market.token_ids.len() {
+        warn!(
+            "Market '{}' lists {} outcomes for {} tokens, labelling token {token_id} by position",
+            market.question, market.outcomes.len(), market.token_ids.len(),
+        );
+        return Some(format!("Outcome {}", index + 1));
+    }
+    market.outcomes.get(index).cloned()
+}
+
+/// Converts a REST book into levels sorted best first, one per price, and cut to `depth` per side
+pub fn summary_levels(book: &OrderBookSummary, depth: usize) -> (Vec<SimpleOrder>, Vec<SimpleOrder>) {
+    let to_orders = |orders: &[polymarket_rs_client::OrderSummary]| -> Vec<SimpleOrder> {
+        orders.iter()
+            .map(|order| SimpleOrder::new(order.price.to_f64().unwrap_or(0.0), order.size.to_f64().unwrap_or(0.0)))
+            .collect()
+    };
+    let mut bids = to_orders(&book.bids);
+    let mut asks = to_orders(&book.asks);
+
+    bids.sort_by(|a, b| b.price.partial_cmp(&a.price).unwrap_or(std::cmp::Ordering::Equal));
+    merge_duplicate_levels(&mut bids, DEFAULT_PRICE_EPSILON);
+    bids.truncate(depth);
+    asks.sort_by(|a, b| a.price.partial_cmp(&b.price).unwrap_or(std::cmp::Ordering::Equal));
+    merge_duplicate_levels(&mut asks, DEFAULT_PRICE_EPSILON);
+    asks.truncate(depth);
+    (bids, asks)
+}
+
+/// Best bid and ask of a REST book, 0.0 for an empty side
+pub fn best_bid_ask(book: &OrderBookSummary) -> (f64, f64) {
+    let best_bid = book.bids.iter()
+        .filter_map(|b| b.price.to_f64())
+        .fold(0.0, f64::max);
+    let best_ask = book.asks.iter()
+        .filter_map(|a| a.price.to_f64())
+        .fold(f64::INFINITY, f64::min);
+    (best_bid, if best_ask.is_finite() { best_ask } else { 0.0 })
+}
+
+/// 32 bytes of hex, optionally `0x`-prefixed
+fn check_private_key_format(key: &str) -> Result<()> {
+    let hex = key.strip_prefix("0x").unwrap_or(key);
+    if hex.len() != 64 {
+        return Err(anyhow::anyhow!("expected 64 hex characters, got {}", hex.len()));
+    }
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(anyhow::anyhow!("contains characters that aren't hex digits"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+
+    #[test]
+    fn private_key_accepts_64_hex_digits_with_or_without_0x() {
+        assert!(check_private_key_format(KEY).is_ok());
+        assert!(check_private_key_format(&format!("0x{KEY}")).is_ok());
+    }
+
+    #[test]
+    fn private_key_rejects_short_keys() {
+        let error = check_private_key_format(&KEY[..40]).unwrap_err();
+        assert!(error.to_string().contains("got 40"));
+        assert!(check_private_key_format(&format!("0x{}", &KEY[..63])).is_err());
+    }
+
+    #[test]
+    fn private_key_rejects_non_hex_characters() {
+        let key = format!("{}zz", &KEY[..62]);
+        let error = check_private_key_format(&key).unwrap_err();
+        assert!(error.to_string().contains("hex digits"));
+    }
+
+    /// Hosts nothing listens on, so deriving an API key fails like it would on a network problem
+    fn offline_options(allow_read_only: bool) -> AppOptions {
+        AppOptions {
+            endpoints: Endpoints {
+                clob_host: "http://127.0.0.1:1".to_string(),
+                gamma_host: "http://127.0.0.1:1".to_string(),
+                ws_host: "ws://127.0.0.1:1".to_string(),
+            },
+            allow_read_only,
+            requests_per_second: DEFAULT_REQUESTS_PER_SECOND,
+            start: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn a_malformed_key_is_reported_before_connecting() {
+        let key_env = "POLYMARKET_TEST_MALFORMED_KEY";
+        // SAFETY: no other test touches this variable
+        unsafe { env::set_var(key_env, &KEY[..40]) };
+        let error = App::new(1.0, 10, key_env, offline_options(true)).await.err().unwrap().to_string();
+        assert!(error.contains(key_env) && error.contains("got 40"), "{error}");
+    }
+
+    #[tokio::test]
+    async fn failing_to_derive_an_api_key_points_at_read_only_mode() {
+        let key_env = "POLYMARKET_TEST_WELL_FORMED_KEY";
+        // SAFETY: no other test touches this variable
+        unsafe { env::set_var(key_env, KEY) };
+        let error = App::new(1.0, 10, key_env, offline_options(false)).await.err().unwrap().to_string();
+        assert!(error.contains("--allow-read-only"), "{error}");
+
+        let app = App::new(1.0, 10, key_env, offline_options(true)).await.unwrap();
+        assert!(app.api_creds.is_none());
+    }
+
+    #[test]
+    fn private_key_counts_digits_after_the_0x_prefix() {
+        // 64 characters including the prefix is two digits short
+        assert!(check_private_key_format(&format!("0x{}", &KEY[..62])).is_err());
+    }
+
+    fn market(question: &str, token_ids: &[&str], volume: i64) -> GammaMarket {
+        GammaMarket {
+            question: question.to_string(),
+            description: None,
+            end_date: None,
+            category: None,
+            resolution_source: None,
+            token_ids: token_ids.iter().map(|id| id.to_string()).collect(),
+            outcomes: (1..=token_ids.len()).map(|n| format!("Outcome {n}")).collect(),
+            volume: Some(Decimal::from(volume)),
+            active: true,
+            closed: false,
+            uma_resolution_statuses: None,
+        }
+    }
+
+    #[test]
+    fn market_listed_under_two_events_is_kept_once() {
+        // Sorted by volume as load_markets leaves them; the second copy lists its tokens in another order
+        let mut markets = vec![
+            market("Will it rain?", &["yes", "no"], 500),
+            market("Will it snow?", &["snow-yes", "snow-no"], 100),
+            market("Will it rain?", &["no", "yes"], 50),
+        ];
+
+        assert_eq!(dedupe_markets(&mut markets), 1);
+
+        let questions: Vec<&str> = markets.iter().map(|market| market.question.as_str()).collect();
+        assert_eq!(questions, vec!["Will it rain?", "Will it snow?"]);
+        assert_eq!(markets[0].volume, Some(Decimal::from(500)));
+    }
+
+    #[test]
+    fn markets_without_tokens_are_all_kept() {
+        let mut markets = vec![market("Pending", &[], 10), market("Pending", &[], 5)];
+
+        assert_eq!(dedupe_markets(&mut markets), 0);
+        assert_eq!(markets.len(), 2);
+    }
+}
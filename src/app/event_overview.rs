@@ -0,0 +1,56 @@
+//! Aggregated top-of-book overview for all markets within an event
+
+use anyhow::Result;
+use cli_log::*;
+
+use super::core::App;
+
+impl App {
+    pub async fn open_event_overview(&mut self) -> Result<()> {
+        self.show_event_market_selector = false;
+        self.show_event_overview = true;
+        self.needs_redraw = true;
+        self.load_event_overview().await
+    }
+
+    pub fn close_event_overview(&mut self) {
+        self.show_event_overview = false;
+        self.show_event_market_selector = true;
+        self.needs_redraw = true;
+    }
+
+    /// Fetches a fresh book for the YES token of every market in the selected event
+    pub async fn load_event_overview(&mut self) -> Result<()> {
+        self.event_overview.clear();
+
+        let markets = match self.filtered_events.get(self.selected_event) {
+            Some(&event_idx) => self.events[event_idx].markets.clone().unwrap_or_default(),
+            None => return Ok(()),
+        };
+
+        for market in &markets {
+            // The first token is the YES outcome for binary markets
+            let token_id = match market.token_ids.first() {
+                Some(token_id) => token_id.clone(),
+                None => continue,
+            };
+
+            let quote = self.fetch_token_quote(&token_id, market.question.clone()).await;
+            self.event_overview.push(quote);
+        }
+
+        info!("Loaded event overview for {} markets", self.event_overview.len());
+        self.needs_redraw = true;
+        Ok(())
+    }
+
+    /// Sum of YES midpoints, or `None` while any market is missing a two-sided book
+    pub fn event_overview_yes_sum(&self) -> Option<f64> {
+        if self.event_overview.is_empty() {
+            return None;
+        }
+        self.event_overview.iter()
+            .map(|quote| quote.get_midpoint())
+            .try_fold(0.0, |sum, mid| if mid > 0.0 { Some(sum + mid) } else { None })
+    }
+}
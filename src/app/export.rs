@@ -0,0 +1,108 @@
+//! Market catalog and orderbook exports for external tooling
+
+use anyhow::Result;
+use cli_log::*;
+use rust_decimal::prelude::*;
+use serde_json::json;
+use std::{fs, path::PathBuf};
+
+use super::clipboard::copy_to_clipboard;
+use super::core::App;
+use super::types::PriceFormat;
+use crate::config::BOOK_EXPORT_DIR;
+use crate::data::OrderBookData;
+
+impl App {
+    /// Writes every loaded market with its event to a JSON array, returning the market count
+    pub fn dump_markets(&self, path: &str) -> Result<usize> {
+        let markets: Vec<serde_json::Value> = self.events.iter()
+            .flat_map(|event| {
+                event.markets.iter().flatten().map(move |market| json!({
+                    "question": market.question,
+                    "token_ids": market.token_ids,
+                    "outcomes": market.outcomes,
+                    "volume": market.volume.and_then(|v| v.to_f64()),
+                    "active": market.active,
+                    "closed": market.closed,
+                    "event_id": event.id,
+                    "event_title": event.title,
+                }))
+            })
+            .collect();
+
+        fs::write(path, serde_json::to_string_pretty(&markets)?)?;
+        info!("Wrote {} markets to {path}", markets.len());
+        Ok(markets.len())
+    }
+
+    /// Copies the top of the book as a Markdown table, or writes it to `BOOK_EXPORT_DIR` without a clipboard
+    pub fn export_book_markdown(&mut self) {
+        let Some(ref orderbook) = self.orderbook else { return };
+        let markdown = book_to_markdown(orderbook, self.depth, self.price_format);
+
+        if copy_to_clipboard(&markdown).is_ok() {
+            info!("Copied the {} book as Markdown", orderbook.token_id);
+            self.set_status_message("Copied the book as a Markdown table".to_string());
+            return;
+        }
+        let token_id = orderbook.token_id.clone();
+        match write_book_markdown(&token_id, &markdown) {
+            Ok(path) => {
+                info!("Wrote the book as Markdown to {}", path.display());
+                self.set_status_message(format!("Book table saved to {}", path.display()));
+            }
+            Err(e) => {
+                warn!("Book Markdown export failed: {e}");
+                self.set_status_message(format!("Book export failed: {e}"));
+            }
+        }
+    }
+}
+
+/// Market question, timestamp and spread above a table of the top `depth` levels per side with cumulative notional
+pub fn book_to_markdown(orderbook: &OrderBookData, depth: usize, price_format: PriceFormat) -> String {
+    let decimal_places = orderbook.price_decimal_places();
+    let price = |price: f64| price_format.format(price, decimal_places);
+
+    let mut lines = vec![
+        format!("**{}**", orderbook.market_question),
+        String::new(),
+        format!(
+            "{} | Spread: {} | Mid: {}",
+            orderbook.last_updated.format("%Y-%m-%d %H:%M:%S UTC"),
+            if orderbook.is_crossed() { "crossed".to_string() } else { price(orderbook.get_spread()) },
+            price(orderbook.get_midpoint()),
+        ),
+        String::new(),
+        "| Bid Cum. ($) | Bid Size | Bid | Ask | Ask Size | Ask Cum. ($) |".to_string(),
+        "|---:|---:|---:|---:|---:|---:|".to_string(),
+    ];
+
+    let (mut bid_total, mut ask_total) = (0.0, 0.0);
+    let rows = orderbook.bids.len().max(orderbook.asks.len()).min(depth);
+    for level in 0..rows {
+        let side = |orders: &[crate::data::SimpleOrder], total: &mut f64| match orders.get(level) {
+            Some(order) => {
+                *total += order.price * order.size;
+                (format!("{total:.2}"), format!("{:.2}", order.size), price(order.price))
+            }
+            None => (String::new(), String::new(), String::new()),
+        };
+        let (bid_cum, bid_size, bid) = side(&orderbook.bids, &mut bid_total);
+        let (ask_cum, ask_size, ask) = side(&orderbook.asks, &mut ask_total);
+        lines.push(format!("| {bid_cum} | {bid_size} | {bid} | {ask} | {ask_size} | {ask_cum} |"));
+    }
+
+    lines.join("\n") + "\n"
+}
+
+fn write_book_markdown(token_id: &str, markdown: &str) -> Result<PathBuf> {
+    fs::create_dir_all(BOOK_EXPORT_DIR)?;
+    let path = PathBuf::from(BOOK_EXPORT_DIR).join(format!(
+        "book-{}-{}.md",
+        crate::utils::short_id(token_id, 12),
+        chrono::Utc::now().format("%Y%m%d-%H%M%S"),
+    ));
+    fs::write(&path, markdown)?;
+    Ok(path)
+}
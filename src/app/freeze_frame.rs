@@ -0,0 +1,146 @@
+//! Freeze frame: a book snapshot kept in memory, and the net change of the live book against it
+
+use cli_log::*;
+
+use super::core::App;
+use crate::config::SIZE_EPSILON;
+use crate::data::{OrderBookData, SimpleOrder};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LevelChange {
+    Appeared,
+    Changed,
+    Unchanged,
+    Gone,
+}
+
+/// One price level of the live book compared with the snapshot
+#[derive(Debug, Clone, PartialEq)]
+pub struct LevelDiff {
+    pub price: f64,
+    pub size: f64, // Live size, 0 for a level that is gone
+    pub size_change: f64, // Live size minus snapshot size
+    pub change: LevelChange,
+}
+
+/// Every level on either side of the comparison, best price first (`descending` for bids). Prices within
+/// `epsilon` are one level. A snapshot level is only gone when it lies within the live side's price range;
+/// beyond the worst live level it may just have dropped out of the displayed depth, so it is left out.
+pub fn diff_book_side(live: &[SimpleOrder], snapshot: &[SimpleOrder], descending: bool, epsilon: f64) -> Vec<LevelDiff> {
+    let at = |levels: &[SimpleOrder], price: f64| {
+        levels.iter().find(|level| (level.price - price).abs() < epsilon).map(|level| level.size)
+    };
+    let live_range = live.iter().map(|level| level.price).fold(None, |range: Option<(f64, f64)>, price| {
+        Some(range.map_or((price, price), |(low, high)| (low.min(price), high.max(price))))
+    });
+    // An emptied side has no depth cut-off hiding anything, so all of its snapshot levels are gone
+    let in_live_range = |price: f64| live_range.is_none_or(|(low, high)| price > low - epsilon && price < high + epsilon);
+
+    let mut diffs: Vec<LevelDiff> = live.iter().map(|level| match at(snapshot, level.price) {
+        Some(before) => LevelDiff {
+            price: level.price,
+            size: level.size,
+            size_change: level.size - before,
+            change: if (level.size - before).abs() < SIZE_EPSILON { LevelChange::Unchanged } else { LevelChange::Changed },
+        },
+        None => LevelDiff { price: level.price, size: level.size, size_change: level.size, change: LevelChange::Appeared },
+    }).collect();
+    diffs.extend(snapshot.iter()
+        .filter(|level| at(live, level.price).is_none() && in_live_range(level.price))
+        .map(|level| LevelDiff { price: level.price, size: 0.0, size_change: -level.size, change: LevelChange::Gone }));
+
+    diffs.sort_by(|a, b| if descending { b.price.total_cmp(&a.price) } else { a.price.total_cmp(&b.price) });
+    diffs
+}
+
+impl App {
+    /// Replaces any earlier snapshot; the live view carries on until the diff is shown
+    pub fn freeze_book(&mut self) {
+        let Some(orderbook) = self.orderbook.clone() else { return };
+        info!("Froze book for {} at {}", orderbook.token_id, orderbook.last_updated);
+        self.set_status_message(format!("Book frozen at {} - Z shows changes since", orderbook.last_updated.format("%H:%M:%S")));
+        self.freeze_frame = Some(orderbook);
+        self.needs_redraw = true;
+    }
+
+    pub fn toggle_freeze_diff(&mut self) {
+        if self.freeze_frame_for_loaded_book().is_none() {
+            self.set_status_message("No frozen book for this market - press z first".to_string());
+            return;
+        }
+        self.show_freeze_diff = !self.show_freeze_diff;
+        self.needs_redraw = true;
+    }
+
+    pub fn clear_freeze_frame(&mut self) {
+        if self.freeze_frame.take().is_some() {
+            self.set_status_message("Frozen book cleared".to_string());
+        }
+        self.show_freeze_diff = false;
+        self.needs_redraw = true;
+    }
+
+    /// The snapshot, if it was taken of the book currently loaded
+    pub fn freeze_frame_for_loaded_book(&self) -> Option<&OrderBookData> {
+        let loaded = self.orderbook.as_ref()?;
+        self.freeze_frame.as_ref().filter(|frozen| frozen.token_id == loaded.token_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f64 = 0.005; // Half of a 0.01 tick
+
+    fn levels(levels: &[(f64, f64)]) -> Vec<SimpleOrder> {
+        levels.iter().map(|&(price, size)| SimpleOrder::new(price, size)).collect()
+    }
+
+    fn changes(diffs: &[LevelDiff]) -> Vec<(f64, LevelChange)> {
+        diffs.iter().map(|diff| (diff.price, diff.change)).collect()
+    }
+
+    #[test]
+    fn levels_are_matched_within_the_epsilon() {
+        let diffs = diff_book_side(&levels(&[(0.501, 10.0)]), &levels(&[(0.50, 10.0)]), true, EPSILON);
+        assert_eq!(changes(&diffs), vec![(0.501, LevelChange::Unchanged)]);
+    }
+
+    #[test]
+    fn a_size_change_is_not_judged_by_the_price_epsilon() {
+        // 0.001 shares is well under half a tick, but still a different size
+        let diffs = diff_book_side(&levels(&[(0.50, 10.001)]), &levels(&[(0.50, 10.0)]), true, EPSILON);
+        assert_eq!(diffs[0].change, LevelChange::Changed);
+        assert!((diffs[0].size_change - 0.001).abs() < 1e-9);
+    }
+
+    #[test]
+    fn new_and_removed_levels_inside_the_live_range() {
+        let live = levels(&[(0.50, 10.0), (0.48, 5.0), (0.46, 7.0)]);
+        let snapshot = levels(&[(0.50, 10.0), (0.47, 3.0), (0.46, 7.0)]);
+        let diffs = diff_book_side(&live, &snapshot, true, EPSILON);
+        assert_eq!(changes(&diffs), vec![
+            (0.50, LevelChange::Unchanged),
+            (0.48, LevelChange::Appeared),
+            (0.47, LevelChange::Gone),
+            (0.46, LevelChange::Unchanged),
+        ]);
+        assert_eq!(diffs[2].size_change, -3.0);
+    }
+
+    #[test]
+    fn snapshot_levels_past_the_displayed_depth_are_not_gone() {
+        // The live side was cut at 0.48; 0.45 may still be resting below it
+        let live = levels(&[(0.50, 10.0), (0.48, 5.0)]);
+        let snapshot = levels(&[(0.50, 10.0), (0.48, 5.0), (0.45, 9.0)]);
+        let diffs = diff_book_side(&live, &snapshot, true, EPSILON);
+        assert_eq!(changes(&diffs), vec![(0.50, LevelChange::Unchanged), (0.48, LevelChange::Unchanged)]);
+    }
+
+    #[test]
+    fn an_emptied_side_is_all_gone() {
+        let diffs = diff_book_side(&[], &levels(&[(0.52, 4.0), (0.55, 6.0)]), false, EPSILON);
+        assert_eq!(changes(&diffs), vec![(0.52, LevelChange::Gone), (0.55, LevelChange::Gone)]);
+    }
+}
@@ -0,0 +1,200 @@
+//! `/healthz` liveness endpoint, for running the viewer under a supervisor or in a container
+
+use anyhow::Result;
+use cli_log::*;
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::{IpAddr, TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use super::core::App;
+use crate::config::{HEALTH_HEARTBEAT_TIMEOUT_SECS, HEALTH_WS_DOWN_TIMEOUT_SECS};
+
+/// What the main loop last reported, read by the HTTP thread
+#[derive(Debug, Clone, Copy)]
+pub struct HealthState {
+    pub started: Instant,
+    pub heartbeat: Instant, // Last `App::update`
+    pub market_loaded: bool, // No market open means no connection is expected
+    pub connected: bool,
+    pub last_message_age: Option<Duration>, // Silence of the market channel, pongs included
+    pub down_since: Option<Instant>, // When the market channel was last seen going away
+}
+
+impl Default for HealthState {
+    fn default() -> Self {
+        Self {
+            started: Instant::now(),
+            heartbeat: Instant::now(),
+            market_loaded: false,
+            connected: false,
+            last_message_age: None,
+            down_since: None,
+        }
+    }
+}
+
+impl HealthState {
+    fn main_loop_alive(&self) -> bool {
+        self.heartbeat.elapsed() < Duration::from_secs(HEALTH_HEARTBEAT_TIMEOUT_SECS)
+    }
+
+    /// Unhealthy once the main loop stops or an open market's connection has been down too long
+    pub fn is_healthy(&self) -> bool {
+        let websocket_down_too_long = self.down_since
+            .is_some_and(|since| since.elapsed() >= Duration::from_secs(HEALTH_WS_DOWN_TIMEOUT_SECS));
+        self.main_loop_alive() && !websocket_down_too_long
+    }
+
+    /// Starts the down clock when an open market loses its connection and keeps it running until it is back
+    pub fn record(&mut self, market_loaded: bool, connected: bool, last_message_age: Option<Duration>) {
+        self.heartbeat = Instant::now();
+        self.market_loaded = market_loaded;
+        self.connected = connected;
+        self.last_message_age = last_message_age;
+        self.down_since = match (market_loaded && !connected, self.down_since) {
+            (true, Some(since)) => Some(since),
+            (true, None) => Some(Instant::now()),
+            (false, _) => None,
+        };
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        let websocket = match (self.market_loaded, self.connected) {
+            (false, _) => "idle",
+            (true, true) => "connected",
+            (true, false) => "disconnected",
+        };
+        serde_json::json!({
+            "status": if self.is_healthy() { "ok" } else { "unhealthy" },
+            "uptime_secs": self.started.elapsed().as_secs(),
+            "websocket": websocket,
+            "last_message_age_secs": self.last_message_age.map(|age| age.as_secs_f64()),
+            "websocket_down_secs": self.down_since.map(|since| since.elapsed().as_secs()),
+            "main_loop_alive": self.main_loop_alive(),
+            "heartbeat_age_secs": self.heartbeat.elapsed().as_secs_f64(),
+        })
+    }
+}
+
+/// Serves `/healthz` on its own thread, so a stuck main loop still gets an answer (a 503)
+pub fn start_health_server(bind: IpAddr, port: u16, health: Arc<Mutex<HealthState>>) -> Result<()> {
+    let listener = TcpListener::bind((bind, port))?;
+    info!("Health endpoint listening on http://{}/healthz", listener.local_addr()?);
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    if let Err(e) = answer_health_request(stream, &health) {
+                        debug!("Health request failed: {e}");
+                    }
+                }
+                Err(e) => warn!("Health endpoint accept failed: {e}"),
+            }
+        }
+    });
+    Ok(())
+}
+
+fn answer_health_request(mut stream: TcpStream, health: &Mutex<HealthState>) -> Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let mut request_line = String::new();
+    BufReader::new(&stream).read_line(&mut request_line)?;
+    let path = request_line.split_whitespace().nth(1).unwrap_or("");
+
+    let (status, body) = if path == "/healthz" {
+        // A poisoned lock means the main loop panicked while holding it
+        match health.lock() {
+            Ok(state) if state.is_healthy() => ("200 OK", state.to_json().to_string()),
+            Ok(state) => ("503 Service Unavailable", state.to_json().to_string()),
+            Err(_) => ("503 Service Unavailable", r#"{"status":"unhealthy"}"#.to_string()),
+        }
+    } else {
+        ("404 Not Found", r#"{"error":"not found"}"#.to_string())
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len(),
+    )?;
+    Ok(())
+}
+
+impl App {
+    /// Called from every `update`, which doubles as the main loop's heartbeat
+    pub fn report_health(&mut self) {
+        let market_loaded = self.orderbook.is_some();
+        let live = self.current_websocket.as_ref().filter(|ws| !ws.thread_handle.is_finished());
+        let connected = live.is_some() && !self.websocket_stalled;
+        let last_message_age = live.map(|ws| ws.silent_for());
+
+        let Ok(mut health) = self.health.lock() else { return };
+        health.record(market_loaded, connected, last_message_age);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ago(secs: u64) -> Instant {
+        Instant::now() - Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn a_stalled_main_loop_is_unhealthy() {
+        let mut health = HealthState::default();
+        assert!(health.is_healthy());
+        health.heartbeat = ago(HEALTH_HEARTBEAT_TIMEOUT_SECS - 1);
+        assert!(health.is_healthy());
+        health.heartbeat = ago(HEALTH_HEARTBEAT_TIMEOUT_SECS);
+        assert!(!health.is_healthy());
+        assert_eq!(health.to_json()["status"], "unhealthy");
+    }
+
+    #[test]
+    fn a_websocket_down_past_the_threshold_is_unhealthy() {
+        let mut health = HealthState { down_since: Some(ago(HEALTH_WS_DOWN_TIMEOUT_SECS - 1)), ..Default::default() };
+        assert!(health.is_healthy());
+        health.down_since = Some(ago(HEALTH_WS_DOWN_TIMEOUT_SECS));
+        assert!(!health.is_healthy());
+    }
+
+    #[test]
+    fn the_down_clock_runs_from_the_first_disconnected_report() {
+        let mut health = HealthState::default();
+        health.record(true, false, None);
+        let since = health.down_since.expect("an open market without a connection is down");
+
+        health.record(true, false, None);
+        assert_eq!(health.down_since, Some(since), "later reports keep the original start");
+
+        health.record(true, true, Some(Duration::from_millis(200)));
+        assert!(health.down_since.is_none());
+    }
+
+    #[test]
+    fn no_open_market_is_never_down() {
+        let mut health = HealthState::default();
+        health.record(false, false, None);
+        assert!(health.down_since.is_none());
+        assert_eq!(health.to_json()["websocket"], "idle");
+    }
+
+    #[test]
+    fn the_server_answers_on_the_address_it_was_given() {
+        let health = Arc::new(Mutex::new(HealthState::default()));
+        // Port 0 lets the OS pick; find a free one first so the request knows where to go
+        let port = TcpListener::bind(("127.0.0.1", 0)).unwrap().local_addr().unwrap().port();
+        start_health_server(IpAddr::from([127, 0, 0, 1]), port, health).unwrap();
+
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        write!(stream, "GET /healthz HTTP/1.1\r\n\r\n").unwrap();
+        let mut status = String::new();
+        BufReader::new(&stream).read_line(&mut status).unwrap();
+        assert_eq!(status.trim_end(), "HTTP/1.1 200 OK");
+    }
+}
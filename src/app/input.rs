@@ -1,69 +1,111 @@
 use anyhow::Result;
 use crossterm::event::KeyCode;
 use crate::App;
-use super::MarketSelectorTab;
+use super::{keymap::Action, price_history::next_fidelity, ChartKind, MarketSelectorTab, NumericInputTarget, SelectedTab};
+use crate::config::DEPTH_CHART_PAN_TICKS;
+use crate::ui::components::{NumericInput, NumericInputEvent};
 
 impl App {
     pub async fn handle_key_input(&mut self, key_code: KeyCode) -> Result<bool> {
-        match key_code {
-            KeyCode::Char('q') => {
-                if self.search_mode {
-                    self.add_search_char('q');
-                } else {
-                    return Ok(false); // Exit
+        // An open number entry takes every key, unmapped, until it is applied or cancelled
+        if let Some((target, input)) = self.numeric_input.as_mut() {
+            let target = *target;
+            match input.handle_key(key_code) {
+                NumericInputEvent::Editing => {}
+                NumericInputEvent::Submitted(value) => {
+                    self.numeric_input = None;
+                    self.apply_numeric_input(target, value);
                 }
+                NumericInputEvent::Cancelled => self.numeric_input = None,
             }
-            KeyCode::Char('m') => {
-                if self.search_mode {
-                    self.add_search_char('m');
-                } else {
-                    self.show_market_selector = true;
-                    self.show_token_selector = false;
-                    self.search_mode = false;
-                    self.needs_redraw = true;
-                }
+            self.needs_redraw = true;
+            return Ok(true);
+        }
+
+        // Typed characters always go to the search query, whatever they are bound to
+        if let (true, KeyCode::Char(ch)) = (self.search_mode, key_code) {
+            self.add_search_char(ch);
+            return Ok(true);
+        }
+
+        let Some(action) = self.keymap.action_for(key_code) else {
+            return Ok(true);
+        };
+
+        // The settings overlay takes every key until it closes
+        if self.show_settings {
+            match action {
+                Action::Quit => return Ok(false),
+                Action::Up => self.move_settings_selection(false),
+                Action::Down => self.move_settings_selection(true),
+                Action::PreviousTab => self.adjust_selected_setting(false).await,
+                Action::NextTab | Action::Select => self.adjust_selected_setting(true).await,
+                Action::ResetSettings => self.reset_settings().await,
+                Action::Settings | Action::Cancel => self.toggle_settings(),
+                Action::ScreenDump => self.request_screen_dump(),
+                Action::ExportSession => self.request_session_export(),
+                _ => {}
             }
-            KeyCode::Char('r') => {
-                if self.search_mode {
-                    self.add_search_char('r');
+            return Ok(true);
+        }
+
+        match action {
+            Action::Quit => return Ok(false), // Exit
+            Action::MarketSelector => {
+                self.show_market_selector = true;
+                self.show_token_selector = false;
+                self.show_arbitrage_dashboard = false;
+                self.show_market_compare = false;
+                self.search_mode = false;
+                self.select_loaded_market_in_selector();
+                self.needs_redraw = true;
+            }
+            Action::Refresh => {
+                if self.show_event_overview {
+                    self.load_event_overview().await?;
+                } else if self.show_arbitrage_dashboard {
+                    self.start_arbitrage_scan();
+                } else if self.show_market_compare {
+                    self.reload_market_comparison().await;
                 } else if let Some(ref orderbook) = self.orderbook {
                     let token_id = orderbook.token_id.clone();
                     self.load_orderbook(&token_id).await?;
                     self.needs_redraw = true;
                 }
             }
-            KeyCode::Left | KeyCode::Char('h') => {
-                self.handle_left_navigation(key_code);
-            }
-            KeyCode::Right | KeyCode::Char('l') => {
-                self.handle_right_navigation(key_code);
-            }
-            KeyCode::Char('/') => {
-                if self.search_mode {
-                    self.add_search_char('/');
-                } else if self.show_market_selector {
+            Action::PreviousTab => self.handle_left_navigation(key_code),
+            Action::NextTab => self.handle_right_navigation(key_code),
+            Action::Search => {
+                if self.show_market_selector {
                     self.toggle_search_mode();
+                } else if !self.show_event_market_selector && !self.show_token_selector
+                    && !self.show_strategy_runner && !self.show_event_overview && !self.show_arbitrage_dashboard
+                    && !self.show_market_compare {
+                    // Quick-switch search from the orderbook view
+                    self.open_quick_search();
                 }
             }
-            KeyCode::Esc => {
-                if self.search_mode {
+            Action::Cancel => {
+                if self.quick_search_mode {
+                    self.close_quick_search();
+                } else if self.search_mode {
                     self.toggle_search_mode();
+                } else if self.show_market_info {
+                    self.toggle_market_info();
                 }
             }
-            KeyCode::Up => self.handle_up_navigation(),
-            KeyCode::Down => self.handle_down_navigation(),
-            KeyCode::PageUp => self.handle_page_up(),
-            KeyCode::PageDown => self.handle_page_down(),
-            KeyCode::Enter => {
+            Action::Up => self.handle_up_navigation(),
+            Action::Down => self.handle_down_navigation(),
+            Action::PageUp => self.handle_page_up(),
+            Action::PageDown => self.handle_page_down(),
+            Action::Select => {
                 self.handle_enter_selection().await?;
             }
-            KeyCode::Backspace => {
+            Action::Back => {
                 self.handle_backspace();
             }
-            KeyCode::Char('s') => {
-                if self.search_mode {
-                    self.add_search_char('s');
-                } else if self.show_strategy_runner {
+            Action::Strategies => {
+                if self.show_strategy_runner {
                     // Start/stop strategy
                     if let Some(strategy_type) = self.get_current_strategy_type() {
                         if let Some(strategy) = self.bot_engine.get_strategy(&strategy_type) {
@@ -81,10 +123,8 @@ impl App {
                     self.needs_redraw = true;
                 }
             }
-            KeyCode::Char('a') => {
-                if self.search_mode {
-                    self.add_search_char('a');
-                } else if self.show_strategy_runner {
+            Action::AddToStrategy => {
+                if self.show_strategy_runner {
                     // Add current market/event to strategy based on strategy scope
                     if let Some(strategy_type) = self.get_current_strategy_type() {
                         match strategy_type.scope() {
@@ -103,29 +143,143 @@ impl App {
                     self.add_current_event_to_strategy();
                 }
             }
-            KeyCode::Char('p') => {
-                if self.search_mode {
-                    self.add_search_char('p');
-                } else if self.show_strategy_runner {
+            Action::RemoveFromStrategy => {
+                if self.show_strategy_runner {
+                    self.remove_selected_strategy_entry();
+                }
+            }
+            Action::PickStrategyMarkets => {
+                if self.show_strategy_runner {
                     // Pick markets/events for strategy
                     self.show_strategy_market_selector();
                 }
             }
-            KeyCode::Char(ch) => {
-                if self.search_mode && !matches!(ch, 'q' | 'm' | 'r' | '/' | 's' | 'a') {
-                    self.add_search_char(ch);
+            Action::EventOverview => {
+                if self.show_event_market_selector {
+                    // Open the aggregated orderbook overview for the selected event
+                    self.open_event_overview().await?;
+                }
+            }
+            Action::ArbitrageDashboard => {
+                if self.show_market_selector && self.market_selector_tab != MarketSelectorTab::Strategies
+                    && !self.strategy_selection_mode {
+                    self.open_arbitrage_dashboard();
+                }
+            }
+            Action::CompareMarkets => {
+                if self.show_market_compare {
+                    self.toggle_compare_normalized();
+                } else if self.show_market_selector && !self.strategy_selection_mode {
+                    self.pick_market_for_comparison().await;
+                }
+            }
+            Action::ToggleStrategyEnabled => {
+                if self.show_market_selector && self.market_selector_tab == MarketSelectorTab::Strategies
+                    && !self.strategy_selection_mode {
+                    self.toggle_current_strategy_enabled();
                 }
             }
-            _ => {}
+            Action::HistoryRange => {
+                if self.show_market_compare {
+                    self.price_history_range = self.price_history_range.next();
+                    self.reload_market_comparison().await;
+                } else if self.is_price_history_tab_active() {
+                    self.cycle_price_history_range().await;
+                }
+            }
+            Action::HistoryFidelity => {
+                if self.show_market_compare {
+                    self.price_history_fidelity = next_fidelity(self.price_history_fidelity);
+                    self.reload_market_comparison().await;
+                } else if self.is_price_history_tab_active() {
+                    self.cycle_price_history_fidelity().await;
+                }
+            }
+            Action::LiquidOnly | Action::RaiseMinLiquidity | Action::LowerMinLiquidity | Action::ResolvingSoon => {
+                if self.show_market_selector && self.market_selector_tab == MarketSelectorTab::AllMarkets {
+                    match action {
+                        Action::LiquidOnly => self.toggle_liquid_only(),
+                        Action::ResolvingSoon => self.toggle_resolving_soon(),
+                        Action::RaiseMinLiquidity => self.adjust_min_liquidity(true),
+                        _ => self.adjust_min_liquidity(false),
+                    }
+                }
+            }
+            Action::Settings => self.toggle_settings(),
+            Action::ScreenDump => self.request_screen_dump(),
+            Action::ExportSession => self.request_session_export(),
+            Action::ResetSettings => {} // Only inside the settings overlay
+            // The remaining actions only apply to the orderbook view
+            _ if self.show_market_selector || self.show_event_market_selector || self.show_token_selector
+                || self.show_arbitrage_dashboard || self.show_market_compare => {}
+            Action::PriceFormat => self.cycle_price_format(),
+            Action::CopyTokenId => self.copy_current_token_id(),
+            Action::CopyBookMarkdown => self.export_book_markdown(),
+            Action::Aggregation => self.cycle_aggregation(),
+            Action::SiblingOverlay => self.toggle_sibling_overlay(),
+            Action::Notional => self.toggle_notional(),
+            Action::LockDepthChart => self.toggle_depth_chart_lock(),
+            Action::OutcomePair => self.toggle_outcome_pair().await,
+            Action::MarketInfo => self.toggle_market_info(),
+            Action::Twap => self.toggle_twap(),
+            Action::PinPriceAxis => self.toggle_pin_price_axis(),
+            Action::Candles => self.toggle_candles(),
+            Action::ToggleCryptoCharts => self.toggle_chart(ChartKind::Crypto),
+            Action::TogglePriceChart => self.toggle_chart(ChartKind::PriceHistory),
+            Action::ToggleDepthChart => self.toggle_chart(ChartKind::Depth),
+            Action::DepthChartMode => self.cycle_depth_chart_mode(),
+            Action::EditDepthBps => self.open_numeric_input(NumericInputTarget::DepthBps),
+            Action::FreezeBook => self.freeze_book(),
+            Action::FreezeDiff => self.toggle_freeze_diff(),
+            Action::ClearFreezeFrame => self.clear_freeze_frame(),
+            Action::LevelAge => self.toggle_level_age(),
+            Action::ImbalanceSparkline => self.toggle_imbalance_sparkline(),
+            Action::ZoomInDepthChart => self.zoom_depth_chart(true),
+            Action::ZoomOutDepthChart => self.zoom_depth_chart(false),
+            Action::ExportChart => self.export_current_chart(),
+            Action::Reconnect => self.force_reconnect_websocket(),
+            Action::ToggleUserChannel => self.toggle_user_channel(),
         }
         Ok(true) // Continue running
     }
 
-    fn handle_left_navigation(&mut self, key_code: KeyCode) {
-        if self.search_mode {
-            if matches!(key_code, KeyCode::Char('h')) {
-                self.add_search_char('h');
+    /// Opens the number entry overlay on the target's current value
+    pub fn open_numeric_input(&mut self, target: NumericInputTarget) {
+        let input = match target {
+            NumericInputTarget::DepthBps => NumericInput::new("Near-mid depth window (bps)", self.depth_bps, 1.0, 10_000.0, 1),
+        };
+        self.numeric_input = Some((target, input));
+        self.needs_redraw = true;
+    }
+
+    fn apply_numeric_input(&mut self, target: NumericInputTarget, value: f64) {
+        match target {
+            NumericInputTarget::DepthBps => {
+                self.depth_bps = value;
+                self.set_status_message(format!("Near-mid depth window: ±{value}bps"));
             }
+        }
+    }
+
+    fn is_price_history_tab_active(&self) -> bool {
+        !self.show_market_selector && !self.show_event_market_selector && !self.show_token_selector
+            && self.selected_tab == SelectedTab::PriceHistory
+    }
+
+    /// Arrow keys pan a locked depth chart; other tab keys keep switching tabs
+    fn is_panning_depth_chart(&self, key_code: KeyCode) -> bool {
+        matches!(key_code, KeyCode::Left | KeyCode::Right)
+            && !self.search_mode
+            && !self.show_market_selector && !self.show_event_market_selector && !self.show_token_selector
+            && self.selected_tab == SelectedTab::Orderbook
+            && self.is_depth_chart_locked()
+    }
+
+    fn handle_left_navigation(&mut self, key_code: KeyCode) {
+        if self.is_panning_depth_chart(key_code) {
+            self.pan_depth_chart(-DEPTH_CHART_PAN_TICKS);
+        } else if self.search_mode {
+            // Typed characters never get here; arrows do nothing while searching
         } else if self.show_market_selector {
             self.previous_market_selector_tab();
         } else if !self.show_market_selector && !self.show_event_market_selector && !self.show_token_selector {
@@ -134,10 +288,10 @@ impl App {
     }
 
     fn handle_right_navigation(&mut self, key_code: KeyCode) {
-        if self.search_mode {
-            if matches!(key_code, KeyCode::Char('l')) {
-                self.add_search_char('l');
-            }
+        if self.is_panning_depth_chart(key_code) {
+            self.pan_depth_chart(DEPTH_CHART_PAN_TICKS);
+        } else if self.search_mode {
+            // Typed characters never get here; arrows do nothing while searching
         } else if self.show_market_selector {
             self.next_market_selector_tab();
         } else if !self.show_market_selector && !self.show_event_market_selector && !self.show_token_selector {
@@ -146,10 +300,15 @@ impl App {
     }
 
     fn handle_up_navigation(&mut self) {
-        if self.show_market_selector {
+        if self.is_unified_search_active() {
+            self.move_unified_selection(-1);
+        } else if self.quick_search_mode {
+            self.previous_market();
+        } else if self.show_market_selector {
             match self.market_selector_tab {
                 MarketSelectorTab::AllMarkets => self.previous_market(),
                 MarketSelectorTab::Events => self.previous_event(),
+                MarketSelectorTab::Categories => self.move_category_selection(-1),
                 MarketSelectorTab::Strategies => self.previous_strategy(),
             }
         } else if self.show_event_market_selector {
@@ -160,14 +319,21 @@ impl App {
             } else {
                 self.previous_token();
             }
+        } else if self.show_strategy_runner {
+            self.move_strategy_entry_selection(false);
         }
     }
 
     fn handle_down_navigation(&mut self) {
-        if self.show_market_selector {
+        if self.is_unified_search_active() {
+            self.move_unified_selection(1);
+        } else if self.quick_search_mode {
+            self.next_market();
+        } else if self.show_market_selector {
             match self.market_selector_tab {
                 MarketSelectorTab::AllMarkets => self.next_market(),
                 MarketSelectorTab::Events => self.next_event(),
+                MarketSelectorTab::Categories => self.move_category_selection(1),
                 MarketSelectorTab::Strategies => self.next_strategy(),
             }
         } else if self.show_event_market_selector {
@@ -178,14 +344,21 @@ impl App {
             } else {
                 self.next_token();
             }
+        } else if self.show_strategy_runner {
+            self.move_strategy_entry_selection(true);
         }
     }
 
     fn handle_page_up(&mut self) {
-        if self.show_market_selector {
+        if self.is_unified_search_active() {
+            self.move_unified_selection(-10);
+        } else if self.quick_search_mode {
+            self.page_up_markets();
+        } else if self.show_market_selector {
             match self.market_selector_tab {
                 MarketSelectorTab::AllMarkets => self.page_up_markets(),
                 MarketSelectorTab::Events => self.page_up_events(),
+                MarketSelectorTab::Categories => self.page_category_selection(false),
                 MarketSelectorTab::Strategies => {}, // No pagination for strategies
             }
         } else if self.show_token_selector {
@@ -194,10 +367,15 @@ impl App {
     }
 
     fn handle_page_down(&mut self) {
-        if self.show_market_selector {
+        if self.is_unified_search_active() {
+            self.move_unified_selection(10);
+        } else if self.quick_search_mode {
+            self.page_down_markets();
+        } else if self.show_market_selector {
             match self.market_selector_tab {
                 MarketSelectorTab::AllMarkets => self.page_down_markets(),
                 MarketSelectorTab::Events => self.page_down_events(),
+                MarketSelectorTab::Categories => self.page_category_selection(true),
                 MarketSelectorTab::Strategies => {}, // No pagination for strategies
             }
         } else if self.show_token_selector {
@@ -206,10 +384,15 @@ impl App {
     }
 
     async fn handle_enter_selection(&mut self) -> Result<()> {
-        if self.show_market_selector {
+        if self.is_unified_search_active() {
+            self.select_unified_result().await?;
+        } else if self.quick_search_mode {
+            self.select_quick_search_market().await?;
+        } else if self.show_market_selector {
             match self.market_selector_tab {
                 MarketSelectorTab::AllMarkets => self.select_market(),
                 MarketSelectorTab::Events => self.select_event(),
+                MarketSelectorTab::Categories => self.select_category(),
                 MarketSelectorTab::Strategies => self.select_strategy(),
             }
         } else if self.show_event_market_selector {
@@ -248,6 +431,15 @@ impl App {
             self.show_strategy_selector = false;
             self.market_selector_tab = MarketSelectorTab::AllMarkets;
             self.needs_redraw = true;
+        } else if self.show_market_selector && self.market_selector_tab == MarketSelectorTab::Categories
+            && self.show_category_markets && !self.search_mode {
+            self.close_category();
+        } else if self.show_event_overview {
+            self.close_event_overview();
+        } else if self.show_arbitrage_dashboard {
+            self.close_arbitrage_dashboard();
+        } else if self.show_market_compare {
+            self.close_market_comparison();
         } else if self.show_token_selector {
             if self.market_selector_tab == MarketSelectorTab::Events {
                 self.show_event_market_selector = true;
@@ -260,6 +452,7 @@ impl App {
         } else if self.show_event_market_selector {
             self.show_market_selector = true;
             self.show_event_market_selector = false;
+            self.selected_market = self.market_list_position;
             self.needs_redraw = true;
         } else if self.search_mode {
             self.remove_search_char();
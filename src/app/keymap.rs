@@ -0,0 +1,405 @@
+//! Remappable keybindings
+
+use anyhow::{anyhow, Result};
+use cli_log::*;
+use crossterm::event::KeyCode;
+use std::{collections::HashMap, fs, path::Path};
+use crate::App;
+
+/// Everything a key can trigger; what it does may still depend on the current view
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Quit,
+    MarketSelector,
+    Refresh,
+    PreviousTab,
+    NextTab,
+    Search,
+    Cancel,
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    Select,
+    Back,
+    Strategies,
+    AddToStrategy,
+    PickStrategyMarkets,
+    ToggleStrategyEnabled,
+    EventOverview,
+    PriceFormat,
+    CopyTokenId,
+    Aggregation,
+    SiblingOverlay,
+    Notional,
+    HistoryRange,
+    HistoryFidelity,
+    LockDepthChart,
+    OutcomePair,
+    LiquidOnly,
+    RaiseMinLiquidity,
+    LowerMinLiquidity,
+    MarketInfo,
+    Twap,
+    ZoomInDepthChart,
+    ZoomOutDepthChart,
+    ExportChart,
+    Settings,
+    ResetSettings,
+    LevelAge,
+    ScreenDump,
+    ResolvingSoon,
+    RemoveFromStrategy,
+    CopyBookMarkdown,
+    ArbitrageDashboard,
+    Reconnect,
+    ImbalanceSparkline,
+    CompareMarkets,
+    ExportSession,
+    PinPriceAxis,
+    FreezeBook,
+    FreezeDiff,
+    ClearFreezeFrame,
+    ToggleUserChannel,
+    Candles,
+    ToggleCryptoCharts,
+    TogglePriceChart,
+    ToggleDepthChart,
+    DepthChartMode,
+    EditDepthBps,
+}
+
+impl Action {
+    pub fn all() -> &'static [Action] {
+        &[
+            Self::Quit, Self::MarketSelector, Self::Refresh, Self::PreviousTab, Self::NextTab,
+            Self::Search, Self::Cancel, Self::Up, Self::Down, Self::PageUp, Self::PageDown,
+            Self::Select, Self::Back, Self::Strategies, Self::AddToStrategy, Self::PickStrategyMarkets,
+            Self::ToggleStrategyEnabled, Self::EventOverview, Self::PriceFormat, Self::CopyTokenId,
+            Self::Aggregation, Self::SiblingOverlay, Self::Notional, Self::HistoryRange,
+            Self::HistoryFidelity, Self::LockDepthChart, Self::OutcomePair, Self::LiquidOnly,
+            Self::RaiseMinLiquidity, Self::LowerMinLiquidity, Self::MarketInfo,
+            Self::Twap, Self::ZoomInDepthChart, Self::ZoomOutDepthChart,
+            Self::ExportChart, Self::Settings, Self::ResetSettings,
+            Self::LevelAge, Self::ScreenDump, Self::ResolvingSoon,
+            Self::RemoveFromStrategy, Self::CopyBookMarkdown, Self::ArbitrageDashboard,
+            Self::Reconnect, Self::ImbalanceSparkline, Self::CompareMarkets, Self::ExportSession, Self::PinPriceAxis,
+            Self::FreezeBook, Self::FreezeDiff, Self::ClearFreezeFrame, Self::ToggleUserChannel, Self::Candles,
+            Self::ToggleCryptoCharts, Self::TogglePriceChart, Self::ToggleDepthChart, Self::DepthChartMode, Self::EditDepthBps,
+        ]
+    }
+
+    /// Name used in the keymap file
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Quit => "quit",
+            Self::MarketSelector => "market_selector",
+            Self::Refresh => "refresh",
+            Self::PreviousTab => "previous_tab",
+            Self::NextTab => "next_tab",
+            Self::Search => "search",
+            Self::Cancel => "cancel",
+            Self::Up => "up",
+            Self::Down => "down",
+            Self::PageUp => "page_up",
+            Self::PageDown => "page_down",
+            Self::Select => "select",
+            Self::Back => "back",
+            Self::Strategies => "strategies",
+            Self::AddToStrategy => "add_to_strategy",
+            Self::PickStrategyMarkets => "pick_strategy_markets",
+            Self::ToggleStrategyEnabled => "toggle_strategy_enabled",
+            Self::EventOverview => "event_overview",
+            Self::PriceFormat => "price_format",
+            Self::CopyTokenId => "copy_token_id",
+            Self::Aggregation => "aggregation",
+            Self::SiblingOverlay => "sibling_overlay",
+            Self::Notional => "notional",
+            Self::HistoryRange => "history_range",
+            Self::HistoryFidelity => "history_fidelity",
+            Self::LockDepthChart => "lock_depth_chart",
+            Self::OutcomePair => "outcome_pair",
+            Self::LiquidOnly => "liquid_only",
+            Self::RaiseMinLiquidity => "raise_min_liquidity",
+            Self::LowerMinLiquidity => "lower_min_liquidity",
+            Self::MarketInfo => "market_info",
+            Self::Twap => "twap",
+            Self::ZoomInDepthChart => "zoom_in_depth_chart",
+            Self::ZoomOutDepthChart => "zoom_out_depth_chart",
+            Self::ExportChart => "export_chart",
+            Self::Settings => "settings",
+            Self::ResetSettings => "reset_settings",
+            Self::LevelAge => "level_age",
+            Self::ScreenDump => "screen_dump",
+            Self::ResolvingSoon => "resolving_soon",
+            Self::RemoveFromStrategy => "remove_from_strategy",
+            Self::CopyBookMarkdown => "copy_book_markdown",
+            Self::ArbitrageDashboard => "arbitrage_dashboard",
+            Self::Reconnect => "reconnect",
+            Self::ImbalanceSparkline => "imbalance_sparkline",
+            Self::CompareMarkets => "compare_markets",
+            Self::ExportSession => "export_session",
+            Self::PinPriceAxis => "pin_price_axis",
+            Self::FreezeBook => "freeze_book",
+            Self::FreezeDiff => "freeze_diff",
+            Self::ClearFreezeFrame => "clear_freeze_frame",
+            Self::ToggleUserChannel => "toggle_user_channel",
+            Self::Candles => "candles",
+            Self::ToggleCryptoCharts => "toggle_crypto_charts",
+            Self::TogglePriceChart => "toggle_price_chart",
+            Self::ToggleDepthChart => "toggle_depth_chart",
+            Self::DepthChartMode => "depth_chart_mode",
+            Self::EditDepthBps => "edit_depth_bps",
+        }
+    }
+
+    fn default_keys(self) -> Vec<KeyCode> {
+        match self {
+            Self::Quit => vec![KeyCode::Char('q')],
+            Self::MarketSelector => vec![KeyCode::Char('m')],
+            Self::Refresh => vec![KeyCode::Char('r')],
+            Self::PreviousTab => vec![KeyCode::Left, KeyCode::Char('h')],
+            Self::NextTab => vec![KeyCode::Right, KeyCode::Char('l')],
+            Self::Search => vec![KeyCode::Char('/')],
+            Self::Cancel => vec![KeyCode::Esc],
+            Self::Up => vec![KeyCode::Up],
+            Self::Down => vec![KeyCode::Down],
+            Self::PageUp => vec![KeyCode::PageUp],
+            Self::PageDown => vec![KeyCode::PageDown],
+            Self::Select => vec![KeyCode::Enter],
+            Self::Back => vec![KeyCode::Backspace],
+            Self::Strategies => vec![KeyCode::Char('s')],
+            Self::AddToStrategy => vec![KeyCode::Char('a')],
+            Self::PickStrategyMarkets => vec![KeyCode::Char('p')],
+            Self::ToggleStrategyEnabled => vec![KeyCode::Char('e')],
+            Self::EventOverview => vec![KeyCode::Char('o')],
+            Self::PriceFormat => vec![KeyCode::Char('f')],
+            Self::CopyTokenId => vec![KeyCode::Char('y')],
+            Self::Aggregation => vec![KeyCode::Char('x')],
+            Self::SiblingOverlay => vec![KeyCode::Char('v')],
+            Self::Notional => vec![KeyCode::Char('n')],
+            Self::HistoryRange => vec![KeyCode::Char('t')],
+            Self::HistoryFidelity => vec![KeyCode::Char('g')],
+            Self::LockDepthChart => vec![KeyCode::Char('c')],
+            Self::OutcomePair => vec![KeyCode::Char('b')],
+            Self::LiquidOnly => vec![KeyCode::Char('L')],
+            Self::RaiseMinLiquidity => vec![KeyCode::Char('+')],
+            Self::LowerMinLiquidity => vec![KeyCode::Char('-')],
+            Self::MarketInfo => vec![KeyCode::Char('i')],
+            Self::Twap => vec![KeyCode::Char('w')],
+            Self::ZoomInDepthChart => vec![KeyCode::Char('[')],
+            Self::ZoomOutDepthChart => vec![KeyCode::Char(']')],
+            Self::ExportChart => vec![KeyCode::Char('E')],
+            Self::Settings => vec![KeyCode::Char(',')],
+            Self::ResetSettings => vec![KeyCode::Char('R')],
+            Self::LevelAge => vec![KeyCode::Char('A')],
+            Self::ScreenDump => vec![KeyCode::Char('S')],
+            Self::ResolvingSoon => vec![KeyCode::Char('d')],
+            Self::RemoveFromStrategy => vec![KeyCode::Char('D'), KeyCode::Delete],
+            Self::CopyBookMarkdown => vec![KeyCode::Char('M')],
+            Self::ArbitrageDashboard => vec![KeyCode::Char('G')],
+            Self::Reconnect => vec![KeyCode::Char('W')],
+            Self::ImbalanceSparkline => vec![KeyCode::Char('I')],
+            Self::CompareMarkets => vec![KeyCode::Char('C')],
+            Self::ExportSession => vec![KeyCode::Char('X')],
+            Self::PinPriceAxis => vec![KeyCode::Char('P')],
+            Self::FreezeBook => vec![KeyCode::Char('z')],
+            Self::FreezeDiff => vec![KeyCode::Char('Z')],
+            Self::ClearFreezeFrame => vec![KeyCode::Char('u')],
+            Self::ToggleUserChannel => vec![KeyCode::Char('U')],
+            Self::Candles => vec![KeyCode::Char('K')],
+            Self::ToggleCryptoCharts => vec![KeyCode::Char('1')],
+            Self::TogglePriceChart => vec![KeyCode::Char('2')],
+            Self::ToggleDepthChart => vec![KeyCode::Char('3')],
+            Self::DepthChartMode => vec![KeyCode::Char('V')],
+            Self::EditDepthBps => vec![KeyCode::Char('B')],
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: HashMap<KeyCode, Action>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let bindings = Action::all().iter()
+            .flat_map(|&action| action.default_keys().into_iter().map(move |key| (key, action)))
+            .collect();
+        Self { bindings }
+    }
+}
+
+impl Keymap {
+    pub fn action_for(&self, key_code: KeyCode) -> Option<Action> {
+        self.bindings.get(&key_code).copied()
+    }
+
+    /// Loads overrides from a JSON object of `"action": "key"` or `"action": ["key", ...]`.
+    /// Actions missing from the file keep their default keys. A missing file gives the defaults.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let overrides: HashMap<String, serde_json::Value> = serde_json::from_str(&fs::read_to_string(path)?)
+            .map_err(|e| anyhow!("Invalid keymap {}: {e}", path.display()))?;
+        let mut keys_by_action: HashMap<Action, Vec<KeyCode>> = Action::all().iter()
+            .map(|&action| (action, action.default_keys()))
+            .collect();
+
+        let mut problems = Vec::new();
+        for (name, value) in overrides {
+            let Some(&action) = Action::all().iter().find(|action| action.name() == name) else {
+                problems.push(format!("unknown action '{name}'"));
+                continue;
+            };
+            let key_names: Vec<String> = match value {
+                serde_json::Value::String(key) => vec![key],
+                serde_json::Value::Array(keys) if keys.iter().all(|key| key.is_string()) => keys.into_iter()
+                    .filter_map(|key| key.as_str().map(str::to_string))
+                    .collect(),
+                _ => {
+                    problems.push(format!("'{name}' must be a key or a list of keys"));
+                    continue;
+                }
+            };
+            let mut keys = Vec::new();
+            for key_name in key_names {
+                match parse_key(&key_name) {
+                    Some(key) => keys.push(key),
+                    None => problems.push(format!("unknown key '{key_name}' for '{name}'")),
+                }
+            }
+            keys_by_action.insert(action, keys);
+        }
+
+        // Every key may only trigger one action
+        let mut bindings: HashMap<KeyCode, Action> = HashMap::new();
+        for &action in Action::all() {
+            for &key in &keys_by_action[&action] {
+                if let Some(existing) = bindings.insert(key, action) {
+                    problems.push(format!("'{}' is bound to both '{}' and '{}'", key_label(key), existing.name(), action.name()));
+                }
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(Self { bindings })
+        } else {
+            Err(anyhow!("Invalid keymap {}: {}", path.display(), problems.join("; ")))
+        }
+    }
+}
+
+impl App {
+    /// Applies the keymap file, keeping the default bindings if it is invalid
+    pub fn load_keymap(&mut self, path: &str) {
+        self.keymap_path = path.into();
+        match Keymap::load(path) {
+            Ok(keymap) => self.keymap = keymap,
+            Err(e) => {
+                warn!("{e}; using default keybindings");
+                self.error_message = Some(format!("{e} (using default keybindings)"));
+            }
+        }
+    }
+}
+
+fn parse_key(name: &str) -> Option<KeyCode> {
+    let mut chars = name.chars();
+    if let (Some(ch), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(ch));
+    }
+    match name.to_lowercase().as_str() {
+        "left" => Some(KeyCode::Left),
+        "right" => Some(KeyCode::Right),
+        "up" => Some(KeyCode::Up),
+        "down" => Some(KeyCode::Down),
+        "pageup" => Some(KeyCode::PageUp),
+        "pagedown" => Some(KeyCode::PageDown),
+        "home" => Some(KeyCode::Home),
+        "end" => Some(KeyCode::End),
+        "enter" => Some(KeyCode::Enter),
+        "esc" => Some(KeyCode::Esc),
+        "backspace" => Some(KeyCode::Backspace),
+        "tab" => Some(KeyCode::Tab),
+        "delete" => Some(KeyCode::Delete),
+        "space" => Some(KeyCode::Char(' ')),
+        other => other.strip_prefix('f')
+            .and_then(|n| n.parse::<u8>().ok())
+            .filter(|n| (1..=12).contains(n))
+            .map(KeyCode::F),
+    }
+}
+
+fn key_label(key: KeyCode) -> String {
+    match key {
+        KeyCode::Char(' ') => "space".to_string(),
+        KeyCode::Char(ch) => ch.to_string(),
+        KeyCode::F(n) => format!("F{n}"),
+        other => format!("{other:?}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Writes `json` to a keymap file of its own and loads it
+    fn load(name: &str, json: &str) -> Result<Keymap> {
+        let path = std::env::temp_dir().join(format!("polymarket-keymap-{}-{name}.json", std::process::id()));
+        fs::write(&path, json).unwrap();
+        let keymap = Keymap::load(&path);
+        let _ = fs::remove_file(&path);
+        keymap
+    }
+
+    #[test]
+    fn default_keys_are_unique() {
+        let mut seen: HashMap<KeyCode, Action> = HashMap::new();
+        for &action in Action::all() {
+            for key in action.default_keys() {
+                if let Some(existing) = seen.insert(key, action) {
+                    panic!("'{}' is a default key of both '{}' and '{}'", key_label(key), existing.name(), action.name());
+                }
+            }
+        }
+        assert_eq!(Keymap::default().bindings.len(), seen.len());
+    }
+
+    #[test]
+    fn override_replaces_the_default_keys() {
+        let keymap = load("override", r#"{ "quit": ["Q", "f10"] }"#).unwrap();
+
+        assert_eq!(keymap.action_for(KeyCode::Char('Q')), Some(Action::Quit));
+        assert_eq!(keymap.action_for(KeyCode::F(10)), Some(Action::Quit));
+        assert_eq!(keymap.action_for(KeyCode::Char('q')), None);
+        assert_eq!(keymap.action_for(KeyCode::Char('m')), Some(Action::MarketSelector));
+    }
+
+    #[test]
+    fn conflicting_keys_are_reported() {
+        let error = load("conflict", r#"{ "quit": "m" }"#).unwrap_err().to_string();
+
+        assert!(error.contains("'m' is bound to both 'quit' and 'market_selector'"), "{error}");
+    }
+
+    #[test]
+    fn unknown_actions_and_keys_are_reported_together() {
+        let error = load("unknown", r#"{ "fly": "q", "quit": "ctrl+q", "refresh": 5 }"#).unwrap_err().to_string();
+
+        assert!(error.contains("unknown action 'fly'"), "{error}");
+        assert!(error.contains("unknown key 'ctrl+q' for 'quit'"), "{error}");
+        assert!(error.contains("'refresh' must be a key or a list of keys"), "{error}");
+    }
+
+    #[test]
+    fn missing_file_gives_the_defaults() {
+        let keymap = Keymap::load(std::env::temp_dir().join("polymarket-keymap-does-not-exist.json")).unwrap();
+
+        assert_eq!(keymap.action_for(KeyCode::Char('q')), Some(Action::Quit));
+    }
+}
@@ -0,0 +1,112 @@
+//! "Only markets I can trade" filter for the market list
+
+use cli_log::*;
+use polymarket_rs_client::GammaMarket;
+use rust_decimal::prelude::*;
+use std::time::{Duration, Instant};
+
+use super::core::App;
+use crate::config::{MIN_LIQUIDITY_THRESHOLDS, LIQUID_MAX_SPREAD, LIQUIDITY_PROBE_BATCH, LIQUIDITY_PROBE_INTERVAL_MS, LIQUIDITY_PROBE_WINDOW};
+
+impl App {
+    pub fn toggle_liquid_only(&mut self) {
+        self.liquid_only = !self.liquid_only;
+        self.refilter_markets_keeping_selection();
+        let state = if self.liquid_only { "on" } else { "off" };
+        self.set_status_message(format!("Tradable-only filter {state} (min ${})", self.min_liquidity));
+    }
+
+    /// Steps the threshold through `MIN_LIQUIDITY_THRESHOLDS`, clamping at either end
+    pub fn adjust_min_liquidity(&mut self, raise: bool) {
+        let current = MIN_LIQUIDITY_THRESHOLDS.iter()
+            .position(|&threshold| threshold == self.min_liquidity)
+            .unwrap_or(0);
+        let next = if raise {
+            (current + 1).min(MIN_LIQUIDITY_THRESHOLDS.len() - 1)
+        } else {
+            current.saturating_sub(1)
+        };
+        self.min_liquidity = MIN_LIQUIDITY_THRESHOLDS[next];
+        if self.liquid_only {
+            self.refilter_markets_keeping_selection();
+        }
+        self.set_status_message(format!("Minimum liquidity: ${}", self.min_liquidity));
+    }
+
+    /// Volume from gamma when it reports one, else the displayed book notional if it was probed
+    fn market_liquidity(&self, market: &GammaMarket) -> Option<f64> {
+        market.volume
+            .and_then(|volume| volume.to_f64())
+            .or_else(|| market.token_ids.first().and_then(|token_id| self.probed_liquidity.get(token_id).copied()))
+    }
+
+    /// Markets with no volume that haven't been probed yet stay visible until their book is fetched
+    pub fn is_market_liquid(&self, market: &GammaMarket) -> bool {
+        self.market_liquidity(market)
+            .is_none_or(|liquidity| liquidity >= self.min_liquidity as f64)
+    }
+
+    /// Fetches books for markets near the cursor that gamma reports no volume for,
+    /// or for every one of them when the market list shows spreads
+    pub async fn probe_visible_market_liquidity_if_needed(&mut self) {
+        let wants_spread = self.market_row_format.uses_spread();
+        if !(self.liquid_only || wants_spread)
+            || self.last_liquidity_probe.elapsed() < Duration::from_millis(LIQUIDITY_PROBE_INTERVAL_MS)
+        {
+            return;
+        }
+        self.last_liquidity_probe = Instant::now();
+
+        let token_ids: Vec<String> = self.filtered_markets.iter()
+            .skip(self.selected_market)
+            .take(LIQUIDITY_PROBE_WINDOW)
+            .map(|&market_idx| &self.markets[market_idx])
+            .filter(|market| market.volume.is_none() || wants_spread)
+            .filter_map(|market| market.token_ids.first())
+            .filter(|token_id| !self.probed_liquidity.contains_key(*token_id))
+            .take(LIQUIDITY_PROBE_BATCH)
+            .cloned()
+            .collect();
+        if token_ids.is_empty() {
+            return;
+        }
+
+        for token_id in token_ids {
+            match self.rate_limiter.run(self.client.get_order_book(&token_id)).await {
+                Ok(book) => {
+                    let best_bid = book.bids.iter().map(|order| order.price).max();
+                    let best_ask = book.asks.iter().map(|order| order.price).min();
+                    if let (Some(bid), Some(ask)) = (best_bid, best_ask) {
+                        self.probed_spreads.insert(token_id.clone(), (ask - bid).to_f64().unwrap_or(0.0));
+                    }
+                    // A one-sided book or a wide spread can't be traded regardless of size
+                    let tradable = matches!((best_bid, best_ask),
+                        (Some(bid), Some(ask)) if (ask - bid).to_f64().unwrap_or(f64::MAX) <= LIQUID_MAX_SPREAD);
+                    let notional = if tradable {
+                        book.bids.iter().chain(&book.asks)
+                            .map(|order| (order.price * order.size).to_f64().unwrap_or(0.0))
+                            .sum()
+                    } else {
+                        0.0
+                    };
+                    self.probed_liquidity.insert(token_id, notional);
+                }
+                Err(e) => warn!("Failed to probe liquidity for {token_id}: {e}"),
+            }
+        }
+
+        self.refilter_markets_keeping_selection();
+    }
+
+    /// Reapplies the market filters without moving the cursor off the selected market
+    pub fn refilter_markets_keeping_selection(&mut self) {
+        let selected_market_idx = self.filtered_markets.get(self.selected_market).copied();
+        self.update_filtered_markets();
+        if let Some(position) = selected_market_idx
+            .and_then(|market_idx| self.filtered_markets.iter().position(|&idx| idx == market_idx))
+        {
+            self.selected_market = position;
+        }
+        self.needs_redraw = true;
+    }
+}
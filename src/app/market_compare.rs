@@ -0,0 +1,119 @@
+//! Two markets' API price histories on one chart, for eyeballing correlated markets
+
+use cli_log::*;
+
+use super::{core::App, MarketSelectorTab};
+use crate::utils::retry_with_backoff;
+
+/// One side of a comparison: the YES token of a market and its history as `(unix seconds, price)`
+#[derive(Debug, Clone)]
+pub struct ComparedMarket {
+    pub question: String,
+    pub token_id: String,
+    pub points: Vec<(f64, f64)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MarketComparison {
+    pub first: ComparedMarket,
+    pub second: ComparedMarket, // Both are clipped to the time range the two histories share
+}
+
+/// Keeps only the points inside the time range both series cover; no overlap leaves both empty
+pub fn clip_to_overlap(first: &mut Vec<(f64, f64)>, second: &mut Vec<(f64, f64)>) {
+    let range = |points: &[(f64, f64)]| Some((points.first()?.0, points.last()?.0));
+    let (Some((first_start, first_end)), Some((second_start, second_end))) = (range(first), range(second)) else {
+        first.clear();
+        second.clear();
+        return;
+    };
+    let (start, end) = (first_start.max(second_start), first_end.min(second_end));
+    first.retain(|(t, _)| (start..=end).contains(t));
+    second.retain(|(t, _)| (start..=end).contains(t));
+}
+
+/// Rebases a series so its first price reads 100; a series starting at zero can't be indexed
+pub fn index_to_100(points: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    match points.first() {
+        Some(&(_, base)) if base > 0.0 => points.iter().map(|&(t, p)| (t, p / base * 100.0)).collect(),
+        _ => Vec::new(),
+    }
+}
+
+impl App {
+    /// First press picks the highlighted market, a press on a second market opens the comparison
+    pub async fn pick_market_for_comparison(&mut self) {
+        if self.market_selector_tab != MarketSelectorTab::AllMarkets || self.selected_market >= self.filtered_markets.len() {
+            return;
+        }
+        let market = &self.markets[self.filtered_markets[self.selected_market]];
+        let Some(token_id) = market.token_ids.first().filter(|token_id| !token_id.is_empty()).cloned() else {
+            self.set_status_message("This market has no token to compare".to_string());
+            return;
+        };
+        let question = market.question.clone();
+
+        match self.compare_pick.take() {
+            None => {
+                self.set_status_message(format!("Comparing \"{question}\" - press C on a second market"));
+                self.compare_pick = Some((token_id, question));
+            }
+            Some((picked, _)) if picked == token_id => {
+                self.set_status_message("Comparison cancelled".to_string());
+            }
+            Some((first_token, first_question)) => {
+                let first = ComparedMarket { question: first_question, token_id: first_token, points: Vec::new() };
+                let second = ComparedMarket { question, token_id, points: Vec::new() };
+                self.market_comparison = Some(MarketComparison { first, second });
+                self.show_market_selector = false;
+                self.show_market_compare = true;
+                self.search_mode = false;
+                self.reload_market_comparison().await;
+            }
+        }
+    }
+
+    /// Fetches both histories for the current range and fidelity, the same selection as the price history tab
+    pub async fn reload_market_comparison(&mut self) {
+        let Some(comparison) = self.market_comparison.as_ref() else { return };
+        let token_ids = [comparison.first.token_id.clone(), comparison.second.token_id.clone()];
+        let interval = self.price_history_range.interval();
+        let fidelity = self.price_history_fidelity;
+
+        let mut histories = Vec::with_capacity(token_ids.len());
+        for token_id in &token_ids {
+            let result = retry_with_backoff("Price history fetch", || {
+                self.rate_limiter.run(self.client.get_price_history(token_id, interval, fidelity))
+            }).await;
+            match result {
+                Ok(history) => histories.push(history.history.iter().map(|point| (point.t as f64, point.p)).collect::<Vec<_>>()),
+                Err(e) => {
+                    warn!("Failed to load price history for comparison: {e}");
+                    self.error_message = Some(format!("Failed to load price history: {e}"));
+                    histories.push(Vec::new());
+                }
+            }
+        }
+
+        let (mut first, mut second) = (histories.remove(0), histories.remove(0));
+        clip_to_overlap(&mut first, &mut second);
+        info!("Comparison ({interval} @ {fidelity}m): {} and {} overlapping points", first.len(), second.len());
+        if let Some(comparison) = self.market_comparison.as_mut() {
+            comparison.first.points = first;
+            comparison.second.points = second;
+        }
+        self.needs_redraw = true;
+    }
+
+    pub fn toggle_compare_normalized(&mut self) {
+        self.compare_normalized = !self.compare_normalized;
+        self.needs_redraw = true;
+    }
+
+    pub fn close_market_comparison(&mut self) {
+        self.show_market_compare = false;
+        self.market_comparison = None;
+        self.show_market_selector = true;
+        self.needs_redraw = true;
+    }
+}
@@ -0,0 +1,32 @@
+//! Metadata panel for the loaded market
+
+use super::core::{outcome_for_token, App};
+use crate::data::MarketMetadata;
+
+impl App {
+    pub fn toggle_market_info(&mut self) {
+        self.show_market_info = !self.show_market_info;
+        self.needs_redraw = true;
+    }
+
+    /// Collects the gamma fields for a token, falling back to its event where the market leaves one out
+    pub fn market_metadata(&self, token_id: &str) -> Option<MarketMetadata> {
+        let market = self.find_market_for_token(token_id)?;
+        let event = self.events.iter().find(|event| {
+            event.markets.as_ref()
+                .is_some_and(|markets| markets.iter().any(|m| m.token_ids.iter().any(|t| t == token_id)))
+        });
+        let non_empty = |value: &Option<String>| value.as_ref().filter(|v| !v.trim().is_empty()).cloned();
+
+        Some(MarketMetadata {
+            question: market.question.clone(),
+            outcome: outcome_for_token(market, token_id),
+            event_title: event.map(|event| event.title.clone()),
+            category: non_empty(&market.category).or_else(|| event.and_then(|event| non_empty(&event.category))),
+            end_date: non_empty(&market.end_date).or_else(|| event.and_then(|event| non_empty(&event.end_date))),
+            resolution_source: non_empty(&market.resolution_source),
+            description: non_empty(&market.description)
+                .or_else(|| event.map(|event| event.description.clone()).filter(|d| !d.trim().is_empty())),
+        })
+    }
+}
@@ -0,0 +1,186 @@
+//! User-configurable row template for the market list
+
+use polymarket_rs_client::GammaMarket;
+use rust_decimal::prelude::*;
+
+use super::{core::App, resolving_soon::market_end_time, types::NumberFormat};
+
+#[derive(Debug, Clone, PartialEq)]
+enum RowPart {
+    Text(String),
+    Question,
+    Outcomes,
+    Volume,
+    Spread,
+    End,
+}
+
+/// A template such as `{question} ({outcomes} outcomes) {volume}`; `{{` and `}}` are literal braces
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarketRowFormat {
+    parts: Vec<RowPart>,
+}
+
+impl Default for MarketRowFormat {
+    fn default() -> Self {
+        Self { parts: vec![RowPart::Question] }
+    }
+}
+
+impl std::str::FromStr for MarketRowFormat {
+    type Err = String;
+
+    fn from_str(template: &str) -> Result<Self, Self::Err> {
+        let mut parts = Vec::new();
+        let mut text = String::new();
+        let mut chars = template.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => return Err("unclosed '{', write '{{' for a literal brace".to_string()),
+                        }
+                    }
+                    let part = match name.trim() {
+                        "question" => RowPart::Question,
+                        "outcomes" => RowPart::Outcomes,
+                        "volume" => RowPart::Volume,
+                        "spread" => RowPart::Spread,
+                        "end" => RowPart::End,
+                        other => return Err(format!(
+                            "unknown field '{{{other}}}', expected one of: {{question}}, {{outcomes}}, {{volume}}, {{spread}}, {{end}}"
+                        )),
+                    };
+                    if !text.is_empty() {
+                        parts.push(RowPart::Text(std::mem::take(&mut text)));
+                    }
+                    parts.push(part);
+                }
+                '}' => return Err("unmatched '}', write '}}' for a literal brace".to_string()),
+                _ => text.push(c),
+            }
+        }
+        if !text.is_empty() {
+            parts.push(RowPart::Text(text));
+        }
+        Ok(Self { parts })
+    }
+}
+
+impl MarketRowFormat {
+    /// Fields without data (no volume, a spread that hasn't been seen) render as "-"; volume follows `number_format`
+    pub fn render(&self, market: &GammaMarket, spread: Option<f64>, number_format: NumberFormat) -> String {
+        self.parts.iter().map(|part| match part {
+            RowPart::Text(text) => text.clone(),
+            RowPart::Question => market.question.clone(),
+            RowPart::Outcomes => market.outcomes.len().max(market.token_ids.len()).to_string(),
+            RowPart::Volume => market.volume.and_then(|volume| volume.to_f64()).map_or("-".to_string(), |volume| format!("${}", number_format.format(volume, 0))),
+            RowPart::Spread => spread.map_or("-".to_string(), |spread| format!("{:.1}¢", spread * 100.0)),
+            RowPart::End => market_end_time(market).map_or("-".to_string(), |end| end.format("%Y-%m-%d").to_string()),
+        }).collect()
+    }
+
+    pub fn uses_spread(&self) -> bool {
+        self.parts.contains(&RowPart::Spread)
+    }
+}
+
+impl App {
+    /// Spread of a market's first token from any book we hold for it, else from the liquidity probe
+    pub fn known_spread(&self, market: &GammaMarket) -> Option<f64> {
+        let token_id = market.token_ids.first()?;
+        let held = [self.orderbook.as_ref(), self.sibling_orderbook.as_ref(), self.prewarm_orderbook.as_ref()]
+            .into_iter()
+            .flatten()
+            .find(|orderbook| &orderbook.token_id == token_id);
+        match held.map(|orderbook| orderbook.best_prices()) {
+            Some((Some(best_bid), Some(best_ask))) => Some(best_ask - best_bid),
+            _ => self.probed_spreads.get(token_id).copied(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::truncate_chars;
+
+    fn market() -> GammaMarket {
+        GammaMarket {
+            question: "Will it rain?".to_string(),
+            description: None,
+            end_date: Some("2026-11-03T00:00:00Z".to_string()),
+            category: None,
+            resolution_source: None,
+            token_ids: vec!["yes".to_string(), "no".to_string()],
+            outcomes: vec!["Yes".to_string(), "No".to_string()],
+            volume: Some(Decimal::from(12_345)),
+            active: true,
+            closed: false,
+            uma_resolution_statuses: None,
+        }
+    }
+
+    fn render(template: &str, spread: Option<f64>) -> String {
+        template.parse::<MarketRowFormat>().unwrap().render(&market(), spread, NumberFormat::Grouped)
+    }
+
+    #[test]
+    fn every_field_renders() {
+        assert_eq!(
+            render("{question} | {outcomes} | {volume} | {spread} | {end}", Some(0.02)),
+            "Will it rain? | 2 | $12,345 | 2.0¢ | 2026-11-03",
+        );
+    }
+
+    #[test]
+    fn fields_without_data_render_as_a_dash() {
+        let mut market = market();
+        market.volume = None;
+        market.end_date = None;
+        let format: MarketRowFormat = "{volume} {spread} {end}".parse().unwrap();
+        assert_eq!(format.render(&market, None, NumberFormat::Plain), "- - -");
+    }
+
+    #[test]
+    fn doubled_braces_are_literal() {
+        assert_eq!(render("{{{question}}}", None), "{Will it rain?}");
+        assert_eq!(render("{{}}", None), "{}");
+    }
+
+    #[test]
+    fn an_unknown_field_is_rejected() {
+        let error = "{question} {price}".parse::<MarketRowFormat>().unwrap_err();
+        assert!(error.starts_with("unknown field '{price}'"), "{error}");
+    }
+
+    #[test]
+    fn an_unclosed_brace_is_rejected() {
+        assert_eq!("{question".parse::<MarketRowFormat>().unwrap_err(), "unclosed '{', write '{{' for a literal brace");
+        assert_eq!("{question} {".parse::<MarketRowFormat>().unwrap_err(), "unclosed '{', write '{{' for a literal brace");
+    }
+
+    #[test]
+    fn an_unmatched_closing_brace_is_rejected() {
+        assert!("{question}}".parse::<MarketRowFormat>().unwrap_err().starts_with("unmatched '}'"));
+    }
+
+    #[test]
+    fn rendered_rows_truncate_by_characters() {
+        let row = render("{question} – {volume}", None);
+        assert_eq!(truncate_chars(&row, 15), "Will it rain? …");
+        assert_eq!(truncate_chars(&row, 100), row);
+    }
+}
@@ -0,0 +1,199 @@
+//! Tick-by-tick midpoint CSV, for offline analysis beyond the capped in-memory price history
+
+use anyhow::Result;
+use cli_log::*;
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{BufWriter, Write},
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use super::core::App;
+use crate::config::MID_LOG_FLUSH_SECS;
+use crate::data::OrderBookData;
+
+const HEADER: &str = "timestamp,token_id,midpoint,best_bid,best_ask,spread";
+
+/// Buffered so busy books don't hit the disk per row; flushed every MID_LOG_FLUSH_SECS, from rows and the update tick alike, and on drop
+pub struct MidpointCsvLogger {
+    pub path: PathBuf,
+    max_bytes: Option<u64>, // When the file grows past this it moves to `<path>.1` and a new one starts
+    writer: BufWriter<File>,
+    written: u64,
+    last_flush: Instant,
+}
+
+impl MidpointCsvLogger {
+    pub fn new(path: impl Into<PathBuf>, max_bytes: Option<u64>) -> Result<Self> {
+        let path = path.into();
+        let (writer, written) = Self::open(&path)?;
+        Ok(Self { path, max_bytes, writer, written, last_flush: Instant::now() })
+    }
+
+    /// Appends to an existing file, writing the header only into an empty one
+    fn open(path: &PathBuf) -> Result<(BufWriter<File>, u64)> {
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let mut written = file.metadata()?.len();
+        let mut writer = BufWriter::new(file);
+        if written == 0 {
+            writeln!(writer, "{HEADER}")?;
+            written = HEADER.len() as u64 + 1;
+        }
+        Ok((writer, written))
+    }
+
+    /// One row for a two-sided, uncrossed book; anything else has no midpoint worth logging
+    pub fn append(&mut self, orderbook: &OrderBookData) -> Result<()> {
+        let (Some(best_bid), Some(best_ask)) = orderbook.best_prices() else { return Ok(()) };
+        if orderbook.is_crossed() {
+            return Ok(());
+        }
+
+        let row = format!(
+            "{},{},{},{best_bid},{best_ask},{}\n",
+            chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            orderbook.token_id,
+            orderbook.get_midpoint(),
+            best_ask - best_bid,
+        );
+        self.writer.write_all(row.as_bytes())?;
+        self.written += row.len() as u64;
+
+        if self.max_bytes.is_some_and(|max_bytes| self.written >= max_bytes) {
+            self.rotate()
+        } else {
+            self.flush_if_due()
+        }
+    }
+
+    /// Also called when no row arrives, so a book that went quiet still gets its last rows onto the disk
+    pub fn flush_if_due(&mut self) -> Result<()> {
+        if self.last_flush.elapsed() >= Duration::from_secs(MID_LOG_FLUSH_SECS) {
+            self.writer.flush()?;
+            self.last_flush = Instant::now();
+        }
+        Ok(())
+    }
+
+    /// Keeps one previous file, so the log never takes more than twice `max_bytes`
+    fn rotate(&mut self) -> Result<()> {
+        self.writer.flush()?;
+        let mut rotated = self.path.clone().into_os_string();
+        rotated.push(".1");
+        fs::rename(&self.path, &rotated)?;
+        info!("Rotated midpoint log {} to {}", self.path.display(), PathBuf::from(&rotated).display());
+        (self.writer, self.written) = Self::open(&self.path)?;
+        self.last_flush = Instant::now();
+        Ok(())
+    }
+}
+
+impl App {
+    /// Called wherever the loaded book's midpoint is recomputed; a failing log is reported once and closed
+    pub fn log_midpoint(&mut self) {
+        let (Some(logger), Some(orderbook)) = (self.mid_logger.as_mut(), self.orderbook.as_ref()) else { return };
+        if let Err(e) = logger.append(orderbook) {
+            self.stop_midpoint_log(e);
+        }
+    }
+
+    /// Called from the update tick
+    pub fn flush_midpoint_log_if_due(&mut self) {
+        let Some(logger) = self.mid_logger.as_mut() else { return };
+        if let Err(e) = logger.flush_if_due() {
+            self.stop_midpoint_log(e);
+        }
+    }
+
+    fn stop_midpoint_log(&mut self, e: anyhow::Error) {
+        if let Some(logger) = self.mid_logger.take() {
+            warn!("Failed to write midpoint log {}: {e}", logger.path.display());
+        }
+        self.error_message = Some(format!("Midpoint logging stopped: {e}"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SimpleOrder;
+
+    fn log_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("polymarket-mid-log-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir.join("mid.csv")
+    }
+
+    fn book(best_bid: f64, best_ask: f64) -> OrderBookData {
+        let mut book = OrderBookData::empty("token".to_string(), "Question?".to_string());
+        book.bids = vec![SimpleOrder::new(best_bid, 10.0)];
+        book.asks = vec![SimpleOrder::new(best_ask, 10.0)];
+        book
+    }
+
+    fn lines(path: &PathBuf) -> Vec<String> {
+        fs::read_to_string(path).unwrap().lines().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn rows_follow_a_single_header_across_reopens() {
+        let path = log_path("reopen");
+        let mut logger = MidpointCsvLogger::new(&path, None).unwrap();
+        logger.append(&book(0.25, 0.75)).unwrap();
+        drop(logger);
+        let mut logger = MidpointCsvLogger::new(&path, None).unwrap();
+        logger.append(&book(0.41, 0.45)).unwrap();
+        drop(logger);
+
+        let lines = lines(&path);
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], HEADER);
+        let fields: Vec<&str> = lines[1].split(',').collect();
+        assert_eq!(fields[1..], ["token", "0.5", "0.25", "0.75", "0.5"]);
+        assert!(lines[2].contains(",token,"));
+    }
+
+    #[test]
+    fn one_sided_and_crossed_books_are_skipped() {
+        let path = log_path("skip");
+        let mut logger = MidpointCsvLogger::new(&path, None).unwrap();
+        let mut one_sided = book(0.40, 0.44);
+        one_sided.asks.clear();
+        logger.append(&one_sided).unwrap();
+        logger.append(&book(0.50, 0.45)).unwrap();
+        drop(logger);
+
+        assert_eq!(lines(&path), vec![HEADER.to_string()]);
+    }
+
+    #[test]
+    fn a_full_file_rotates_to_dot_one() {
+        let path = log_path("rotate");
+        let mut logger = MidpointCsvLogger::new(&path, Some(HEADER.len() as u64 + 10)).unwrap();
+        logger.append(&book(0.40, 0.44)).unwrap();
+        logger.append(&book(0.41, 0.45)).unwrap();
+        drop(logger);
+
+        let rotated = PathBuf::from(format!("{}.1", path.display()));
+        // Each row fills the file past the limit, so the second row rotated the first file away again
+        assert_eq!(lines(&rotated).len(), 2);
+        assert!(lines(&rotated)[1].contains(",0.41,"));
+        assert_eq!(lines(&path), vec![HEADER.to_string()]);
+    }
+
+    #[test]
+    fn a_due_flush_writes_buffered_rows() {
+        let path = log_path("flush");
+        let mut logger = MidpointCsvLogger::new(&path, None).unwrap();
+        logger.append(&book(0.40, 0.44)).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "", "rows stay buffered until a flush is due");
+
+        logger.last_flush -= Duration::from_secs(MID_LOG_FLUSH_SECS);
+        logger.flush_if_due().unwrap();
+        assert_eq!(lines(&path).len(), 2);
+    }
+}
@@ -8,7 +8,29 @@ pub mod websocket;
 pub mod price_history;
 pub mod input;
 pub mod strategies;
+pub mod event_overview;
+pub mod arbitrage_scan;
+pub mod categories;
+pub mod market_compare;
+pub mod outcome_pair;
+pub mod outcome_prices;
+pub mod prewarm;
+pub mod clipboard;
+pub mod chart_export;
+pub mod export;
+pub mod freeze_frame;
+pub mod health;
+pub mod keymap;
+pub mod liquidity;
+pub mod resolving_soon;
+pub mod resolution_warning;
+pub mod market_info;
+pub mod market_row;
+pub mod settings;
+pub mod screen_dump;
+pub mod session_export;
+pub mod mid_log;
 
 // Re-export the main App struct and key types
 pub use core::App;
-pub use types::{SelectedTab, MarketSelectorTab};
+pub use types::{AppOptions, ChartKind, DepthChartMode, Endpoints, SelectedTab, MarketSelectorTab, NumberFormat, NumericInputTarget, PriceFormat, PriceHistoryRange, StartView};
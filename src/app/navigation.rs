@@ -1,7 +1,8 @@
 //! Navigation logic for markets, events, and tokens
 
 use super::core::App;
-use super::types::MarketSelectorTab;
+use super::types::{ChartKind, DepthChartMode, MarketSelectorTab};
+use crate::config::{AGGREGATION_BUCKET_TICKS, DEPTH_CHART_ZOOM_TICKS, SELECTOR_PIN_CONTEXT_ROWS};
 
 impl App {
     // Basic navigation methods
@@ -28,15 +29,57 @@ impl App {
         }
     }
 
+    /// Demo mode: opens the first market in the selector's (volume) order that has a token, skipping both selectors.
+    /// Returns false when no market has one.
+    pub async fn auto_select_top_market(&mut self) -> anyhow::Result<bool> {
+        let Some(position) = self.filtered_markets.iter()
+            .position(|&market_idx| self.markets[market_idx].token_ids.first().is_some_and(|token_id| !token_id.is_empty()))
+        else {
+            return Ok(false);
+        };
+
+        self.market_selector_tab = MarketSelectorTab::AllMarkets;
+        self.selected_market = position;
+        self.selected_token = 0;
+        self.show_market_selector = false;
+        self.show_token_selector = false;
+        let token_id = self.markets[self.filtered_markets[position]].token_ids[0].clone();
+        cli_log::info!("Auto-selected top market: {}", self.markets[self.filtered_markets[position]].question);
+        self.load_orderbook(&token_id).await?;
+        self.start_websocket_for_token(&token_id);
+        self.needs_redraw = true;
+        Ok(true)
+    }
+
     pub fn select_token(&mut self) {
-        if !self.filtered_markets.is_empty() && self.selected_market < self.filtered_markets.len() {
-            let market_idx = self.filtered_markets[self.selected_market];
-            let market = &self.markets[market_idx];
-            if self.selected_token < market.token_ids.len() {
-                self.show_token_selector = false;
-                self.needs_redraw = true;
-                // We'll load the orderbook in the main loop
-            }
+        // Resolves through the event's markets too when the token selector was reached from an event
+        if self.get_current_token_id().is_some() {
+            self.show_token_selector = false;
+            self.needs_redraw = true;
+            // We'll load the orderbook in the main loop
+        }
+    }
+
+    /// Moves the market and event selections onto whatever market is loaded, so reopening the selector keeps our place
+    pub fn select_loaded_market_in_selector(&mut self) {
+        let Some(token_id) = self.orderbook.as_ref().map(|orderbook| orderbook.token_id.clone()) else {
+            return;
+        };
+        let owns_token = |token_ids: &[String]| token_ids.contains(&token_id);
+
+        if let Some(position) = self.filtered_markets.iter()
+            .position(|&market_idx| owns_token(&self.markets[market_idx].token_ids))
+        {
+            self.selected_market = position;
+            self.market_scroll_offset = position.saturating_sub(SELECTOR_PIN_CONTEXT_ROWS);
+        }
+
+        if let Some(position) = self.filtered_events.iter().position(|&event_idx| {
+            self.events[event_idx].markets.as_ref()
+                .is_some_and(|markets| markets.iter().any(|market| owns_token(&market.token_ids)))
+        }) {
+            self.selected_event = position;
+            self.event_scroll_offset = position.saturating_sub(SELECTOR_PIN_CONTEXT_ROWS);
         }
     }
 
@@ -157,6 +200,117 @@ impl App {
         self.needs_redraw = true;
     }
 
+    pub fn cycle_price_format(&mut self) {
+        self.price_format = self.price_format.next();
+        self.needs_redraw = true;
+    }
+
+    pub fn toggle_notional(&mut self) {
+        self.show_notional = !self.show_notional;
+        self.needs_redraw = true;
+    }
+
+    /// Steps the depth chart width to the next `DEPTH_CHART_ZOOM_TICKS` entry, stopping at either end
+    pub fn zoom_depth_chart(&mut self, zoom_in: bool) {
+        let current = self.depth_chart_ticks;
+        let next = if zoom_in {
+            DEPTH_CHART_ZOOM_TICKS.iter().rev().find(|&&ticks| ticks < current)
+        } else {
+            DEPTH_CHART_ZOOM_TICKS.iter().find(|&&ticks| ticks > current)
+        };
+        if let Some(&ticks) = next {
+            self.depth_chart_ticks = ticks;
+            self.needs_redraw = true;
+        }
+    }
+
+    pub fn toggle_level_age(&mut self) {
+        self.show_level_age = !self.show_level_age;
+        self.needs_redraw = true;
+    }
+
+    pub fn toggle_imbalance_sparkline(&mut self) {
+        self.show_imbalance_sparkline = !self.show_imbalance_sparkline;
+        self.needs_redraw = true;
+    }
+
+    pub fn toggle_twap(&mut self) {
+        self.show_twap = !self.show_twap;
+        self.needs_redraw = true;
+    }
+
+    pub fn toggle_chart(&mut self, chart: ChartKind) {
+        let state = if self.hidden_charts.remove(&chart) {
+            "shown"
+        } else {
+            self.hidden_charts.insert(chart);
+            "hidden"
+        };
+        self.set_status_message(format!("{} {state}", chart.name()));
+        self.needs_redraw = true;
+    }
+
+    pub fn cycle_depth_chart_mode(&mut self) {
+        self.depth_chart_mode = match self.depth_chart_mode {
+            DepthChartMode::Cumulative => DepthChartMode::Levels,
+            DepthChartMode::Levels => DepthChartMode::Cumulative,
+        };
+        self.set_status_message(format!("Depth chart: {}", self.depth_chart_mode.name()));
+        self.needs_redraw = true;
+    }
+
+    pub fn toggle_candles(&mut self) {
+        self.show_candles = !self.show_candles;
+        self.needs_redraw = true;
+    }
+
+    pub fn toggle_pin_price_axis(&mut self) {
+        self.pin_price_axis = !self.pin_price_axis;
+        self.needs_redraw = true;
+    }
+
+    pub fn cycle_aggregation(&mut self) {
+        let next_idx = AGGREGATION_BUCKET_TICKS.iter()
+            .position(|&ticks| ticks == self.aggregation_ticks)
+            .map_or(0, |idx| (idx + 1) % AGGREGATION_BUCKET_TICKS.len());
+        self.aggregation_ticks = AGGREGATION_BUCKET_TICKS[next_idx];
+        self.needs_redraw = true;
+    }
+
+    // Depth chart view
+    pub fn toggle_depth_chart_lock(&mut self) {
+        if let Some(ref mut orderbook) = self.orderbook {
+            orderbook.chart_locked = !orderbook.chart_locked;
+            if orderbook.chart_locked {
+                // Pin the window where it currently is
+                if orderbook.chart_center_price.is_none() {
+                    orderbook.chart_center_price = Some(orderbook.get_midpoint());
+                }
+            } else {
+                orderbook.chart_needs_recentering = true;
+            }
+            self.needs_redraw = true;
+        }
+    }
+
+    /// Moves a locked depth chart window by a number of ticks
+    pub fn pan_depth_chart(&mut self, ticks: i64) {
+        if let Some(ref mut orderbook) = self.orderbook {
+            if !orderbook.chart_locked {
+                return;
+            }
+            if let Some(center) = orderbook.chart_center_price {
+                let shifted = center + ticks as f64 * orderbook.tick_size;
+                orderbook.chart_center_price = Some(shifted.clamp(0.0, 1.0));
+                self.needs_redraw = true;
+            }
+        }
+    }
+
+    pub fn is_depth_chart_locked(&self) -> bool {
+        self.orderbook.as_ref().is_some_and(|orderbook| orderbook.chart_locked)
+    }
+
     // Market selector tab navigation
     pub fn next_market_selector_tab(&mut self) {
         self.market_selector_tab = self.market_selector_tab.next();
@@ -184,6 +338,9 @@ impl App {
                 // Normal event selection - go to event markets
                 self.show_market_selector = false;
                 self.show_event_market_selector = true;
+                // selected_market indexes the event's markets from here on
+                self.market_list_position = self.selected_market;
+                self.selected_market = 0;
                 self.selected_token = 0;
                 self.token_scroll_offset = 0;
                 self.search_mode = false;
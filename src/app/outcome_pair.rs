@@ -0,0 +1,196 @@
+//! Side-by-side view of both outcomes of a binary market
+
+use cli_log::*;
+use polymarket_rs_client::GammaMarket;
+use std::time::{Duration, Instant};
+
+use super::core::{summary_levels, App};
+use crate::config::OUTCOME_PAIR_UPDATE_INTERVAL_MS;
+use crate::data::{OrderBookData, OutcomePair, TokenQuote};
+
+impl App {
+    pub async fn toggle_outcome_pair(&mut self) {
+        if self.show_outcome_pair {
+            self.show_outcome_pair = false;
+            self.outcome_pair = None;
+            self.needs_redraw = true;
+            return;
+        }
+
+        let is_binary = self.orderbook.as_ref()
+            .and_then(|orderbook| self.find_market_for_token(&orderbook.token_id))
+            .is_some_and(|market| market.token_ids.len() == 2);
+        if !is_binary {
+            self.set_status_message("Outcome pair is only available for binary markets".to_string());
+            return;
+        }
+
+        self.show_outcome_pair = true;
+        self.load_outcome_pair().await;
+    }
+
+    /// Refreshes the sibling quote periodically and whenever the loaded token changes
+    pub async fn update_outcome_pair_if_needed(&mut self) {
+        let token_id = match &self.orderbook {
+            Some(orderbook) => orderbook.token_id.clone(),
+            None => return,
+        };
+
+        let token_changed = self.outcome_pair.as_ref()
+            .is_none_or(|pair| pair.primary_token_id != token_id);
+        // A cached sibling is live from the WebSocket, so reading it costs nothing
+        if token_changed
+            || self.sibling_orderbook.is_some()
+            || self.last_outcome_pair_update.elapsed() >= Duration::from_millis(OUTCOME_PAIR_UPDATE_INTERVAL_MS)
+        {
+            self.load_outcome_pair().await;
+        }
+    }
+
+    async fn load_outcome_pair(&mut self) {
+        self.last_outcome_pair_update = Instant::now();
+
+        let token_id = match &self.orderbook {
+            Some(orderbook) => orderbook.token_id.clone(),
+            None => return,
+        };
+
+        let market = match self.find_market_for_token(&token_id) {
+            Some(market) if market.token_ids.len() == 2 => market.clone(),
+            _ => {
+                // The newly loaded market is not binary, so the pair no longer applies
+                self.show_outcome_pair = false;
+                self.outcome_pair = None;
+                self.needs_redraw = true;
+                return;
+            }
+        };
+
+        let primary_idx = if market.token_ids[0] == token_id { 0 } else { 1 };
+        let sibling_idx = 1 - primary_idx;
+        let outcome_label = |idx: usize| {
+            market.outcomes.get(idx).cloned().unwrap_or_else(|| format!("Outcome {}", idx + 1))
+        };
+
+        let sibling_token_id = &market.token_ids[sibling_idx];
+        let sibling = match self.cached_sibling_quote(sibling_token_id, outcome_label(sibling_idx)) {
+            Some(quote) => quote,
+            None => self.fetch_token_quote(sibling_token_id, outcome_label(sibling_idx)).await,
+        };
+        debug!("Outcome pair refreshed: {} bid={:.4} ask={:.4}", sibling.label, sibling.best_bid, sibling.best_ask);
+
+        self.outcome_pair = Some(OutcomePair {
+            primary_token_id: token_id,
+            primary_outcome: outcome_label(primary_idx),
+            sibling,
+        });
+        self.needs_redraw = true;
+    }
+
+    /// The other token of a binary market, None for anything else
+    pub fn sibling_token_id(&self, token_id: &str) -> Option<String> {
+        let market = self.find_market_for_token(token_id).filter(|market| market.token_ids.len() == 2)?;
+        market.token_ids.iter().find(|id| *id != token_id).cloned()
+    }
+
+    /// Fetches the other outcome's book once per market; the WebSocket keeps it current from then on
+    pub async fn load_sibling_orderbook_if_needed(&mut self, token_id: &str) {
+        let Some(sibling_token_id) = self.sibling_token_id(token_id) else {
+            self.sibling_orderbook = None;
+            return;
+        };
+        if self.sibling_orderbook.as_ref().is_some_and(|sibling| sibling.token_id == sibling_token_id) {
+            return;
+        }
+
+        match self.rate_limiter.run(self.client.get_order_book(&sibling_token_id)).await {
+            Ok(book) => {
+                let mut sibling = OrderBookData::empty(sibling_token_id.clone(), self.market_question_for_token(&sibling_token_id));
+                (sibling.bids, sibling.asks) = summary_levels(&book, self.depth);
+                if let Some(orderbook) = self.orderbook.as_ref() {
+                    sibling.set_tick_size(orderbook.tick_size);
+                }
+                sibling.metadata = self.market_metadata(&sibling_token_id);
+                sibling.record_open_midpoint_if_missing();
+                sibling.record_midpoint();
+                debug!("Cached sibling book {sibling_token_id} ({} bids, {} asks)", sibling.bids.len(), sibling.asks.len());
+                self.sibling_orderbook = Some(sibling);
+            }
+            Err(e) => {
+                warn!("Failed to load sibling orderbook {sibling_token_id}: {e}");
+                self.sibling_orderbook = None;
+            }
+        }
+    }
+
+    /// Makes the cached sibling the loaded book and keeps the previous one as the new sibling
+    pub fn swap_in_sibling_orderbook(&mut self, token_id: &str) -> bool {
+        let cached = self.sibling_orderbook.as_ref().is_some_and(|sibling| {
+            sibling.token_id == token_id && (!sibling.bids.is_empty() || !sibling.asks.is_empty())
+        });
+        if !cached {
+            return false;
+        }
+
+        let previous = self.orderbook.take().filter(|orderbook| self.sibling_token_id(token_id).as_deref() == Some(orderbook.token_id.as_str()));
+        self.orderbook = self.sibling_orderbook.take();
+        self.sibling_orderbook = previous;
+        if let Some(ref mut orderbook) = self.orderbook {
+            orderbook.chart_needs_recentering = !orderbook.chart_locked;
+        }
+        info!("Switched to cached sibling book {token_id}");
+        self.last_book_snapshot = Instant::now();
+        self.error_message = None;
+        self.last_update = Instant::now();
+        self.needs_redraw = true;
+        true
+    }
+
+    fn cached_sibling_quote(&self, token_id: &str, label: String) -> Option<TokenQuote> {
+        let sibling = self.sibling_orderbook.as_ref().filter(|sibling| sibling.token_id == token_id)?;
+        Some(TokenQuote {
+            label,
+            token_id: token_id.to_string(),
+            best_bid: sibling.bids.first().map_or(0.0, |bid| bid.price),
+            best_ask: sibling.asks.first().map_or(0.0, |ask| ask.price),
+        })
+    }
+
+    /// Overlays the implied sibling price (1 - price) on the session price chart
+    pub fn toggle_sibling_overlay(&mut self) {
+        if self.sibling_overlay.take().is_some() {
+            self.needs_redraw = true;
+            return;
+        }
+
+        let token_id = match &self.orderbook {
+            Some(orderbook) => orderbook.token_id.clone(),
+            None => return,
+        };
+        let sibling_outcome = self.find_market_for_token(&token_id)
+            .filter(|market| market.token_ids.len() == 2)
+            .map(|market| {
+                let sibling_idx = if market.token_ids[0] == token_id { 1 } else { 0 };
+                market.outcomes.get(sibling_idx).cloned().unwrap_or_else(|| "Other side".to_string())
+            });
+
+        match sibling_outcome {
+            Some(outcome) => {
+                self.sibling_overlay = Some((token_id, outcome));
+                self.needs_redraw = true;
+            }
+            None => self.set_status_message("Sibling overlay is only available for binary markets".to_string()),
+        }
+    }
+
+    /// Looks up the market owning a token in both the flat market list and the events
+    pub fn find_market_for_token(&self, token_id: &str) -> Option<&GammaMarket> {
+        let owns_token = |market: &&GammaMarket| market.token_ids.iter().any(|id| id == token_id);
+        self.markets.iter().find(owns_token).or_else(|| {
+            self.events.iter()
+                .filter_map(|event| event.markets.as_ref())
+                .flat_map(|markets| markets.iter())
+                .find(owns_token)
+        })
+    }
+}
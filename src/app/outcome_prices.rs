@@ -0,0 +1,45 @@
+//! Prices next to each outcome in the token selector, so an outcome can be picked without loading its book
+
+use std::time::{Duration, Instant};
+
+use super::core::App;
+use crate::config::OUTCOME_PRICE_REFRESH_SECS;
+use crate::data::TokenQuote;
+
+impl App {
+    /// Token ids of the market the token selector lists
+    fn token_selector_token_ids(&self) -> Vec<String> {
+        self.filtered_markets.get(self.selected_market)
+            .map(|&market_idx| self.markets[market_idx].token_ids.clone())
+            .unwrap_or_default()
+    }
+
+    /// Fetches the outcomes never priced or priced over OUTCOME_PRICE_REFRESH_SECS ago, through the rate limiter
+    pub async fn refresh_outcome_prices_if_due(&mut self) {
+        let refresh = Duration::from_secs(OUTCOME_PRICE_REFRESH_SECS);
+        let due: Vec<String> = self.token_selector_token_ids().into_iter()
+            .filter(|token_id| self.outcome_prices.get(token_id).is_none_or(|(_, fetched)| fetched.elapsed() >= refresh))
+            .collect();
+
+        for token_id in due {
+            let quote = self.fetch_token_quote(&token_id, token_id.clone()).await;
+            self.outcome_prices.insert(token_id, (quote, Instant::now()));
+            self.needs_redraw = true;
+        }
+    }
+
+    /// Midpoint, or the only side quoted; `None` until the first fetch, `Some(None)` for an empty book
+    pub fn outcome_price(&self, token_id: &str) -> Option<Option<f64>> {
+        let (quote, _) = self.outcome_prices.get(token_id)?;
+        Some(outcome_price(quote))
+    }
+}
+
+fn outcome_price(quote: &TokenQuote) -> Option<f64> {
+    match (quote.best_bid > 0.0, quote.best_ask > 0.0) {
+        (true, true) => Some(quote.get_midpoint()),
+        (true, false) => Some(quote.best_bid),
+        (false, true) => Some(quote.best_ask),
+        (false, false) => None,
+    }
+}
@@ -0,0 +1,107 @@
+//! Pre-warming: streams the market highlighted in the selector so opening it needs no new connection or fetch
+
+use cli_log::*;
+use std::time::{Duration, Instant};
+
+use super::{core::App, websocket::connect_market_websocket, MarketSelectorTab};
+use crate::config::PREWARM_DEBOUNCE_MS;
+use crate::data::OrderBookData;
+use crate::websocket::PolymarketWebSocket;
+
+impl App {
+    /// The token the selector would open next, if it isn't the loaded one
+    fn highlighted_token_id(&self) -> Option<String> {
+        let token_id = if self.show_token_selector || self.show_event_market_selector {
+            self.get_current_token_id()
+        } else if (self.show_market_selector && self.market_selector_tab == MarketSelectorTab::AllMarkets
+            && !self.strategy_selection_mode && !self.is_unified_search_active()) || self.quick_search_mode
+        {
+            self.filtered_markets.get(self.selected_market)
+                .and_then(|&market_idx| self.markets[market_idx].token_ids.first().cloned())
+        } else {
+            None
+        };
+        let loaded = self.orderbook.as_ref().map(|orderbook| orderbook.token_id.as_str());
+        token_id.filter(|token_id| !token_id.is_empty() && Some(token_id.as_str()) != loaded)
+    }
+
+    /// Connects once the highlight has rested for PREWARM_DEBOUNCE_MS, and tears the connection down
+    /// as soon as it moves on or the selector closes, so at most one extra connection is ever open
+    pub async fn prewarm_highlighted_market_if_settled(&mut self) {
+        if !self.prewarm {
+            return;
+        }
+        let Some(token_id) = self.highlighted_token_id() else {
+            self.prewarm_candidate = None;
+            self.drop_prewarmed_market();
+            return;
+        };
+        if self.prewarm_token.as_deref() == Some(token_id.as_str()) {
+            return;
+        }
+
+        match self.prewarm_candidate {
+            Some((ref candidate, since)) if *candidate == token_id => {
+                if since.elapsed() < Duration::from_millis(PREWARM_DEBOUNCE_MS) {
+                    return;
+                }
+            }
+            _ => {
+                self.prewarm_candidate = Some((token_id, Instant::now()));
+                return;
+            }
+        }
+
+        self.drop_prewarmed_market();
+        let mut orderbook = OrderBookData::empty(token_id.clone(), self.market_question_for_token(&token_id));
+        orderbook.set_tick_size(self.get_tick_size_for_token(&token_id).await);
+        orderbook.metadata = self.market_metadata(&token_id);
+        orderbook.price_history = self.new_price_history();
+        info!("Pre-warming WebSocket for highlighted token: {token_id}");
+        self.prewarm_websocket = Some(connect_market_websocket(self, &token_id));
+        self.prewarm_orderbook = Some(orderbook);
+        self.prewarm_token = Some(token_id);
+    }
+
+    /// Dropping the handle closes the connection
+    fn drop_prewarmed_market(&mut self) {
+        if let Some(token_id) = self.prewarm_token.take() {
+            debug!("Closing pre-warmed WebSocket for token: {token_id}");
+        }
+        self.prewarm_websocket = None;
+        self.prewarm_orderbook = None;
+    }
+
+    /// Hands over the pre-warmed connection when it streams `token_id` and is still running
+    pub fn take_prewarmed_websocket(&mut self, token_id: &str) -> Option<PolymarketWebSocket> {
+        if self.prewarm_token.as_deref() != Some(token_id) {
+            return None;
+        }
+        self.prewarm_token = None;
+        self.prewarm_orderbook = None;
+        self.prewarm_websocket.take().filter(|ws| !ws.thread_handle.is_finished())
+    }
+
+    /// Makes the pre-warmed book the loaded one once its first snapshot has arrived
+    pub fn swap_in_prewarmed_orderbook(&mut self, token_id: &str) -> bool {
+        let Some(mut orderbook) = self.prewarm_orderbook.take_if(|prewarmed| {
+            prewarmed.token_id == token_id && (!prewarmed.bids.is_empty() || !prewarmed.asks.is_empty())
+        }) else {
+            return false;
+        };
+
+        orderbook.chart_needs_recentering = true;
+        orderbook.record_open_midpoint_if_missing();
+        orderbook.record_midpoint();
+        info!("Switched to pre-warmed book {token_id}");
+        self.orderbook = Some(orderbook);
+        self.sibling_orderbook = None; // Belonged to the previous market
+        self.log_midpoint();
+        self.last_book_snapshot = Instant::now();
+        self.websocket_reconnecting = false;
+        self.error_message = None;
+        self.last_update = Instant::now();
+        self.needs_redraw = true;
+        true
+    }
+}
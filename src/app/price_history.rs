@@ -7,16 +7,53 @@ use std::{
 };
 use cli_log::*;
 
+use crate::config::PRICE_HISTORY_FIDELITIES;
 use crate::data::{CryptoPrice};
-use crate::websocket::{CryptoWebSocket, CryptoSymbol};
+use crate::websocket::{CryptoFeed, CryptoWebSocket, CryptoSymbol};
 use super::core::App;
 
+impl App {
+    pub async fn cycle_price_history_range(&mut self) {
+        self.price_history_range = self.price_history_range.next();
+        self.reload_market_price_history().await;
+    }
+
+    pub async fn cycle_price_history_fidelity(&mut self) {
+        self.price_history_fidelity = next_fidelity(self.price_history_fidelity);
+        self.reload_market_price_history().await;
+    }
+
+    pub async fn reload_market_price_history(&mut self) {
+        if let Some(token_id) = self.orderbook.as_ref().map(|orderbook| orderbook.token_id.clone()) {
+            self.load_market_price_history(&token_id).await;
+        }
+    }
+
+    /// Keeps the API history of the loaded market current on its own interval; the realtime
+    /// `price_history` covers the moves in between
+    pub async fn refresh_market_price_history_if_due(&mut self) {
+        let due = self.market_price_history_refresh
+            .is_some_and(|refresh| self.last_market_price_history_load.elapsed() >= refresh);
+        if due {
+            self.reload_market_price_history().await;
+        }
+    }
+}
+
+/// The fidelity after `current` in PRICE_HISTORY_FIDELITIES, wrapping; an unlisted one restarts the cycle
+pub fn next_fidelity(current: u32) -> u32 {
+    let next_idx = PRICE_HISTORY_FIDELITIES.iter()
+        .position(|&fidelity| fidelity == current)
+        .map_or(0, |idx| (idx + 1) % PRICE_HISTORY_FIDELITIES.len());
+    PRICE_HISTORY_FIDELITIES[next_idx]
+}
+
 pub fn update_price_history_if_needed(app: &mut App) {
     if should_update_price_history(app) {
         if let Some(ref mut orderbook) = app.orderbook {
-            let midpoint = orderbook.get_midpoint();
-            orderbook.price_history.add_price(midpoint);
+            orderbook.record_midpoint();
             app.last_price_history_update = Instant::now();
+            app.log_midpoint();
         }
     }
 }
@@ -91,17 +128,17 @@ fn start_crypto_websockets_if_needed(app: &mut App) {
             app.crypto_prices.insert(symbol.clone(), crypto_price.clone());
             
             // Start the WebSocket in a separate thread
-            start_crypto_websocket(symbol.clone(), crypto_price.clone());
+            start_crypto_websocket(symbol.clone(), crypto_price.clone(), app.crypto_feed.clone());
             app.crypto_websocket_active.insert(symbol.clone(), true);
         }
     }
 }
 
-fn start_crypto_websocket(symbol: CryptoSymbol, price_arc: Arc<Mutex<CryptoPrice>>) {
+fn start_crypto_websocket(symbol: CryptoSymbol, price_arc: Arc<Mutex<CryptoPrice>>, feed: CryptoFeed) {
     use std::thread;
     
     thread::spawn(move || {
-        let mut crypto_ws = CryptoWebSocket::new();
+        let mut crypto_ws = CryptoWebSocket::with_feed(feed);
         crypto_ws.start_single(symbol.clone());  // Use start_single for individual symbol
         
         info!("{} WebSocket started", symbol.name());
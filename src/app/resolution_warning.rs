@@ -0,0 +1,78 @@
+//! One-time warnings when the viewed market, or one a strategy watches, is about to resolve
+
+use chrono::{DateTime, Utc};
+use cli_log::*;
+use std::time::{Duration, Instant};
+
+use super::{core::App, resolving_soon::{format_ends_in, market_end_time, parse_end_date}};
+use crate::bot::{AlertSeverity, StrategyAlert, StrategyStatus, StrategyType};
+use crate::config::RESOLUTION_CHECK_INTERVAL_SECS;
+
+impl App {
+    pub fn check_upcoming_resolutions_if_due(&mut self) {
+        if self.resolution_warning <= chrono::Duration::zero()
+            || self.last_resolution_check.elapsed() < Duration::from_secs(RESOLUTION_CHECK_INTERVAL_SECS) {
+            return;
+        }
+        self.last_resolution_check = Instant::now();
+        self.warn_viewed_market_resolving();
+        self.alert_strategy_markets_resolving();
+    }
+
+    /// End time of a market still ahead of us but inside the warning window
+    fn resolving_within_window(&self, end: Option<DateTime<Utc>>) -> Option<DateTime<Utc>> {
+        let now = Utc::now();
+        end.filter(|&end| end > now && end <= now + self.resolution_warning)
+    }
+
+    fn warn_viewed_market_resolving(&mut self) {
+        let Some(orderbook) = self.orderbook.as_ref() else { return };
+        let end = orderbook.metadata.as_ref()
+            .and_then(|metadata| metadata.end_date.as_deref())
+            .and_then(parse_end_date);
+        let Some(end) = self.resolving_within_window(end) else { return };
+
+        let key = (None, orderbook.token_id.clone());
+        if self.resolution_warned.insert(key) {
+            let message = format!("Market {}: {}", format_ends_in(end), orderbook.market_question);
+            info!("Resolution warning: {message}");
+            self.set_status_message(message);
+        }
+    }
+
+    /// Raised as an alert on every running strategy that has the market selected
+    fn alert_strategy_markets_resolving(&mut self) {
+        let mut due: Vec<(StrategyType, String, StrategyAlert)> = Vec::new();
+        for (strategy_type, strategy) in &self.bot_engine.strategies {
+            if !(matches!(strategy.status, StrategyStatus::Running) && strategy.enabled) {
+                continue;
+            }
+            for (token_id, name) in strategy.selected_market_ids.iter().zip(&strategy.selected_market_names) {
+                let Some(market) = self.markets.iter().find(|market| market.token_ids.contains(token_id)) else { continue };
+                let Some(end) = self.resolving_within_window(market_end_time(market)) else { continue };
+                if self.resolution_warned.contains(&(Some(strategy_type.clone()), token_id.clone())) {
+                    continue;
+                }
+
+                let market_data = [
+                    ("token_id".to_string(), serde_json::json!(token_id)),
+                    ("end_date".to_string(), serde_json::json!(end.to_rfc3339())),
+                ].into_iter().collect();
+                let alert = StrategyAlert {
+                    timestamp: Utc::now(),
+                    strategy: strategy_type.name().to_string(),
+                    severity: AlertSeverity::High,
+                    message: format!("{name} {} - close or adjust positions before resolution", format_ends_in(end)),
+                    market_data,
+                };
+                due.push((strategy_type.clone(), token_id.clone(), alert));
+            }
+        }
+
+        for (strategy_type, token_id, alert) in due {
+            info!("Resolution alert for {}: {}", strategy_type.name(), alert.message);
+            self.resolution_warned.insert((Some(strategy_type.clone()), token_id));
+            self.bot_engine.emit_alerts(&strategy_type, vec![alert]);
+        }
+    }
+}
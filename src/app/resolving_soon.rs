@@ -0,0 +1,59 @@
+//! "Resolving soon" filter: markets ending within the next day, soonest first
+
+use chrono::{DateTime, NaiveDate, Utc};
+use polymarket_rs_client::GammaMarket;
+
+use super::core::App;
+use crate::config::RESOLVING_SOON_HOURS;
+
+pub fn market_end_time(market: &GammaMarket) -> Option<DateTime<Utc>> {
+    parse_end_date(market.end_date.as_deref()?)
+}
+
+/// Gamma usually sends RFC 3339, but some markets only carry a date, taken as the end of that day
+pub fn parse_end_date(raw: &str) -> Option<DateTime<Utc>> {
+    let raw = raw.trim();
+    DateTime::parse_from_rfc3339(raw)
+        .map(|end| end.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(raw, "%Y-%m-%d").ok()
+                .and_then(|date| date.and_hms_opt(23, 59, 59))
+                .map(|end| end.and_utc())
+        })
+}
+
+/// The end time if it falls inside the resolving-soon window from now
+pub fn resolving_soon_end(market: &GammaMarket) -> Option<DateTime<Utc>> {
+    let now = Utc::now();
+    market_end_time(market)
+        .filter(|&end| end > now && end <= now + chrono::Duration::hours(RESOLVING_SOON_HOURS))
+}
+
+/// "ends in 3h" style label, minutes under an hour
+pub fn format_ends_in(end: DateTime<Utc>) -> String {
+    let minutes = (end - Utc::now()).num_minutes().max(0);
+    if minutes < 60 {
+        format!("ends in {minutes}m")
+    } else {
+        format!("ends in {}h", minutes / 60)
+    }
+}
+
+impl App {
+    pub fn toggle_resolving_soon(&mut self) {
+        self.resolving_soon = !self.resolving_soon;
+        self.refilter_markets_keeping_selection();
+        let state = if self.resolving_soon { "on" } else { "off" };
+        self.set_status_message(format!("Resolving within {RESOLVING_SOON_HOURS}h filter {state}"));
+    }
+
+    /// Markets without an end date never pass; the rest are ordered soonest first
+    pub(super) fn filter_resolving_soon(&self, filtered: &mut Vec<usize>) {
+        let mut ending: Vec<(usize, DateTime<Utc>)> = filtered.iter()
+            .filter_map(|&market_idx| resolving_soon_end(&self.markets[market_idx]).map(|end| (market_idx, end)))
+            .collect();
+        ending.sort_by_key(|&(_, end)| end);
+        *filtered = ending.into_iter().map(|(market_idx, _)| market_idx).collect();
+    }
+}
@@ -0,0 +1,58 @@
+//! Plain-text capture of the current screen, for bug reports and notes
+
+use anyhow::Result;
+use cli_log::*;
+use ratatui::buffer::Buffer;
+use std::{fs, path::PathBuf};
+
+use super::core::App;
+use crate::config::SCREEN_DUMP_DIR;
+
+/// Cell symbols row by row with styling dropped and trailing blanks trimmed
+pub fn buffer_to_text(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let mut text = String::new();
+    for y in area.top()..area.bottom() {
+        let mut line = String::new();
+        for x in area.left()..area.right() {
+            if let Some(cell) = buffer.cell((x, y)) {
+                line.push_str(cell.symbol());
+            }
+        }
+        text.push_str(line.trim_end());
+        text.push('\n');
+    }
+    text
+}
+
+impl App {
+    /// The capture happens after the next draw, so the file shows exactly what was on screen
+    pub fn request_screen_dump(&mut self) {
+        self.screen_dump_requested = true;
+        self.needs_redraw = true;
+    }
+
+    pub fn save_screen_dump(&mut self, buffer: &Buffer) {
+        self.screen_dump_requested = false;
+        match write_screen_dump(buffer) {
+            Ok(path) => {
+                info!("Saved screen text to {}", path.display());
+                self.set_status_message(format!("Screen saved to {}", path.display()));
+            }
+            Err(e) => {
+                warn!("Screen dump failed: {e}");
+                self.set_status_message(format!("Screen dump failed: {e}"));
+            }
+        }
+    }
+}
+
+fn write_screen_dump(buffer: &Buffer) -> Result<PathBuf> {
+    fs::create_dir_all(SCREEN_DUMP_DIR)?;
+    let path = PathBuf::from(SCREEN_DUMP_DIR).join(format!(
+        "screen-{}.txt",
+        chrono::Utc::now().format("%Y%m%d-%H%M%S"),
+    ));
+    fs::write(&path, buffer_to_text(buffer))?;
+    Ok(path)
+}
@@ -1,6 +1,40 @@
 //! Search functionality for markets and events
 
+use anyhow::Result;
+use cli_log::*;
+
 use super::core::App;
+use super::types::MarketSelectorTab;
+
+/// Selector positions put aside while the quick-switch overlay borrows the market cursor
+#[derive(Debug, Clone, Copy)]
+pub struct SelectorCursor {
+    selected_market: usize,
+    market_scroll_offset: usize,
+    selected_event: usize,
+    event_scroll_offset: usize,
+}
+
+/// A row of the unified search, pointing into `filtered_events` or `filtered_markets`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchResult {
+    Event(usize),
+    Market(usize),
+}
+
+/// Lower is better: the text starts with the query, then a word does, then it only contains it
+pub fn match_rank(text: &str, query: &str) -> Option<u8> {
+    let text = text.to_lowercase();
+    if text.starts_with(query) {
+        Some(0)
+    } else if text.split(|ch: char| !ch.is_alphanumeric()).any(|word| word.starts_with(query)) {
+        Some(1)
+    } else if text.contains(query) {
+        Some(2)
+    } else {
+        None
+    }
+}
 
 impl App {
     pub fn add_search_char(&mut self, ch: char) {
@@ -26,6 +60,47 @@ impl App {
         self.needs_redraw = true;
     }
 
+    /// Opens the quick-switch search overlay on top of the orderbook view
+    pub fn open_quick_search(&mut self) {
+        self.quick_search_return = Some(SelectorCursor {
+            selected_market: self.selected_market,
+            market_scroll_offset: self.market_scroll_offset,
+            selected_event: self.selected_event,
+            event_scroll_offset: self.event_scroll_offset,
+        });
+        self.quick_search_mode = true;
+        self.search_mode = true;
+        self.clear_search();
+    }
+
+    /// Closes the quick-switch overlay and returns to the current book, with the selector where it was left
+    pub fn close_quick_search(&mut self) {
+        self.quick_search_mode = false;
+        self.search_mode = false;
+        self.clear_search();
+        if let Some(cursor) = self.quick_search_return.take() {
+            self.selected_market = cursor.selected_market;
+            self.market_scroll_offset = cursor.market_scroll_offset;
+            self.selected_event = cursor.selected_event;
+            self.event_scroll_offset = cursor.event_scroll_offset;
+        }
+    }
+
+    /// Loads the first outcome of the highlighted market from the quick-switch overlay.
+    /// The token comes straight from the filtered market list, so the selector keeps its tab and event cursor.
+    pub async fn select_quick_search_market(&mut self) -> Result<()> {
+        let token_id = self.filtered_markets.get(self.selected_market)
+            .and_then(|&market_idx| self.markets[market_idx].token_ids.first().cloned());
+        self.close_quick_search();
+
+        if let Some(token_id) = token_id {
+            info!("Quick switching to token ID: {token_id}");
+            self.load_orderbook(&token_id).await?;
+            self.start_websocket_for_token(&token_id);
+        }
+        Ok(())
+    }
+
     /// Resets search-related state after any search query change
     fn reset_search_state(&mut self) {
         self.update_filtered_markets();
@@ -34,6 +109,71 @@ impl App {
         self.selected_event = 0;
         self.market_scroll_offset = 0;
         self.event_scroll_offset = 0;
+        self.selected_unified_result = 0;
+        self.unified_scroll_offset = 0;
+        self.needs_redraw = true;
+    }
+
+    /// Searching from the All Markets or Events tab lists both kinds together, unless turned off in the settings
+    pub fn is_unified_search_active(&self) -> bool {
+        self.unified_search && self.search_mode && !self.search_query.is_empty()
+            && self.show_market_selector && !self.quick_search_mode && !self.strategy_selection_mode
+            && self.market_selector_tab != MarketSelectorTab::Strategies
+    }
+
+    /// Merges the tab filters into one list, best match first; events win ties since they group several markets.
+    /// Rebuilt whenever either filter changes, so the positions it holds stay valid.
+    pub(super) fn update_unified_results(&mut self) {
+        let query = self.search_query.to_lowercase();
+        let events = self.filtered_events.iter().enumerate().map(|(position, &event_idx)| {
+            let event = &self.events[event_idx];
+            // A match only in the description ranks below any title match
+            let rank = match_rank(&event.title, &query).unwrap_or(3);
+            (rank, SearchResult::Event(position))
+        });
+        let markets = self.filtered_markets.iter().enumerate().map(|(position, &market_idx)| {
+            (match_rank(&self.markets[market_idx].question, &query).unwrap_or(3), SearchResult::Market(position))
+        });
+        let mut ranked: Vec<(u8, SearchResult)> = events.chain(markets).collect();
+        ranked.sort_by_key(|&(rank, _)| rank);
+
+        self.unified_results = ranked.into_iter().map(|(_, result)| result).collect();
+        self.selected_unified_result = self.selected_unified_result.min(self.unified_results.len().saturating_sub(1));
+    }
+
+    pub fn move_unified_selection(&mut self, delta: isize) {
+        if self.unified_results.is_empty() {
+            return;
+        }
+        let last = self.unified_results.len() - 1;
+        self.selected_unified_result = self.selected_unified_result.saturating_add_signed(delta).min(last);
         self.needs_redraw = true;
     }
+
+    /// An event opens its markets like the Events tab would; a market loads its first outcome straight away
+    pub async fn select_unified_result(&mut self) -> Result<()> {
+        let Some(&result) = self.unified_results.get(self.selected_unified_result) else { return Ok(()) };
+        match result {
+            SearchResult::Event(position) => {
+                self.market_selector_tab = MarketSelectorTab::Events;
+                self.selected_event = position;
+                self.select_event();
+            }
+            SearchResult::Market(position) => {
+                self.market_selector_tab = MarketSelectorTab::AllMarkets;
+                self.selected_market = position;
+                self.selected_token = 0;
+                let token_id = self.get_current_token_id();
+                self.show_market_selector = false;
+                self.search_mode = false;
+                if let Some(token_id) = token_id {
+                    info!("Loading search result, token ID: {token_id}");
+                    self.load_orderbook(&token_id).await?;
+                    self.start_websocket_for_token(&token_id);
+                }
+                self.needs_redraw = true;
+            }
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,405 @@
+//! Settings overlay: view settings in one editable list, saved between runs
+
+use anyhow::{anyhow, bail, Result};
+use cli_log::*;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path, time::Duration};
+
+use super::core::App;
+use super::types::{DepthChartMode, NumberFormat, PriceFormat, PriceHistoryRange};
+use crate::config::{
+    AGGREGATION_BUCKET_TICKS, CANDLE_INTERVALS_SECS, DEFAULT_DEPTH_CHART_TICKS, DEFAULT_MIN_LIQUIDITY, DEFAULT_ORDERBOOK_DEPTH,
+    DEFAULT_PRICE_HISTORY_FIDELITY, DEFAULT_UPDATE_INTERVAL, DEPTH_CHART_ZOOM_TICKS, MAX_ORDERBOOK_DEPTH, MIN_LIQUIDITY_THRESHOLDS,
+    ORDERBOOK_DEPTH_STEPS, PRICE_HISTORY_FIDELITIES, UPDATE_INTERVAL_STEPS,
+};
+
+/// Persisted view settings; fields missing from the file keep their defaults
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub update_interval_secs: f64,
+    pub depth: usize,
+    pub price_format: PriceFormat,
+    pub number_format: NumberFormat,
+    pub show_notional: bool,
+    pub aggregation_ticks: u32,
+    pub depth_chart_ticks: u32,
+    pub depth_chart_mode: DepthChartMode,
+    pub show_twap: bool,
+    pub pin_price_axis: bool,
+    pub show_candles: bool,
+    pub candle_interval_secs: u64,
+    pub show_level_age: bool,
+    pub show_imbalance_sparkline: bool,
+    pub price_history_range: PriceHistoryRange,
+    pub price_history_fidelity: u32,
+    pub liquid_only: bool,
+    pub min_liquidity: u32,
+    pub unified_search: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            update_interval_secs: DEFAULT_UPDATE_INTERVAL,
+            depth: DEFAULT_ORDERBOOK_DEPTH,
+            price_format: PriceFormat::default(),
+            number_format: NumberFormat::default(),
+            show_notional: false,
+            aggregation_ticks: 1,
+            depth_chart_ticks: DEFAULT_DEPTH_CHART_TICKS,
+            depth_chart_mode: DepthChartMode::default(),
+            show_twap: false,
+            pin_price_axis: false,
+            show_candles: false,
+            candle_interval_secs: CANDLE_INTERVALS_SECS[0],
+            show_level_age: false,
+            show_imbalance_sparkline: false,
+            price_history_range: PriceHistoryRange::default(),
+            price_history_fidelity: DEFAULT_PRICE_HISTORY_FIDELITY,
+            liquid_only: false,
+            min_liquidity: DEFAULT_MIN_LIQUIDITY,
+            unified_search: true,
+        }
+    }
+}
+
+impl Settings {
+    /// A missing file gives `None` so command line values stay in effect
+    pub fn load(path: impl AsRef<Path>) -> Result<Option<Self>> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(None);
+        }
+        Self::parse(&fs::read_to_string(path)?)
+            .map(Some)
+            .map_err(|e| anyhow!("Invalid settings {}: {e}", path.display()))
+    }
+
+    /// Checks one field at a time so every bad value is reported by its key, then the ranges
+    pub fn parse(text: &str) -> Result<Self> {
+        let value: serde_json::Value = serde_json::from_str(text)?;
+        let serde_json::Value::Object(ref fields) = value else {
+            bail!("expected an object of settings");
+        };
+        let serde_json::Value::Object(known) = serde_json::to_value(Self::default())? else {
+            unreachable!("settings serialize to an object");
+        };
+
+        let mut problems = Vec::new();
+        for (key, field) in fields {
+            if !known.contains_key(key) {
+                problems.push(format!("unknown setting '{key}'"));
+                continue;
+            }
+            let single = serde_json::Map::from_iter([(key.clone(), field.clone())]);
+            if let Err(e) = serde_json::from_value::<Self>(single.into()) {
+                problems.push(format!("'{key}': {e}"));
+            }
+        }
+        if !problems.is_empty() {
+            bail!(problems.join("; "));
+        }
+
+        let settings: Self = serde_json::from_value(value)?;
+        let problems = settings.range_problems();
+        if !problems.is_empty() {
+            bail!(problems.join("; "));
+        }
+        Ok(settings)
+    }
+
+    /// Values that deserialize fine but that the views can't use
+    fn range_problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.update_interval_secs <= 0.0 {
+            problems.push(format!("'update_interval_secs' must be above 0, got {}", self.update_interval_secs));
+        }
+        if !(1..=MAX_ORDERBOOK_DEPTH).contains(&self.depth) {
+            problems.push(format!("'depth' must be between 1 and {MAX_ORDERBOOK_DEPTH}, got {}", self.depth));
+        }
+        if self.aggregation_ticks == 0 {
+            problems.push("'aggregation_ticks' must be at least 1".to_string());
+        }
+        if self.depth_chart_ticks == 0 {
+            problems.push("'depth_chart_ticks' must be at least 1".to_string());
+        }
+        if self.candle_interval_secs == 0 {
+            problems.push("'candle_interval_secs' must be at least 1".to_string());
+        }
+        if self.price_history_fidelity == 0 {
+            problems.push("'price_history_fidelity' must be at least 1 minute".to_string());
+        }
+        problems
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// One editable row of the settings overlay
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingField {
+    UpdateInterval,
+    Depth,
+    PriceFormat,
+    NumberFormat,
+    Notional,
+    Aggregation,
+    DepthChartTicks,
+    DepthChartMode,
+    Twap,
+    PriceAxis,
+    PriceChart,
+    CandleInterval,
+    LevelAge,
+    ImbalanceSparkline,
+    HistoryRange,
+    HistoryFidelity,
+    LiquidOnly,
+    MinLiquidity,
+    UnifiedSearch,
+}
+
+impl SettingField {
+    pub fn all() -> &'static [SettingField] {
+        &[
+            Self::UpdateInterval, Self::Depth, Self::PriceFormat, Self::NumberFormat, Self::Notional,
+            Self::Aggregation, Self::DepthChartTicks, Self::DepthChartMode, Self::Twap, Self::PriceAxis, Self::PriceChart, Self::CandleInterval, Self::LevelAge, Self::ImbalanceSparkline, Self::HistoryRange, Self::HistoryFidelity,
+            Self::LiquidOnly, Self::MinLiquidity, Self::UnifiedSearch,
+        ]
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::UpdateInterval => "Update interval",
+            Self::Depth => "Orders per side",
+            Self::PriceFormat => "Price format",
+            Self::NumberFormat => "Number format",
+            Self::Notional => "Order sizes",
+            Self::Aggregation => "Aggregation",
+            Self::DepthChartTicks => "Depth chart width",
+            Self::DepthChartMode => "Depth chart",
+            Self::Twap => "TWAP overlay",
+            Self::PriceAxis => "Price axis",
+            Self::PriceChart => "Price chart",
+            Self::CandleInterval => "Candle interval",
+            Self::LevelAge => "Level age",
+            Self::ImbalanceSparkline => "Imbalance sparkline",
+            Self::HistoryRange => "History range",
+            Self::HistoryFidelity => "History fidelity",
+            Self::LiquidOnly => "Tradable only",
+            Self::MinLiquidity => "Minimum liquidity",
+            Self::UnifiedSearch => "Search scope",
+        }
+    }
+
+    pub fn value(self, settings: &Settings) -> String {
+        let on_off = |enabled: bool| if enabled { "on" } else { "off" }.to_string();
+        match self {
+            Self::UpdateInterval => format!("{}s", settings.update_interval_secs),
+            Self::Depth => settings.depth.to_string(),
+            Self::PriceFormat => settings.price_format.name().to_string(),
+            Self::NumberFormat => settings.number_format.name().to_string(),
+            Self::Notional => if settings.show_notional { "notional ($)" } else { "shares" }.to_string(),
+            Self::Aggregation => format!("{} ticks", settings.aggregation_ticks),
+            Self::DepthChartTicks => format!("{} ticks", settings.depth_chart_ticks),
+            Self::DepthChartMode => settings.depth_chart_mode.name().to_string(),
+            Self::Twap => on_off(settings.show_twap),
+            Self::PriceAxis => if settings.pin_price_axis { "0..1" } else { "auto" }.to_string(),
+            Self::PriceChart => if settings.show_candles { "candles" } else { "line" }.to_string(),
+            Self::CandleInterval => format!("{}s", settings.candle_interval_secs),
+            Self::LevelAge => on_off(settings.show_level_age),
+            Self::ImbalanceSparkline => on_off(settings.show_imbalance_sparkline),
+            Self::HistoryRange => settings.price_history_range.interval().to_string(),
+            Self::HistoryFidelity => format!("{}m", settings.price_history_fidelity),
+            Self::LiquidOnly => on_off(settings.liquid_only),
+            Self::MinLiquidity => format!("${}", settings.min_liquidity),
+            Self::UnifiedSearch => if settings.unified_search { "events + markets" } else { "current tab" }.to_string(),
+        }
+    }
+
+    /// Moves to the neighbouring option, wrapping around at either end
+    fn step(self, settings: &mut Settings, forward: bool) {
+        match self {
+            Self::UpdateInterval => settings.update_interval_secs = step(&UPDATE_INTERVAL_STEPS, settings.update_interval_secs, forward),
+            Self::Depth => settings.depth = step(&ORDERBOOK_DEPTH_STEPS, settings.depth, forward),
+            Self::PriceFormat => settings.price_format = step(&PriceFormat::ALL, settings.price_format, forward),
+            Self::NumberFormat => settings.number_format = step(&NumberFormat::ALL, settings.number_format, forward),
+            Self::Notional => settings.show_notional = !settings.show_notional,
+            Self::Aggregation => settings.aggregation_ticks = step(&AGGREGATION_BUCKET_TICKS, settings.aggregation_ticks, forward),
+            Self::DepthChartTicks => settings.depth_chart_ticks = step(&DEPTH_CHART_ZOOM_TICKS, settings.depth_chart_ticks, forward),
+            Self::DepthChartMode => settings.depth_chart_mode = step(&DepthChartMode::ALL, settings.depth_chart_mode, forward),
+            Self::Twap => settings.show_twap = !settings.show_twap,
+            Self::PriceAxis => settings.pin_price_axis = !settings.pin_price_axis,
+            Self::PriceChart => settings.show_candles = !settings.show_candles,
+            Self::CandleInterval => settings.candle_interval_secs = step(&CANDLE_INTERVALS_SECS, settings.candle_interval_secs, forward),
+            Self::LevelAge => settings.show_level_age = !settings.show_level_age,
+            Self::ImbalanceSparkline => settings.show_imbalance_sparkline = !settings.show_imbalance_sparkline,
+            Self::HistoryRange => settings.price_history_range = step(&PriceHistoryRange::ALL, settings.price_history_range, forward),
+            Self::HistoryFidelity => settings.price_history_fidelity = step(&PRICE_HISTORY_FIDELITIES, settings.price_history_fidelity, forward),
+            Self::LiquidOnly => settings.liquid_only = !settings.liquid_only,
+            Self::MinLiquidity => settings.min_liquidity = step(&MIN_LIQUIDITY_THRESHOLDS, settings.min_liquidity, forward),
+            Self::UnifiedSearch => settings.unified_search = !settings.unified_search,
+        }
+    }
+}
+
+/// Values that aren't one of the options (e.g. from the command line) jump to the first option
+fn step<T: Copy + PartialEq>(options: &[T], current: T, forward: bool) -> T {
+    let next = match options.iter().position(|&option| option == current) {
+        Some(idx) if forward => (idx + 1) % options.len(),
+        Some(idx) => (idx + options.len() - 1) % options.len(),
+        None => 0,
+    };
+    options[next]
+}
+
+impl App {
+    pub fn current_settings(&self) -> Settings {
+        Settings {
+            update_interval_secs: self.update_interval.as_secs_f64(),
+            depth: self.depth,
+            price_format: self.price_format,
+            number_format: self.number_format,
+            show_notional: self.show_notional,
+            aggregation_ticks: self.aggregation_ticks,
+            depth_chart_ticks: self.depth_chart_ticks,
+            depth_chart_mode: self.depth_chart_mode,
+            show_twap: self.show_twap,
+            pin_price_axis: self.pin_price_axis,
+            show_candles: self.show_candles,
+            candle_interval_secs: self.candle_interval_secs,
+            show_level_age: self.show_level_age,
+            show_imbalance_sparkline: self.show_imbalance_sparkline,
+            price_history_range: self.price_history_range,
+            price_history_fidelity: self.price_history_fidelity,
+            liquid_only: self.liquid_only,
+            min_liquidity: self.min_liquidity,
+            unified_search: self.unified_search,
+        }
+    }
+
+    /// Refetches whatever the change invalidates; the price history only when its range or fidelity moved
+    pub async fn apply_settings(&mut self, settings: &Settings) {
+        let previous = self.current_settings();
+
+        self.update_interval = Duration::from_secs_f64(settings.update_interval_secs.max(0.0));
+        self.depth = settings.depth;
+        self.price_format = settings.price_format;
+        self.number_format = settings.number_format;
+        self.show_notional = settings.show_notional;
+        self.aggregation_ticks = settings.aggregation_ticks;
+        self.depth_chart_ticks = settings.depth_chart_ticks;
+        self.depth_chart_mode = settings.depth_chart_mode;
+        self.show_twap = settings.show_twap;
+        self.pin_price_axis = settings.pin_price_axis;
+        self.show_candles = settings.show_candles;
+        self.candle_interval_secs = settings.candle_interval_secs;
+        self.show_level_age = settings.show_level_age;
+        self.show_imbalance_sparkline = settings.show_imbalance_sparkline;
+        self.price_history_range = settings.price_history_range;
+        self.price_history_fidelity = settings.price_history_fidelity;
+        self.liquid_only = settings.liquid_only;
+        self.min_liquidity = settings.min_liquidity;
+        self.unified_search = settings.unified_search;
+
+        if (previous.liquid_only, previous.min_liquidity) != (settings.liquid_only, settings.min_liquidity) {
+            self.refilter_markets_keeping_selection();
+        }
+        if (previous.price_history_range, previous.price_history_fidelity)
+            != (settings.price_history_range, settings.price_history_fidelity)
+        {
+            self.reload_market_price_history().await;
+        }
+        self.needs_redraw = true;
+    }
+
+    pub fn toggle_settings(&mut self) {
+        self.show_settings = !self.show_settings;
+        if !self.show_settings {
+            self.save_settings();
+        }
+        self.needs_redraw = true;
+    }
+
+    fn save_settings(&mut self) {
+        if let Err(e) = self.current_settings().save(&self.settings_path) {
+            warn!("Failed to save settings to {}: {e}", self.settings_path.display());
+            self.error_message = Some(format!("Failed to save settings: {e}"));
+        }
+    }
+
+    pub fn move_settings_selection(&mut self, down: bool) {
+        let count = SettingField::all().len();
+        self.selected_setting = if down {
+            (self.selected_setting + 1) % count
+        } else {
+            (self.selected_setting + count - 1) % count
+        };
+        self.needs_redraw = true;
+    }
+
+    pub async fn adjust_selected_setting(&mut self, forward: bool) {
+        let Some(&field) = SettingField::all().get(self.selected_setting) else { return };
+        let mut settings = self.current_settings();
+        field.step(&mut settings, forward);
+        self.apply_settings(&settings).await;
+    }
+
+    pub async fn reset_settings(&mut self) {
+        self.apply_settings(&Settings::default()).await;
+        self.set_status_message("Settings reset to defaults".to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem(text: &str) -> String {
+        Settings::parse(text).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn a_bad_type_is_reported_by_its_key() {
+        let message = problem(r#"{"depth": "deep"}"#);
+        assert!(message.starts_with("'depth': invalid type"), "{message}");
+    }
+
+    #[test]
+    fn an_unknown_key_is_reported() {
+        assert_eq!(problem(r#"{"colour": "red"}"#), "unknown setting 'colour'");
+    }
+
+    #[test]
+    fn an_out_of_range_depth_is_reported() {
+        assert_eq!(
+            problem(&format!(r#"{{"depth": {}}}"#, MAX_ORDERBOOK_DEPTH + 1)),
+            format!("'depth' must be between 1 and {MAX_ORDERBOOK_DEPTH}, got {}", MAX_ORDERBOOK_DEPTH + 1),
+        );
+    }
+
+    #[test]
+    fn zero_intervals_are_each_reported() {
+        let message = problem(r#"{"update_interval_secs": 0, "candle_interval_secs": 0, "price_history_fidelity": 0}"#);
+        assert_eq!(
+            message,
+            "'update_interval_secs' must be above 0, got 0; \
+             'candle_interval_secs' must be at least 1; \
+             'price_history_fidelity' must be at least 1 minute",
+        );
+    }
+
+    #[test]
+    fn a_partial_file_keeps_the_other_defaults() {
+        let settings = Settings::parse(r#"{"depth": 5, "show_twap": true}"#).unwrap();
+        assert_eq!(settings, Settings { depth: 5, show_twap: true, ..Settings::default() });
+    }
+}
@@ -39,13 +39,23 @@ impl App {
             self.bot_engine.active_strategy = Some(strategy_type);
             self.show_strategy_selector = false;
             self.show_strategy_runner = true;
+            self.selected_strategy_entry = 0;
             self.needs_redraw = true;
         }
     }
 
+    pub fn toggle_current_strategy_enabled(&mut self) {
+        let Some(strategy_type) = self.get_current_strategy_type() else { return };
+        if let Some(enabled) = self.bot_engine.toggle_strategy_enabled(&strategy_type) {
+            let state = if enabled { "Enabled" } else { "Disabled" };
+            self.set_status_message(format!("{state} strategy: {}", strategy_type.name()));
+        }
+    }
+
     pub fn start_current_strategy(&mut self) -> anyhow::Result<()> {
         if let Some(strategy_type) = self.get_current_strategy_type() {
             self.bot_engine.start_strategy(strategy_type.clone())?;
+            self.resubscribe_strategy_books();
             info!("Started strategy: {}", strategy_type.name());
             self.set_status_message(format!("Started strategy: {}", strategy_type.name()));
             self.error_message = None; // Clear any previous error
@@ -56,6 +66,7 @@ impl App {
     pub fn stop_current_strategy(&mut self) {
         if let Some(strategy_type) = self.get_current_strategy_type() {
             self.bot_engine.stop_strategy(&strategy_type);
+            self.resubscribe_strategy_books();
             info!("Stopped strategy: {}", strategy_type.name());
             self.set_status_message(format!("Stopped strategy: {}", strategy_type.name()));
             self.error_message = None; // Clear any previous error
@@ -107,6 +118,7 @@ impl App {
                     // For arbitrage detector, also add all markets within the event
                     if strategy_type == crate::bot::StrategyType::ArbitrageDetector {
                         let markets_added = self.add_event_markets_to_strategy(&strategy_type, &event_id);
+                        self.sync_categorical_arbitrage_markets();
                         info!("Added {} markets from event '{}' to strategy '{}'", markets_added, event_title, strategy_type.name());
                         self.set_status_message(format!("Added event '{}' with {} markets to {}", event_title, markets_added, strategy_type.name()));
                     } else {
@@ -118,6 +130,55 @@ impl App {
         }
     }
 
+    /// Rows in the runner's selection list: events for event strategies, markets otherwise
+    fn strategy_entry_ids(&self, strategy_type: &StrategyType) -> Vec<String> {
+        let Some(strategy) = self.bot_engine.get_strategy(strategy_type) else { return Vec::new() };
+        match strategy_type.scope() {
+            crate::bot::strategy::StrategyScope::Event => strategy.selected_event_ids.clone(),
+            _ => strategy.selected_market_ids.clone(),
+        }
+    }
+
+    pub fn move_strategy_entry_selection(&mut self, down: bool) {
+        let Some(strategy_type) = self.get_current_strategy_type() else { return };
+        let count = self.strategy_entry_ids(&strategy_type).len();
+        if count == 0 {
+            return;
+        }
+        self.selected_strategy_entry = if down {
+            (self.selected_strategy_entry + 1) % count
+        } else {
+            (self.selected_strategy_entry + count - 1) % count
+        };
+        self.needs_redraw = true;
+    }
+
+    /// Removes the highlighted market or event and drops the books only it needed
+    pub fn remove_selected_strategy_entry(&mut self) {
+        let Some(strategy_type) = self.get_current_strategy_type() else { return };
+        let Some(entry_id) = self.strategy_entry_ids(&strategy_type).get(self.selected_strategy_entry).cloned() else {
+            return;
+        };
+
+        match strategy_type.scope() {
+            crate::bot::strategy::StrategyScope::Event => {
+                self.bot_engine.remove_event_from_strategy(&strategy_type, &entry_id);
+                if strategy_type == StrategyType::ArbitrageDetector {
+                    self.sync_categorical_arbitrage_markets();
+                }
+            }
+            _ => {
+                self.bot_engine.remove_market_from_strategy(&strategy_type, &entry_id);
+                self.resubscribe_strategy_books();
+            }
+        }
+
+        let remaining = self.strategy_entry_ids(&strategy_type).len();
+        self.selected_strategy_entry = self.selected_strategy_entry.min(remaining.saturating_sub(1));
+        info!("Removed {entry_id} from strategy '{}'", strategy_type.name());
+        self.set_status_message(format!("Removed {} from {}", crate::utils::short_id(&entry_id, 12), strategy_type.name()));
+    }
+
     pub fn show_strategy_market_selector(&mut self) {
         if let Some(strategy_type) = self.get_current_strategy_type() {
             match strategy_type.scope() {
@@ -165,4 +226,106 @@ impl App {
         }
         markets_added
     }
+
+    /// Checks every market of the arbitrage detector's events across its own outcomes and streams all their books
+    pub fn sync_categorical_arbitrage_markets(&mut self) {
+        let strategy_type = StrategyType::ArbitrageDetector;
+        let Some(event_ids) = self.bot_engine.get_strategy(&strategy_type).map(|s| s.selected_event_ids.clone()) else {
+            return;
+        };
+
+        let markets: Vec<crate::bot::CategoricalMarket> = self.events
+            .iter()
+            .filter(|event| event_ids.contains(&event.id))
+            .filter_map(|event| event.markets.as_ref())
+            .flatten()
+            .filter(|market| market.token_ids.len() >= 2)
+            .map(|market| crate::bot::CategoricalMarket {
+                name: market.question.clone(),
+                outcomes: market.token_ids
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, token_id)| {
+                        let outcome = market.outcomes.get(idx).cloned().unwrap_or_else(|| format!("Outcome {}", idx + 1));
+                        (token_id.clone(), outcome)
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        let outcome_count: usize = markets.iter().map(|market| market.outcomes.len()).sum();
+        info!("Tracking {} categorical markets ({outcome_count} outcome books) for arbitrage", markets.len());
+
+        self.bot_engine.set_categorical_markets(&strategy_type, markets);
+        self.resubscribe_strategy_books();
+    }
+
+    /// Streams every book a strategy needs regardless of what's on screen: categorical outcomes
+    /// plus the markets of running strategies
+    pub fn resubscribe_strategy_books(&mut self) {
+        let mut token_ids: Vec<String> = Vec::new();
+        for strategy in self.bot_engine.strategies.values() {
+            let categorical = strategy.categorical_markets
+                .iter()
+                .flat_map(|market| market.outcomes.iter().map(|(token_id, _)| token_id));
+            let running = matches!(strategy.status, crate::bot::StrategyStatus::Running) && strategy.enabled;
+            let selected = strategy.selected_market_ids.iter().filter(|_| running);
+            for token_id in categorical.chain(selected) {
+                if !token_ids.contains(token_id) {
+                    token_ids.push(token_id.clone());
+                }
+            }
+        }
+
+        // Reconnecting drops every book, so keep a live subscription that already covers the same tokens
+        let unchanged = token_ids.len() == self.strategy_orderbooks.len()
+            && token_ids.iter().all(|token_id| self.strategy_orderbooks.contains_key(token_id));
+        if unchanged && (self.strategy_websocket.is_some() || token_ids.is_empty()) {
+            return;
+        }
+        self.update_strategy_subscription(token_ids);
+    }
+
+    /// Starts a strategy from the command line with the given markets or events attached
+    pub fn auto_start_strategy(&mut self, strategy_type: &StrategyType, market_ids: &[String], event_ids: &[String]) -> anyhow::Result<()> {
+        match strategy_type.scope() {
+            crate::bot::strategy::StrategyScope::Event if !market_ids.is_empty() => {
+                anyhow::bail!("{} works on events, pass --strategy-event instead of --strategy-market", strategy_type.name());
+            }
+            crate::bot::strategy::StrategyScope::SingleMarket | crate::bot::strategy::StrategyScope::MultiMarket if !event_ids.is_empty() => {
+                anyhow::bail!("{} works on markets, pass --strategy-market instead of --strategy-event", strategy_type.name());
+            }
+            _ => {}
+        }
+
+        for token_id in market_ids {
+            let market_name = self.find_market_name_by_token_id(token_id).unwrap_or_else(|| {
+                warn!("Token {token_id} isn't in the loaded markets, adding it by ID");
+                format!("Market {}", crate::utils::short_id(token_id, 8))
+            });
+            self.bot_engine.add_market_to_strategy(strategy_type, token_id.clone(), market_name);
+        }
+
+        for event_id in event_ids {
+            let Some(event_title) = self.events.iter().find(|event| &event.id == event_id).map(|event| event.title.clone()) else {
+                anyhow::bail!("Event {event_id} isn't in the loaded events (try --include-closed or a higher --max-events)");
+            };
+            self.bot_engine.add_event_to_strategy(strategy_type, event_id.clone(), event_title);
+            if *strategy_type == StrategyType::ArbitrageDetector {
+                self.add_event_markets_to_strategy(strategy_type, event_id);
+            }
+        }
+        if *strategy_type == StrategyType::ArbitrageDetector {
+            self.sync_categorical_arbitrage_markets();
+        }
+
+        let enabled = self.bot_engine.get_strategy(strategy_type).is_some_and(|strategy| strategy.enabled);
+        if !enabled {
+            self.bot_engine.toggle_strategy_enabled(strategy_type);
+        }
+        self.bot_engine.start_strategy(strategy_type.clone())?;
+        self.resubscribe_strategy_books();
+        info!("Auto-started strategy '{}' with {} market(s) and {} event(s)", strategy_type.name(), market_ids.len(), event_ids.len());
+        Ok(())
+    }
 }
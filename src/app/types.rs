@@ -1,5 +1,37 @@
 //! Type definitions for the application
 
+use serde::{Deserialize, Serialize};
+
+use crate::config::{POLYMARKET_GAMMA_HOST, POLYMARKET_HOST, POLYMARKET_WS_HOST};
+use crate::utils::{format_si, group_thousands};
+
+/// Base URLs for the CLOB REST API, gamma and the CLOB WebSocket; production unless overridden
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Endpoints {
+    pub clob_host: String,
+    pub gamma_host: String,
+    pub ws_host: String,
+}
+
+impl Default for Endpoints {
+    fn default() -> Self {
+        Self {
+            clob_host: POLYMARKET_HOST.to_string(),
+            gamma_host: POLYMARKET_GAMMA_HOST.to_string(),
+            ws_host: POLYMARKET_WS_HOST.to_string(),
+        }
+    }
+}
+
+/// What `App::new` connects to and how, beyond the view settings
+#[derive(Clone)]
+pub struct AppOptions {
+    pub endpoints: Endpoints,
+    pub allow_read_only: bool, // Failing to get API credentials leaves the app on public data instead of erroring
+    pub requests_per_second: f64, // REST budget shared by every client call; clamped to at least 0.1
+    pub start: StartView,
+}
+
 #[derive(Default, Clone, Copy, PartialEq, Eq)]
 pub enum SelectedTab {
     #[default]
@@ -25,11 +57,24 @@ impl SelectedTab {
     }
 }
 
+impl std::str::FromStr for SelectedTab {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "orderbook" | "book" => Ok(Self::Orderbook),
+            "history" | "price-history" => Ok(Self::PriceHistory),
+            _ => Err(format!("unknown view '{value}', expected one of: orderbook, history")),
+        }
+    }
+}
+
 #[derive(Default, Clone, Copy, PartialEq, Eq)]
 pub enum MarketSelectorTab {
     #[default]
     AllMarkets,
     Events,
+    Categories,
     Strategies,
 }
 
@@ -39,7 +84,8 @@ impl MarketSelectorTab {
         match self {
             Self::AllMarkets => Self::Strategies,
             Self::Events => Self::AllMarkets,
-            Self::Strategies => Self::Events,
+            Self::Categories => Self::Events,
+            Self::Strategies => Self::Categories,
         }
     }
 
@@ -47,8 +93,204 @@ impl MarketSelectorTab {
     pub fn next(self) -> Self {
         match self {
             Self::AllMarkets => Self::Events,
-            Self::Events => Self::Strategies,
+            Self::Events => Self::Categories,
+            Self::Categories => Self::Strategies,
             Self::Strategies => Self::AllMarkets,
         }
     }
 }
+
+impl std::str::FromStr for MarketSelectorTab {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "markets" | "all" => Ok(Self::AllMarkets),
+            "events" => Ok(Self::Events),
+            "categories" => Ok(Self::Categories),
+            "strategies" => Ok(Self::Strategies),
+            _ => Err(format!("unknown tab '{value}', expected one of: markets, events, categories, strategies")),
+        }
+    }
+}
+
+/// Where the app opens: the market selector tab and the tab shown once a market is loaded
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+pub struct StartView {
+    pub market_tab: MarketSelectorTab,
+    pub tab: SelectedTab,
+}
+
+impl StartView {
+    /// An unknown name falls back to that value's default; the problems are returned for the status line
+    pub fn parse(market_tab: &str, tab: &str) -> (Self, Vec<String>) {
+        fn or_default<T: std::str::FromStr<Err = String> + Default>(value: &str, problems: &mut Vec<String>) -> T {
+            value.parse().unwrap_or_else(|e| {
+                problems.push(e);
+                T::default()
+            })
+        }
+        let mut problems = Vec::new();
+        let start = Self {
+            market_tab: or_default(market_tab, &mut problems),
+            tab: or_default(tab, &mut problems),
+        };
+        (start, problems)
+    }
+}
+
+/// Presentation of prices, which are always stored as 0..1
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PriceFormat {
+    #[default]
+    Decimal,
+    Cents,
+    Percent,
+}
+
+impl PriceFormat {
+    pub const ALL: [Self; 3] = [Self::Decimal, Self::Cents, Self::Percent];
+
+    /// Cycle to the next format.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Decimal => Self::Cents,
+            Self::Cents => Self::Percent,
+            Self::Percent => Self::Decimal,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Decimal => "Decimal",
+            Self::Cents => "Cents",
+            Self::Percent => "Percent",
+        }
+    }
+
+    /// Format a 0..1 price (or price difference) given the decimal places of the raw value
+    pub fn format(self, price: f64, decimal_places: usize) -> String {
+        // Scaling by 100 moves two decimal places in front of the point
+        let scaled_places = decimal_places.saturating_sub(2);
+        match self {
+            Self::Decimal => format!("{price:.decimal_places$}"),
+            Self::Cents => format!("{:.scaled_places$}¢", price * 100.0),
+            Self::Percent => format!("{:.scaled_places$}%", price * 100.0),
+        }
+    }
+}
+
+/// Presentation of sizes, totals and depths; `Plain` is the raw `{:.N}` output
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NumberFormat {
+    #[default]
+    Plain,
+    Grouped,
+    Compact,
+}
+
+impl NumberFormat {
+    pub const ALL: [Self; 3] = [Self::Plain, Self::Grouped, Self::Compact];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Plain => "Plain",
+            Self::Grouped => "1,234",
+            Self::Compact => "1.2k",
+        }
+    }
+
+    pub fn format(self, value: f64, decimal_places: usize) -> String {
+        match self {
+            Self::Plain => format!("{value:.decimal_places$}"),
+            Self::Grouped => group_thousands(value, decimal_places),
+            Self::Compact => format_si(value, decimal_places),
+        }
+    }
+}
+
+/// Time window requested from the price history API
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PriceHistoryRange {
+    Hour,
+    SixHours,
+    Day,
+    Week,
+    #[default]
+    Max,
+}
+
+impl PriceHistoryRange {
+    pub const ALL: [Self; 5] = [Self::Hour, Self::SixHours, Self::Day, Self::Week, Self::Max];
+
+    /// Cycle to the next range.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Hour => Self::SixHours,
+            Self::SixHours => Self::Day,
+            Self::Day => Self::Week,
+            Self::Week => Self::Max,
+            Self::Max => Self::Hour,
+        }
+    }
+
+    /// Interval parameter understood by the prices-history endpoint
+    pub fn interval(self) -> &'static str {
+        match self {
+            Self::Hour => "1h",
+            Self::SixHours => "6h",
+            Self::Day => "1d",
+            Self::Week => "1w",
+            Self::Max => "max",
+        }
+    }
+}
+
+/// Charts of the orderbook view that can be hidden to give the others room
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChartKind {
+    Crypto, // Underlying coin prices, for crypto markets only
+    PriceHistory, // Realtime price chart with its RSI panel
+    Depth,
+}
+
+impl ChartKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Crypto => "Crypto charts",
+            Self::PriceHistory => "Price chart",
+            Self::Depth => "Depth chart",
+        }
+    }
+}
+
+/// The value a `NumericInput` overlay edits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumericInputTarget {
+    DepthBps,
+}
+
+/// What the depth chart plots at each price
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DepthChartMode {
+    /// Cumulative depth, held flat across empty ticks up to the next level: the envelope of the book
+    #[default]
+    Cumulative,
+    /// The size resting at each level only, zero in between, so a sparse book reads as sparse
+    Levels,
+}
+
+impl DepthChartMode {
+    pub const ALL: [Self; 2] = [Self::Cumulative, Self::Levels];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Cumulative => "cumulative",
+            Self::Levels => "levels",
+        }
+    }
+}
@@ -2,18 +2,19 @@
 
 use anyhow::Result;
 use std::{
+    collections::HashSet,
     sync::{Arc, Mutex},
     time::{Duration, Instant},
 };
 use cli_log::*;
 
 use crate::{
-    config::{WS_MAX_ATTEMPTS, WS_RECONNECT_DELAY_SECS},
-    data::{OrderBookData, SimpleOrder}
+    config::{WS_MAX_ATTEMPTS, WS_RECONNECT_DELAY_SECS, USER_FILLS_MAX, UNKNOWN_WS_PAYLOADS_KEPT},
+    data::{diff_levels, merge_duplicate_levels, OrderBookData, SimpleOrder, TradeTick}
 };
 use crate::websocket::{
     BookMessage, LastTradePriceMessage, PolymarketWebSocket, PolymarketWebSocketMessage,
-    PriceChangeMessage, MessageCallback,
+    PriceChangeMessage, UserOrderMessage, UserTradeMessage, MessageCallback,
 };
 use super::core::App;
 
@@ -40,59 +41,157 @@ pub fn process_websocket_updates(app: &mut App) -> Result<()> {
 }
 
 fn check_websocket_health(app: &mut App) {
+    check_websocket_stall(app);
+
     if let Some(ref ws) = app.current_websocket {
         if ws.thread_handle.is_finished() {
             warn!("WebSocket thread terminated, reconnecting");
             app.current_websocket = None;
+            app.websocket_reconnecting = true;
+            app.pending_book_refresh = true;
             if let Some(ref orderbook) = app.orderbook {
                 try_reconnect_websocket(app, &orderbook.token_id.clone());
             }
             app.needs_redraw = true;
         }
     }
+    
+    if let Some(ref ws) = app.user_websocket {
+        if ws.thread_handle.is_finished() {
+            // Restarted on the next token selection
+            warn!("User channel WebSocket thread terminated");
+            app.user_websocket = None;
+            app.needs_redraw = true;
+        }
+    }
 }
 
-fn apply_websocket_update(app: &mut App, update: PolymarketWebSocketMessage) -> Result<()> {
-    let orderbook = match &mut app.orderbook {
-        Some(ob) => ob,
-        None => return Ok(()),
+/// A connection that stays up but goes silent is dropped so the book is polled over REST,
+/// while a replacement connects on the side and takes over once it hears from the server
+fn check_websocket_stall(app: &mut App) {
+    let (Some(timeout), Some(token_id)) = (app.ws_stale_timeout, app.orderbook.as_ref().map(|orderbook| orderbook.token_id.clone())) else {
+        return;
     };
+
+    let stalled = app.current_websocket.as_ref()
+        .is_some_and(|ws| !ws.thread_handle.is_finished() && ws.silent_for() >= timeout);
+    if stalled {
+        warn!("WebSocket silent for {}s, falling back to REST polling", timeout.as_secs());
+        app.current_websocket = None;
+        app.websocket_stalled = true;
+        app.recovery_websocket = None;
+        app.set_status_message(format!("WebSocket silent for {}s, polling REST", timeout.as_secs()));
+    }
+    if !app.websocket_stalled {
+        return;
+    }
+
+    match app.recovery_websocket.as_ref() {
+        Some(ws) if ws.has_received() && !ws.thread_handle.is_finished() => {
+            info!("Recovery WebSocket is live, leaving REST polling");
+            app.current_websocket = app.recovery_websocket.take();
+            app.websocket_stalled = false;
+            app.reset_websocket_reconnect_counter();
+            app.set_status_message("WebSocket live again".to_string());
+        }
+        Some(ws) if !ws.thread_handle.is_finished() && ws.silent_for() < timeout => {}
+        _ if app.last_websocket_attempt.elapsed() >= Duration::from_secs(WS_RECONNECT_DELAY_SECS) => {
+            info!("Connecting recovery WebSocket for token: {token_id}");
+            app.last_websocket_attempt = Instant::now();
+            app.recovery_websocket = Some(connect_market_websocket(app, &token_id));
+        }
+        _ => {}
+    }
+}
+
+fn apply_websocket_update(app: &mut App, update: PolymarketWebSocketMessage) -> Result<()> {
+    match update {
+        PolymarketWebSocketMessage::UserOrder(order_msg) => apply_user_order_update(app, order_msg),
+        PolymarketWebSocketMessage::UserTrade(trade_msg) => apply_user_trade_update(app, trade_msg),
+        market_update => return apply_market_update(app, market_update),
+    }
+    app.needs_redraw = true;
+    Ok(())
+}
+
+fn apply_user_order_update(app: &mut App, order_msg: UserOrderMessage) {
+    app.open_orders.retain(|o| o.id != order_msg.id);
     
-    let asset_matches = match &update {
-        PolymarketWebSocketMessage::Book(msg) => msg.asset_id == orderbook.token_id,
-        PolymarketWebSocketMessage::PriceChange(msg) => msg.asset_id == orderbook.token_id,
-        PolymarketWebSocketMessage::LastTradePrice(msg) => msg.asset_id == orderbook.token_id,
-        PolymarketWebSocketMessage::TickSizeChange(msg) => msg.asset_id == orderbook.token_id,
-        PolymarketWebSocketMessage::Unknown(_) => false,
+    let original_size = order_msg.original_size.parse::<f64>().unwrap_or(0.0);
+    let size_matched = order_msg.size_matched.parse::<f64>().unwrap_or(0.0);
+    let is_open = order_msg.order_type != "CANCELLATION" && size_matched < original_size;
+    if is_open {
+        app.open_orders.push(order_msg);
+    }
+}
+
+fn apply_user_trade_update(app: &mut App, trade_msg: UserTradeMessage) {
+    // Trades are re-sent as their status progresses, keep only the latest state
+    app.recent_fills.retain(|t| t.id != trade_msg.id);
+    app.recent_fills.insert(0, trade_msg);
+    app.recent_fills.truncate(USER_FILLS_MAX);
+}
+
+fn apply_market_update(app: &mut App, update: PolymarketWebSocketMessage) -> Result<()> {
+    let asset_id = match &update {
+        PolymarketWebSocketMessage::Book(msg) => &msg.asset_id,
+        PolymarketWebSocketMessage::PriceChange(msg) => &msg.asset_id,
+        PolymarketWebSocketMessage::LastTradePrice(msg) => &msg.asset_id,
+        PolymarketWebSocketMessage::TickSizeChange(msg) => &msg.asset_id,
+        PolymarketWebSocketMessage::UserOrder(_) |
+        PolymarketWebSocketMessage::UserTrade(_) |
+        PolymarketWebSocketMessage::Unknown(_) => return Ok(()),
     };
     
-    if !asset_matches {
+    // The subscription also carries the sibling outcome of a binary market, which is cached on the side
+    let is_primary = app.orderbook.as_ref().is_some_and(|orderbook| &orderbook.token_id == asset_id);
+    let orderbook = if is_primary {
+        app.orderbook.as_mut()
+    } else {
+        // Besides the sibling, the selector's pre-warmed book streams through the same queue
+        app.sibling_orderbook.as_mut().filter(|sibling| &sibling.token_id == asset_id)
+            .or(app.prewarm_orderbook.as_mut().filter(|prewarmed| &prewarmed.token_id == asset_id))
+    };
+    let Some(orderbook) = orderbook else {
         return Ok(());
-    }
+    };
+    // Everything but a tick size change recomputes the midpoint
+    let midpoint_recomputed = is_primary && !matches!(update, PolymarketWebSocketMessage::TickSizeChange(_));
     
     match update {
         PolymarketWebSocketMessage::Book(book_msg) => {
-            apply_book_update_static(orderbook, &book_msg, app.depth)?;
+            let previous = orderbook.best_prices();
+            apply_book_update_static(orderbook, &book_msg, app.depth, app.highlight_duration)?;
+            if is_primary {
+                orderbook.check_snapshot_jump(previous, app.book_jump_ticks);
+                app.last_book_snapshot = Instant::now();
+                app.websocket_reconnecting = false;
+            }
         }
         PolymarketWebSocketMessage::PriceChange(price_msg) => {
-            apply_price_changes_static(orderbook, &price_msg, app.depth)?;
+            let epsilon = orderbook.price_epsilon();
+            apply_price_changes_static(orderbook, &price_msg, app.depth, epsilon, app.highlight_duration)?;
         }
         PolymarketWebSocketMessage::LastTradePrice(trade_msg) => {
-            apply_trade_update_static(orderbook, &trade_msg)?;
+            apply_trade_update_static(orderbook, &trade_msg, app.large_trade_size, app.trade_activity_window)?;
         }
         PolymarketWebSocketMessage::TickSizeChange(tick_msg) => {
             if let Ok(new_tick_size) = tick_msg.new_tick_size.parse::<f64>() {
-                orderbook.tick_size = new_tick_size;
+                orderbook.set_tick_size(new_tick_size);
             }
         }
-        PolymarketWebSocketMessage::Unknown(_) => return Ok(()),
+        _ => return Ok(()),
     }
     
+    if midpoint_recomputed {
+        app.log_midpoint();
+    }
     app.needs_redraw = true;
     Ok(())
 }
 
-fn apply_book_update_static(orderbook: &mut OrderBookData, book_msg: &BookMessage, depth: usize) -> Result<()> {
+/// Replaces the book with a `book` snapshot, cut to `depth` levels per side
+pub fn apply_book_update_static(orderbook: &mut OrderBookData, book_msg: &BookMessage, depth: usize, highlight: Duration) -> Result<()> {
     // Convert WebSocket book data to our SimpleOrder format
     let mut new_bids = Vec::new();
     for bid in &book_msg.bids {
@@ -108,26 +207,38 @@ fn apply_book_update_static(orderbook: &mut OrderBookData, book_msg: &BookMessag
         }
     }
     
-    // Sort and limit orders
+    // Sort, fold repeated prices into one level and limit orders
+    let epsilon = orderbook.price_epsilon();
     new_bids.sort_by(|a, b| b.price.partial_cmp(&a.price).unwrap_or(std::cmp::Ordering::Equal));
+    merge_duplicate_levels(&mut new_bids, epsilon);
     new_bids.truncate(depth);
     
     new_asks.sort_by(|a, b| a.price.partial_cmp(&b.price).unwrap_or(std::cmp::Ordering::Equal));
+    merge_duplicate_levels(&mut new_asks, epsilon);
     new_asks.truncate(depth);
+
+    // Highlight what the snapshot changed; levels that survive it keep their age
+    let now = Instant::now();
+    diff_levels(&mut new_bids, &orderbook.bids, epsilon, now, highlight);
+    diff_levels(&mut new_asks, &orderbook.asks, epsilon, now, highlight);
     
     // Update orderbook
     orderbook.bids = new_bids;
     orderbook.asks = new_asks;
     orderbook.last_updated = chrono::Utc::now();
-    orderbook.chart_needs_recentering = true; // Re-center chart on updates
+    orderbook.chart_needs_recentering = !orderbook.chart_locked; // Re-center chart on updates unless locked
+    orderbook.record_open_midpoint_if_missing();
     
     // Recalculate market stats and update price history
-    orderbook.price_history.add_price(orderbook.get_midpoint());
+    orderbook.record_midpoint();
     
     Ok(())
 }
 
-fn apply_price_changes_static(orderbook: &mut OrderBookData, price_msg: &PriceChangeMessage, depth: usize) -> Result<()> {
+/// Applies `price_change` deltas, where each size is the level's new total and 0 removes it
+pub fn apply_price_changes_static(orderbook: &mut OrderBookData, price_msg: &PriceChangeMessage, depth: usize, epsilon: f64, highlight: Duration) -> Result<()> {
+    let previous_bids = orderbook.bids.clone();
+    let previous_asks = orderbook.asks.clone();
     for change in &price_msg.changes {
         let (price, size) = match (change.price.parse::<f64>(), change.size.parse::<f64>()) {
             (Ok(p), Ok(s)) => (p, s),
@@ -143,17 +254,17 @@ fn apply_price_changes_static(orderbook: &mut OrderBookData, price_msg: &PriceCh
             }
         };
         
-        // Update or remove existing order
-        if let Some(existing_order) = orders.iter_mut().find(|o| (o.price - price).abs() < 0.0001) {
-            if size == 0.0 {
-                orders.retain(|o| (o.price - price).abs() >= 0.0001);
-            } else {
-                existing_order.update_size(size);
-            }
-        } else if size > 0.0 {
+        // The size is the level's new total, so it replaces every level at the price;
+        // ages and highlights are carried over by the diff below
+        orders.retain(|o| (o.price - price).abs() >= epsilon);
+        if size > 0.0 {
             orders.push(SimpleOrder::new(price, size));
         }
     }
+
+    let now = Instant::now();
+    diff_levels(&mut orderbook.bids, &previous_bids, epsilon, now, highlight);
+    diff_levels(&mut orderbook.asks, &previous_asks, epsilon, now, highlight);
     
     // Re-sort and limit orders
     orderbook.bids.sort_by(|a, b| b.price.partial_cmp(&a.price).unwrap_or(std::cmp::Ordering::Equal));
@@ -162,15 +273,28 @@ fn apply_price_changes_static(orderbook: &mut OrderBookData, price_msg: &PriceCh
     orderbook.asks.truncate(depth);
     
     orderbook.last_updated = chrono::Utc::now();
-    orderbook.chart_needs_recentering = true;
+    orderbook.chart_needs_recentering = !orderbook.chart_locked;
+    orderbook.record_open_midpoint_if_missing();
 
-    orderbook.price_history.add_price(orderbook.get_midpoint());    
+    orderbook.record_midpoint();
     Ok(())
 }
 
-fn apply_trade_update_static(orderbook: &mut OrderBookData, _trade_msg: &LastTradePriceMessage) -> Result<()> {
+fn apply_trade_update_static(orderbook: &mut OrderBookData, trade_msg: &LastTradePriceMessage, large_trade_size: f64, activity_window: chrono::Duration) -> Result<()> {
+    if let (Ok(price), Ok(size)) = (trade_msg.price.parse::<f64>(), trade_msg.size.parse::<f64>()) {
+        let timestamp = trade_msg.timestamp.parse::<i64>().ok()
+            .and_then(chrono::DateTime::from_timestamp_millis)
+            .unwrap_or_else(chrono::Utc::now);
+        orderbook.record_trade(TradeTick {
+            timestamp,
+            price,
+            size,
+            is_buy: trade_msg.side.eq_ignore_ascii_case("buy"),
+        }, large_trade_size, activity_window);
+    }
+
     orderbook.last_updated = chrono::Utc::now();
-    orderbook.price_history.add_price(orderbook.get_midpoint());
+    orderbook.record_midpoint();
     Ok(())
 }
 
@@ -196,16 +320,51 @@ fn start_websocket_for_token(app: &mut App, token_id: &str) {
         info!("Closing existing WebSocket connection");
         app.current_websocket = None;
     }
+    app.recovery_websocket = None;
+    app.websocket_stalled = false;
+    
+    // The selector may already have a live subscription to exactly this market
+    if let Some(ws) = app.take_prewarmed_websocket(token_id) {
+        info!("Using pre-warmed WebSocket for token: {token_id}");
+        app.current_websocket = Some(ws);
+    } else {
+        app.current_websocket = Some(connect_market_websocket(app, token_id));
+    }
+    
+    info!("WebSocket started for token: {token_id}");
     
+    if app.user_websocket.is_none() && app.user_channel_enabled {
+        start_user_websocket(app);
+    }
+}
+
+/// Market channel connection for a token and, for binary markets, its sibling, queued into `websocket_updates`
+pub(super) fn connect_market_websocket(app: &App, token_id: &str) -> PolymarketWebSocket {
     let updates_arc: Arc<Mutex<Vec<PolymarketWebSocketMessage>>> = Arc::clone(&app.websocket_updates);
-    let token_id_owned = token_id.to_string();
+    let unknown_arc = Arc::clone(&app.unknown_ws_payloads);
+    // Binary markets also stream the other outcome so switching to it is instant
+    let mut token_ids = vec![token_id.to_string()];
+    token_ids.extend(app.sibling_token_id(token_id));
+    let subscribed = token_ids.clone();
     
     let callback: MessageCallback = Box::new(move |msg| {
+        // Kept for session exports, where they show what the parser is missing
+        if let PolymarketWebSocketMessage::Unknown(payload) = &msg {
+            if let Ok(mut payloads) = unknown_arc.lock() {
+                payloads.push_back(payload.clone());
+                if payloads.len() > UNKNOWN_WS_PAYLOADS_KEPT {
+                    payloads.pop_front();
+                }
+            }
+            return;
+        }
         let message_matches = match &msg {
-            PolymarketWebSocketMessage::Book(book_msg) => book_msg.asset_id == token_id_owned,
-            PolymarketWebSocketMessage::PriceChange(price_msg) => price_msg.asset_id == token_id_owned,
-            PolymarketWebSocketMessage::TickSizeChange(tick_msg) => tick_msg.asset_id == token_id_owned,
-            PolymarketWebSocketMessage::LastTradePrice(trade_msg) => trade_msg.asset_id == token_id_owned,
+            PolymarketWebSocketMessage::Book(book_msg) => subscribed.contains(&book_msg.asset_id),
+            PolymarketWebSocketMessage::PriceChange(price_msg) => subscribed.contains(&price_msg.asset_id),
+            PolymarketWebSocketMessage::TickSizeChange(tick_msg) => subscribed.contains(&tick_msg.asset_id),
+            PolymarketWebSocketMessage::LastTradePrice(trade_msg) => subscribed.contains(&trade_msg.asset_id),
+            PolymarketWebSocketMessage::UserOrder(_) |
+            PolymarketWebSocketMessage::UserTrade(_) |
             PolymarketWebSocketMessage::Unknown(_) => false,
         };
         
@@ -220,17 +379,177 @@ fn start_websocket_for_token(app: &mut App, token_id: &str) {
         }
     });
     
-    app.current_websocket = Some(PolymarketWebSocket::connect(
+    PolymarketWebSocket::connect(
+        &app.endpoints.ws_host,
         "market".into(),
         None,
-        vec![token_id.to_string()],
+        token_ids,
         callback,
-    ));
+    )
+}
+
+fn start_user_websocket(app: &mut App) {
+    let creds = match app.api_creds {
+        Some(ref creds) => creds,
+        None => return, // Not authenticated, nothing to subscribe to
+    };
     
-    info!("WebSocket started for token: {token_id}");
+    info!("Starting user channel WebSocket");
+    let auth = serde_json::json!({
+        "apiKey": creds.api_key,
+        "secret": creds.secret,
+        "passphrase": creds.passphrase,
+    });
+    
+    let updates_arc: Arc<Mutex<Vec<PolymarketWebSocketMessage>>> = Arc::clone(&app.websocket_updates);
+    let callback: MessageCallback = Box::new(move |msg| {
+        if matches!(msg, PolymarketWebSocketMessage::UserOrder(_) | PolymarketWebSocketMessage::UserTrade(_)) {
+            if let Ok(mut updates) = updates_arc.lock() {
+                updates.push(msg);
+            }
+        }
+    });
+    
+    // No market filter: own orders are kept for every market and filtered when rendered
+    app.user_websocket = Some(PolymarketWebSocket::connect(
+        &app.endpoints.ws_host,
+        "user".into(),
+        Some(auth),
+        Vec::new(),
+        callback,
+    ));
+}
+
+/// Applies queued updates to the strategy books and feeds every changed book to the bot engine
+pub fn process_strategy_websocket_updates(app: &mut App) -> Result<()> {
+    let dropped = app.strategy_websocket.as_ref().is_some_and(|ws| ws.thread_handle.is_finished());
+    if dropped && app.last_strategy_websocket_attempt.elapsed() >= Duration::from_secs(WS_RECONNECT_DELAY_SECS) {
+        warn!("Strategy WebSocket thread terminated, reconnecting");
+        let token_ids: Vec<String> = app.strategy_orderbooks.keys().cloned().collect();
+        start_strategy_websocket(app, token_ids);
+    }
+
+    let updates = match app.strategy_websocket_updates.try_lock() {
+        Ok(mut guard) if !guard.is_empty() => std::mem::take(&mut *guard),
+        _ => return Ok(()),
+    };
+
+    let mut changed = HashSet::new();
+    for update in updates {
+        let asset_id = match &update {
+            PolymarketWebSocketMessage::Book(msg) => &msg.asset_id,
+            PolymarketWebSocketMessage::PriceChange(msg) => &msg.asset_id,
+            PolymarketWebSocketMessage::TickSizeChange(msg) => &msg.asset_id,
+            _ => continue,
+        };
+        let Some(orderbook) = app.strategy_orderbooks.get_mut(asset_id) else { continue };
+
+        match &update {
+            PolymarketWebSocketMessage::Book(book_msg) => apply_book_update_static(orderbook, book_msg, app.depth, app.highlight_duration)?,
+            PolymarketWebSocketMessage::PriceChange(price_msg) => {
+                let epsilon = orderbook.price_epsilon();
+                apply_price_changes_static(orderbook, price_msg, app.depth, epsilon, app.highlight_duration)?;
+            }
+            PolymarketWebSocketMessage::TickSizeChange(tick_msg) => {
+                if let Ok(new_tick_size) = tick_msg.new_tick_size.parse::<f64>() {
+                    orderbook.set_tick_size(new_tick_size);
+                }
+            }
+            _ => continue,
+        }
+        changed.insert(orderbook.token_id.clone());
+    }
+
+    for token_id in changed {
+        if let Some(orderbook) = app.strategy_orderbooks.get(&token_id) {
+            app.bot_engine.process_orderbook(orderbook)?;
+        }
+    }
+    Ok(())
+}
+
+/// Subscribes to every token in one market channel connection, replacing any previous strategy subscription
+fn start_strategy_websocket(app: &mut App, token_ids: Vec<String>) {
+    app.strategy_websocket = None;
+    app.last_strategy_websocket_attempt = Instant::now();
+    app.strategy_orderbooks.retain(|token_id, _| token_ids.contains(token_id));
+    if token_ids.is_empty() {
+        return;
+    }
+
+    for token_id in &token_ids {
+        app.strategy_orderbooks.entry(token_id.clone())
+            .or_insert_with(|| OrderBookData::empty(token_id.clone(), String::new()));
+    }
+
+    let updates_arc = Arc::clone(&app.strategy_websocket_updates);
+    // The subscription changes live, so assets are filtered against `strategy_orderbooks` when applied instead
+    let callback: MessageCallback = Box::new(move |msg| {
+        let message_matches = matches!(msg,
+            PolymarketWebSocketMessage::Book(_) |
+            PolymarketWebSocketMessage::PriceChange(_) |
+            PolymarketWebSocketMessage::TickSizeChange(_));
+
+        if !message_matches {
+            return;
+        }
+        if let Ok(mut updates) = updates_arc.lock() {
+            updates.push(msg);
+            // Several books share this buffer, so only trim once it grows well past one snapshot each
+            if updates.len() > 500 {
+                updates.drain(0..250);
+            }
+        }
+    });
+
+    info!("Starting strategy WebSocket for {} outcome tokens", token_ids.len());
+    app.strategy_websocket = Some(PolymarketWebSocket::connect(
+        &app.endpoints.ws_host,
+        "market".into(),
+        None,
+        token_ids,
+        callback,
+    ));
+}
+
+/// Moves a live strategy connection to `token_ids` without reconnecting, so books that stay keep their state.
+/// Falls back to a fresh connection when there is none or it has ended.
+fn update_strategy_subscription(app: &mut App, token_ids: Vec<String>) {
+    let Some(ws) = app.strategy_websocket.as_ref().filter(|ws| !ws.thread_handle.is_finished() && !token_ids.is_empty()) else {
+        start_strategy_websocket(app, token_ids);
+        return;
+    };
+
+    let removed: Vec<String> = app.strategy_orderbooks.keys()
+        .filter(|token_id| !token_ids.contains(token_id))
+        .cloned()
+        .collect();
+    let added: Vec<String> = token_ids.iter()
+        .filter(|token_id| !app.strategy_orderbooks.contains_key(*token_id))
+        .cloned()
+        .collect();
+    if !ws.unsubscribe_assets(&removed) || !ws.subscribe_assets(&added) {
+        warn!("Strategy WebSocket closed while resubscribing, reconnecting");
+        start_strategy_websocket(app, token_ids);
+        return;
+    }
+
+    info!("Strategy WebSocket resubscribed: {} added, {} removed", added.len(), removed.len());
+    app.strategy_orderbooks.retain(|token_id, _| token_ids.contains(token_id));
+    for token_id in added {
+        app.strategy_orderbooks.insert(token_id.clone(), OrderBookData::empty(token_id, String::new()));
+    }
 }
 
 impl App {
+    pub fn start_strategy_websocket(&mut self, token_ids: Vec<String>) {
+        start_strategy_websocket(self, token_ids);
+    }
+
+    pub fn update_strategy_subscription(&mut self, token_ids: Vec<String>) {
+        update_strategy_subscription(self, token_ids);
+    }
+
     pub fn start_websocket_for_token(&mut self, token_id: &str) {
         start_websocket_for_token(self, token_id);
     }
@@ -242,4 +561,112 @@ impl App {
     pub fn reset_websocket_reconnect_counter(&mut self) {
         self.websocket_reconnect_attempts = 0;
     }
+
+    /// Manual escape hatch for a connection that is still up but has stopped delivering data
+    pub fn force_reconnect_websocket(&mut self) {
+        let Some(token_id) = self.orderbook.as_ref().map(|orderbook| orderbook.token_id.clone()) else {
+            return;
+        };
+        info!("Manual WebSocket reconnect for token: {token_id}");
+        self.current_websocket = None;
+        self.reset_websocket_reconnect_counter();
+        self.last_websocket_attempt = Instant::now();
+        // Shown until the new connection's first snapshot; the REST refresh covers the gap
+        self.websocket_reconnecting = true;
+        self.pending_book_refresh = true;
+        start_websocket_for_token(self, &token_id);
+        self.set_status_message("Reconnecting…".to_string());
+    }
+
+    /// Connects or drops the user channel; the market channel is unaffected
+    pub fn toggle_user_channel(&mut self) {
+        if self.api_creds.is_none() {
+            self.set_status_message("User channel needs API credentials".to_string());
+            return;
+        }
+        self.user_channel_enabled = !self.user_channel_enabled;
+        if self.user_channel_enabled {
+            start_user_websocket(self);
+            self.set_status_message("User channel on".to_string());
+        } else {
+            info!("Closing user channel WebSocket");
+            self.user_websocket = None;
+            self.open_orders.clear();
+            self.recent_fills.clear();
+            self.set_status_message("User channel off".to_string());
+        }
+        self.needs_redraw = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::websocket::clob::{OrderSummary, PriceChange};
+
+    const HIGHLIGHT: Duration = Duration::from_millis(1000);
+
+    fn levels(levels: &[(&str, &str)]) -> Vec<OrderSummary> {
+        levels.iter().map(|&(price, size)| OrderSummary { price: price.to_string(), size: size.to_string() }).collect()
+    }
+
+    fn book(bids: &[(&str, &str)], asks: &[(&str, &str)]) -> BookMessage {
+        BookMessage {
+            event_type: "book".to_string(),
+            asset_id: "token".to_string(),
+            market: "0xmarket".to_string(),
+            timestamp: "0".to_string(),
+            hash: "0x0".to_string(),
+            bids: levels(bids),
+            asks: levels(asks),
+        }
+    }
+
+    fn price_change(changes: &[(&str, &str, &str)]) -> PriceChangeMessage {
+        PriceChangeMessage {
+            event_type: "price_change".to_string(),
+            asset_id: "token".to_string(),
+            market: "0xmarket".to_string(),
+            timestamp: "0".to_string(),
+            hash: "0x0".to_string(),
+            changes: changes.iter()
+                .map(|&(side, price, size)| PriceChange { side: side.to_string(), price: price.to_string(), size: size.to_string() })
+                .collect(),
+        }
+    }
+
+    fn sizes(orders: &[SimpleOrder]) -> Vec<(f64, f64)> {
+        orders.iter().map(|order| (order.price, order.size)).collect()
+    }
+
+    #[test]
+    fn price_changes_match_levels_within_half_a_tick() {
+        let mut orderbook = OrderBookData::empty("token".to_string(), String::new());
+        orderbook.set_tick_size(0.001);
+        apply_book_update_static(&mut orderbook, &book(&[("0.500", "100"), ("0.499", "50")], &[("0.501", "80")]), 10, HIGHLIGHT).unwrap();
+
+        // Off the grid by less than half a tick: the fixed 0.0001 tolerance would add a second 0.500 row
+        let epsilon = orderbook.price_epsilon();
+        apply_price_changes_static(&mut orderbook, &price_change(&[("BUY", "0.5004", "70")]), 10, epsilon, HIGHLIGHT).unwrap();
+        assert_eq!(sizes(&orderbook.bids), vec![(0.5004, 70.0), (0.499, 50.0)]);
+
+        // Removing the neighbouring tick leaves the level one tick away alone
+        apply_price_changes_static(&mut orderbook, &price_change(&[("BUY", "0.499", "0")]), 10, epsilon, HIGHLIGHT).unwrap();
+        assert_eq!(sizes(&orderbook.bids), vec![(0.5004, 70.0)]);
+    }
+
+    #[test]
+    fn snapshot_with_duplicate_prices_merges_them_into_one_level() {
+        let mut orderbook = OrderBookData::empty("token".to_string(), String::new());
+        orderbook.set_tick_size(0.01);
+        let snapshot = book(
+            &[("0.48", "10"), ("0.50", "100"), ("0.48", "15"), ("0.50", "25")],
+            &[("0.52", "40"), ("0.52", "60")],
+        );
+
+        apply_book_update_static(&mut orderbook, &snapshot, 10, HIGHLIGHT).unwrap();
+
+        assert_eq!(sizes(&orderbook.bids), vec![(0.50, 125.0), (0.48, 25.0)]);
+        assert_eq!(sizes(&orderbook.asks), vec![(0.52, 100.0)]);
+    }
 }
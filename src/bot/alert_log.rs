@@ -0,0 +1,67 @@
+use anyhow::Result;
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+};
+
+use super::strategy::{StrategyAlert, StrategyType};
+
+/// Appends strategy alerts to one CSV file per strategy
+#[derive(Debug, Clone)]
+pub struct AlertCsvLogger {
+    pub dir: PathBuf,
+}
+
+impl AlertCsvLogger {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn path_for(&self, strategy_type: &StrategyType) -> PathBuf {
+        self.dir.join(format!("{}.csv", strategy_type.slug()))
+    }
+
+    pub fn append(&self, strategy_type: &StrategyType, alerts: &[StrategyAlert]) -> Result<()> {
+        if alerts.is_empty() {
+            return Ok(());
+        }
+
+        fs::create_dir_all(&self.dir)?;
+        let path = self.path_for(strategy_type);
+        let write_header = fs::metadata(&path).map(|m| m.len() == 0).unwrap_or(true);
+
+        let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+        if write_header {
+            writeln!(file, "timestamp,severity,message,market_data")?;
+        }
+
+        for alert in alerts {
+            // Flatten market data into sorted key=value pairs so rows stay comparable
+            let mut market_data: Vec<String> = alert.market_data
+                .iter()
+                .map(|(key, value)| format!("{key}={value}"))
+                .collect();
+            market_data.sort();
+
+            writeln!(
+                file,
+                "{},{:?},{},{}",
+                alert.timestamp.to_rfc3339(),
+                alert.severity,
+                escape_csv_field(&alert.message),
+                escape_csv_field(&market_data.join(";")),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
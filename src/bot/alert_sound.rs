@@ -0,0 +1,64 @@
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+    thread,
+};
+use cli_log::*;
+
+use super::strategy::{AlertSeverity, StrategyAlert};
+
+/// Audible notification for severe alerts: terminal bell and/or a shell command
+#[derive(Debug, Clone)]
+pub struct AlertSound {
+    pub bell: bool,
+    pub command: Option<String>,
+    pub min_severity: AlertSeverity,
+}
+
+impl AlertSound {
+    pub fn new(bell: bool, command: Option<String>) -> Self {
+        Self {
+            bell,
+            command,
+            min_severity: AlertSeverity::High,
+        }
+    }
+
+    pub fn notify(&self, alerts: &[StrategyAlert]) {
+        if !alerts.iter().any(|alert| alert.severity >= self.min_severity) {
+            return;
+        }
+
+        if self.bell {
+            let mut stdout = std::io::stdout();
+            let _ = stdout.write_all(b"\x07");
+            let _ = stdout.flush();
+        }
+
+        if let Some(ref command) = self.command {
+            Self::spawn_detached(command);
+        }
+    }
+
+    fn spawn_detached(command: &str) {
+        let mut cmd = if cfg!(windows) {
+            let mut cmd = Command::new("cmd");
+            cmd.arg("/C").arg(command);
+            cmd
+        } else {
+            let mut cmd = Command::new("sh");
+            cmd.arg("-c").arg(command);
+            cmd
+        };
+
+        // Output would corrupt the TUI, and we never wait on the render thread
+        match cmd.stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null()).spawn() {
+            Ok(mut child) => {
+                thread::spawn(move || {
+                    let _ = child.wait();
+                });
+            }
+            Err(e) => warn!("Failed to run alert command '{command}': {e}"),
+        }
+    }
+}
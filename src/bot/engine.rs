@@ -1,14 +1,26 @@
 use anyhow::Result;
-use chrono::Utc;
+use chrono::{Local, Utc};
 use cli_log::*;
 use std::collections::HashMap;
 
+use crate::config::{DEFAULT_SPREAD_ALERT_TICKS, DEFAULT_DEPTH_IMBALANCE_RATIO, DEFAULT_MAX_STRATEGY_ALERTS, DEPTH_IMBALANCE_LEVELS};
 use crate::data::OrderBookData;
-use super::strategy::{Strategy, StrategyAlert, StrategyStatus, StrategyType, AlertSeverity};
+use super::alert_log::AlertCsvLogger;
+use super::alert_sound::AlertSound;
+use super::quiet_hours::QuietHours;
+use super::strategy_store::StrategyStore;
+use super::strategy::{CategoricalMarket, Strategy, StrategyAlert, StrategyStatus, StrategyType, AlertSeverity};
 
 pub struct BotEngine {
     pub strategies: HashMap<StrategyType, Strategy>,
     pub active_strategy: Option<StrategyType>,
+    pub alert_logger: Option<AlertCsvLogger>, // When set, every emitted alert is appended to CSV
+    pub alert_sound: Option<AlertSound>, // When set, severe alerts ring the bell or run a command
+    pub quiet_hours: Option<QuietHours>, // Local time window where only alerts above its floor reach `alert_sound`
+    pub spread_alert_ticks: f64, // Spread width in ticks above which the tick spread strategy alerts
+    pub depth_imbalance_ratio: f64, // Bid/ask size ratio (or its inverse) above which the depth imbalance strategy alerts
+    pub max_alerts: usize, // Alerts kept in memory per strategy
+    pub strategy_store: Option<StrategyStore>, // When set, enablement and selections are saved on every change
 }
 
 impl Default for BotEngine {
@@ -28,9 +40,43 @@ impl BotEngine {
         Self {
             strategies,
             active_strategy: None,
+            alert_logger: None,
+            alert_sound: None,
+            quiet_hours: None,
+            spread_alert_ticks: DEFAULT_SPREAD_ALERT_TICKS,
+            depth_imbalance_ratio: DEFAULT_DEPTH_IMBALANCE_RATIO,
+            max_alerts: DEFAULT_MAX_STRATEGY_ALERTS,
+            strategy_store: None,
         }
     }
 
+    /// Attaches a store and restores any previously saved setup from it
+    pub fn load_strategy_store(&mut self, store: StrategyStore) {
+        if let Err(e) = store.load(&mut self.strategies) {
+            warn!("Failed to load strategy config from {}: {e}", store.path.display());
+        }
+        self.strategy_store = Some(store);
+    }
+
+    fn save_strategy_store(&self) {
+        let Some(ref store) = self.strategy_store else { return };
+        if let Err(e) = store.save(&self.strategies) {
+            warn!("Failed to save strategy config to {}: {e}", store.path.display());
+        }
+    }
+
+    pub fn toggle_strategy_enabled(&mut self, strategy_type: &StrategyType) -> Option<bool> {
+        let enabled = self.strategies.get_mut(strategy_type).map(|strategy| {
+            strategy.enabled = !strategy.enabled;
+            strategy.enabled
+        });
+        if let Some(enabled) = enabled {
+            info!("Strategy '{}' {}", strategy_type.name(), if enabled { "enabled" } else { "disabled" });
+            self.save_strategy_store();
+        }
+        enabled
+    }
+
     pub fn get_strategy(&self, strategy_type: &StrategyType) -> Option<&Strategy> {
         self.strategies.get(strategy_type)
     }
@@ -70,6 +116,7 @@ impl BotEngine {
                 info!("Added market '{}' (ID: {}) to strategy '{}'", market_name, token_id, strategy_type.name());
             }
         }
+        self.save_strategy_store();
     }
 
     pub fn add_event_to_strategy(&mut self, strategy_type: &StrategyType, event_id: String, event_name: String) {
@@ -81,6 +128,19 @@ impl BotEngine {
                 info!("Added event '{}' (ID: {}) to strategy '{}'", event_name, event_id, strategy_type.name());
             }
         }
+        self.save_strategy_store();
+    }
+
+    /// Derived from the selected events on every change, so these aren't saved with the strategy
+    pub fn set_categorical_markets(&mut self, strategy_type: &StrategyType, markets: Vec<CategoricalMarket>) {
+        if let Some(strategy) = self.strategies.get_mut(strategy_type) {
+            // Books of outcomes that are no longer tracked would only go stale
+            strategy.orderbooks.retain(|token_id, _| {
+                markets.iter().any(|market| market.outcomes.iter().any(|(id, _)| id == token_id))
+                    || strategy.selected_market_ids.contains(token_id)
+            });
+            strategy.categorical_markets = markets;
+        }
     }
 
     pub fn remove_market_from_strategy(&mut self, strategy_type: &StrategyType, token_id: &str) {
@@ -92,6 +152,7 @@ impl BotEngine {
                 }
             }
         }
+        self.save_strategy_store();
     }
 
     pub fn remove_event_from_strategy(&mut self, strategy_type: &StrategyType, event_id: &str) {
@@ -103,6 +164,7 @@ impl BotEngine {
                 }
             }
         }
+        self.save_strategy_store();
     }
 
     pub fn process_orderbook(&mut self, orderbook: &OrderBookData) -> Result<()> {
@@ -111,7 +173,9 @@ impl BotEngine {
         // Collect strategies that need processing
         for (strategy_type, strategy) in &self.strategies {
             if matches!(strategy.status, StrategyStatus::Running)
-                && strategy.selected_market_ids.contains(&orderbook.token_id) {
+                && strategy.enabled
+                && (strategy.selected_market_ids.contains(&orderbook.token_id)
+                    || strategy.tracks_categorical_token(&orderbook.token_id)) {
                 updates.push(strategy_type.clone());
             }
         }
@@ -124,7 +188,7 @@ impl BotEngine {
         for strategy_type in updates {
             if let Some(strategy) = self.strategies.get_mut(&strategy_type) {
                 // For arbitrage detector, update the orderbook cache
-                if strategy_type == StrategyType::ArbitrageDetector {
+                let alerts = if strategy_type == StrategyType::ArbitrageDetector {
                     strategy.update_orderbook(orderbook.clone());
                     let alerts = strategy.check_arbitrage_opportunities();
                     if !alerts.is_empty() {
@@ -133,44 +197,78 @@ impl BotEngine {
                             info!("Alert: {}", alert.message);
                         }
                     }
-                    strategy.alerts.extend(alerts);
+                    alerts
                 } else {
-                    Self::run_strategy_analysis_static(&strategy_type, strategy, orderbook)?;
-                }
+                    Self::run_strategy_analysis_static(&strategy_type, strategy, orderbook, self.spread_alert_ticks, self.depth_imbalance_ratio)?
+                };
+
+                self.emit_alerts(&strategy_type, alerts);
             }
         }
         
         Ok(())
     }
 
+    /// Logs, sounds and records alerts for a strategy, whether its analysis or the app raised them
+    pub fn emit_alerts(&mut self, strategy_type: &StrategyType, alerts: Vec<StrategyAlert>) {
+        if alerts.is_empty() {
+            return;
+        }
+        if let Some(ref logger) = self.alert_logger {
+            if let Err(e) = logger.append(strategy_type, &alerts) {
+                warn!("Failed to write alerts to {}: {e}", logger.path_for(strategy_type).display());
+            }
+        }
+        if let Some(ref sound) = self.alert_sound {
+            match self.quiet_hours {
+                Some(ref quiet_hours) => sound.notify(&quiet_hours.filter(&alerts, Local::now().time())),
+                None => sound.notify(&alerts),
+            }
+        }
+        if let Some(strategy) = self.strategies.get_mut(strategy_type) {
+            strategy.push_alerts(alerts, self.max_alerts);
+        }
+    }
+
     fn run_strategy_analysis_static(
         strategy_type: &StrategyType,
         strategy: &mut Strategy,
         orderbook: &OrderBookData,
-    ) -> Result<()> {
+        spread_alert_ticks: f64,
+        depth_imbalance_ratio: f64,
+    ) -> Result<Vec<StrategyAlert>> {
         strategy.run_count += 1;
         strategy.last_run = Some(Utc::now());
 
         // Basic strategy implementations - these would be expanded
-        match strategy_type {
+        let alerts = match strategy_type {
             StrategyType::PriceAnomaly => {
-                Self::analyze_price_anomaly_static(strategy, orderbook)?;
+                Self::analyze_price_anomaly_static(orderbook)?
             }
             StrategyType::VolumeSpike => {
-                Self::analyze_volume_spike_static(strategy, orderbook)?;
+                Self::analyze_volume_spike_static(orderbook)?
+            }
+            StrategyType::SpreadTicks => {
+                Self::analyze_spread_ticks_static(orderbook, spread_alert_ticks)?
+            }
+            StrategyType::DepthImbalance => {
+                Self::analyze_depth_imbalance_static(orderbook, depth_imbalance_ratio)?
             }
             StrategyType::ArbitrageDetector => {
                 // Requires multiple markets - implemented when we have market data
+                Vec::new()
             }
             StrategyType::CrossMarketCorrelation => {
                 // Requires multiple markets - implemented when we have market data
+                Vec::new()
             }
-        }
+        };
 
-        Ok(())
+        Ok(alerts)
     }
 
-    fn analyze_price_anomaly_static(strategy: &mut Strategy, orderbook: &OrderBookData) -> Result<()> {
+    fn analyze_price_anomaly_static(orderbook: &OrderBookData) -> Result<Vec<StrategyAlert>> {
+        let mut alerts = Vec::new();
         let spread = orderbook.get_spread();
         let midpoint = orderbook.get_midpoint();
 
@@ -187,18 +285,87 @@ impl BotEngine {
                 ),
                 market_data: std::collections::HashMap::new(),
             };
-            strategy.alerts.push(alert);
-            
-            // Keep only last 100 alerts
-            if strategy.alerts.len() > 100 {
-                strategy.alerts.remove(0);
-            }
+            alerts.push(alert);
         }
 
-        Ok(())
+        Ok(alerts)
+    }
+
+    fn analyze_spread_ticks_static(orderbook: &OrderBookData, threshold_ticks: f64) -> Result<Vec<StrategyAlert>> {
+        let mut alerts = Vec::new();
+        if orderbook.tick_size <= 0.0 || orderbook.bids.is_empty() || orderbook.asks.is_empty() {
+            return Ok(alerts);
+        }
+
+        let spread = orderbook.get_spread();
+        // Round away float noise so an exact 10-tick spread reads as 10, not 9.999
+        let spread_ticks = (spread / orderbook.tick_size * 1e6).round() / 1e6;
+
+        if spread_ticks > threshold_ticks {
+            let mut market_data = HashMap::new();
+            market_data.insert("spread".to_string(), serde_json::json!(spread));
+            market_data.insert("spread_ticks".to_string(), serde_json::json!(spread_ticks));
+            market_data.insert("tick_size".to_string(), serde_json::json!(orderbook.tick_size));
+            market_data.insert("midpoint".to_string(), serde_json::json!(orderbook.get_midpoint()));
+
+            alerts.push(StrategyAlert {
+                timestamp: Utc::now(),
+                strategy: "Tick Spread".to_string(),
+                severity: if spread_ticks > threshold_ticks * 2.0 { AlertSeverity::High } else { AlertSeverity::Medium },
+                message: format!(
+                    "Wide spread: {spread_ticks:.0} ticks ({spread:.4} at tick {}) exceeds {threshold_ticks:.0} ticks",
+                    orderbook.tick_size
+                ),
+                market_data,
+            });
+        }
+
+        Ok(alerts)
     }
 
-    fn analyze_volume_spike_static(strategy: &mut Strategy, orderbook: &OrderBookData) -> Result<()> {
+    fn analyze_depth_imbalance_static(orderbook: &OrderBookData, threshold_ratio: f64) -> Result<Vec<StrategyAlert>> {
+        let mut alerts = Vec::new();
+        let bid_size: f64 = orderbook.bids.iter().take(DEPTH_IMBALANCE_LEVELS).map(|b| b.size).sum();
+        let ask_size: f64 = orderbook.asks.iter().take(DEPTH_IMBALANCE_LEVELS).map(|a| a.size).sum();
+        if bid_size <= 0.0 || ask_size <= 0.0 || threshold_ratio <= 1.0 {
+            return Ok(alerts);
+        }
+
+        let bid_notional: f64 = orderbook.bids.iter().take(DEPTH_IMBALANCE_LEVELS).map(|b| b.price * b.size).sum();
+        let ask_notional: f64 = orderbook.asks.iter().take(DEPTH_IMBALANCE_LEVELS).map(|a| a.price * a.size).sum();
+        let ratio = bid_size / ask_size;
+
+        // Lopsided either way: ratio above the threshold is buy pressure, below its inverse is sell pressure
+        let (pressure, strength) = if ratio >= threshold_ratio {
+            ("Buy", ratio)
+        } else if ratio <= 1.0 / threshold_ratio {
+            ("Sell", 1.0 / ratio)
+        } else {
+            return Ok(alerts);
+        };
+
+        let mut market_data = HashMap::new();
+        market_data.insert("bid_ask_ratio".to_string(), serde_json::json!(ratio));
+        market_data.insert("bid_size".to_string(), serde_json::json!(bid_size));
+        market_data.insert("ask_size".to_string(), serde_json::json!(ask_size));
+        market_data.insert("bid_notional".to_string(), serde_json::json!(bid_notional));
+        market_data.insert("ask_notional".to_string(), serde_json::json!(ask_notional));
+
+        alerts.push(StrategyAlert {
+            timestamp: Utc::now(),
+            strategy: "Depth Imbalance".to_string(),
+            severity: if strength >= threshold_ratio * 2.0 { AlertSeverity::High } else { AlertSeverity::Medium },
+            message: format!(
+                "{pressure} pressure: top {DEPTH_IMBALANCE_LEVELS} levels bid/ask ratio {ratio:.2} (bids ${bid_notional:.0} vs asks ${ask_notional:.0})"
+            ),
+            market_data,
+        });
+
+        Ok(alerts)
+    }
+
+    fn analyze_volume_spike_static(orderbook: &OrderBookData) -> Result<Vec<StrategyAlert>> {
+        let mut alerts = Vec::new();
         let total_bid_volume: f64 = orderbook.bids.iter().map(|b| b.size).sum();
         let total_ask_volume: f64 = orderbook.asks.iter().map(|a| a.size).sum();
         let total_volume = total_bid_volume + total_ask_volume;
@@ -213,15 +380,10 @@ impl BotEngine {
                     ),
                 market_data: std::collections::HashMap::new(),
             };
-            strategy.alerts.push(alert);
-            
-            // Keep only last 100 alerts
-            if strategy.alerts.len() > 100 {
-                strategy.alerts.remove(0);
-            }
+            alerts.push(alert);
         }
 
-        Ok(())
+        Ok(alerts)
     }
 
     pub fn get_strategy_status(&self, strategy_type: &StrategyType) -> Option<String> {
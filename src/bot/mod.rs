@@ -1,7 +1,15 @@
 pub mod strategy;
 pub mod engine;
+pub mod alert_log;
+pub mod alert_sound;
+pub mod quiet_hours;
+pub mod strategy_store;
 mod orderbooks;
 
-pub use strategy::{Strategy, StrategyType, StrategyScope, StrategyStatus, StrategyAlert, AlertSeverity};
+pub use strategy::{CategoricalMarket, Strategy, StrategyType, StrategyScope, StrategyStatus, StrategyAlert, AlertSeverity};
 pub use engine::BotEngine;
+pub use alert_log::AlertCsvLogger;
+pub use alert_sound::AlertSound;
+pub use quiet_hours::{QuietFloor, QuietHours};
+pub use strategy_store::StrategyStore;
 use orderbooks::{OrderBook, OrderBooks};
\ No newline at end of file
@@ -0,0 +1,136 @@
+use chrono::NaiveTime;
+
+use super::strategy::{AlertSeverity, StrategyAlert};
+
+/// Local time window in which external alert notifications are held back; alerts are still recorded
+#[derive(Debug, Clone)]
+pub struct QuietHours {
+    pub start: NaiveTime,
+    pub end: NaiveTime, // May be before `start`, for windows running past midnight
+    pub min_severity: QuietFloor,
+}
+
+impl QuietHours {
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+
+    /// The alerts that may notify at `time`
+    pub fn filter(&self, alerts: &[StrategyAlert], time: NaiveTime) -> Vec<StrategyAlert> {
+        if !self.contains(time) {
+            return alerts.to_vec();
+        }
+        alerts.iter()
+            .filter(|alert| self.min_severity.allows(&alert.severity))
+            .cloned()
+            .collect()
+    }
+}
+
+impl std::str::FromStr for QuietHours {
+    type Err = String;
+
+    /// `HH:MM-HH:MM` in local time, e.g. `22:00-07:00`; only critical alerts pass until the floor is changed
+    fn from_str(window: &str) -> Result<Self, Self::Err> {
+        let (start, end) = window.split_once('-')
+            .ok_or_else(|| format!("expected HH:MM-HH:MM, got '{window}'"))?;
+        let parse = |time: &str| NaiveTime::parse_from_str(time.trim(), "%H:%M")
+            .map_err(|e| format!("invalid time '{time}': {e}"));
+        Ok(Self {
+            start: parse(start)?,
+            end: parse(end)?,
+            min_severity: QuietFloor::Severity(AlertSeverity::Critical),
+        })
+    }
+}
+
+/// Which alerts still notify during quiet hours
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuietFloor {
+    Severity(AlertSeverity), // Alerts at or above this severity
+    None, // Every alert is silenced
+}
+
+impl QuietFloor {
+    pub fn allows(&self, severity: &AlertSeverity) -> bool {
+        match self {
+            QuietFloor::Severity(floor) => severity >= floor,
+            QuietFloor::None => false,
+        }
+    }
+}
+
+impl std::str::FromStr for QuietFloor {
+    type Err = String;
+
+    /// A severity name, or `none` to silence every alert
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "none" => Ok(QuietFloor::None),
+            "low" => Ok(QuietFloor::Severity(AlertSeverity::Low)),
+            "medium" => Ok(QuietFloor::Severity(AlertSeverity::Medium)),
+            "high" => Ok(QuietFloor::Severity(AlertSeverity::High)),
+            "critical" => Ok(QuietFloor::Severity(AlertSeverity::Critical)),
+            _ => Err(format!("unknown severity '{value}', expected one of: none, low, medium, high, critical")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::collections::HashMap;
+
+    fn at(time: &str) -> NaiveTime {
+        NaiveTime::parse_from_str(time, "%H:%M").unwrap()
+    }
+
+    fn alert(severity: AlertSeverity) -> StrategyAlert {
+        StrategyAlert {
+            timestamp: Utc::now(),
+            strategy: "Test".to_string(),
+            severity,
+            message: String::new(),
+            market_data: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn start_is_inside_and_end_is_outside() {
+        let quiet: QuietHours = "09:00-17:00".parse().unwrap();
+        assert!(!quiet.contains(at("08:59")));
+        assert!(quiet.contains(at("09:00")));
+        assert!(quiet.contains(at("16:59")));
+        assert!(!quiet.contains(at("17:00")));
+    }
+
+    #[test]
+    fn a_window_past_midnight_wraps() {
+        let quiet: QuietHours = "22:00-07:00".parse().unwrap();
+        assert!(!quiet.contains(at("21:59")));
+        assert!(quiet.contains(at("22:00")));
+        assert!(quiet.contains(at("00:00")));
+        assert!(quiet.contains(at("06:59")));
+        assert!(!quiet.contains(at("07:00")));
+        assert!(!quiet.contains(at("12:00")));
+    }
+
+    #[test]
+    fn filter_holds_back_alerts_below_the_floor_only_inside_the_window() {
+        let mut quiet: QuietHours = "22:00-07:00".parse().unwrap();
+        let alerts = [alert(AlertSeverity::Medium), alert(AlertSeverity::Critical)];
+
+        let passed = quiet.filter(&alerts, at("23:30"));
+        assert_eq!(passed.len(), 1);
+        assert_eq!(passed[0].severity, AlertSeverity::Critical);
+        assert_eq!(quiet.filter(&alerts, at("07:00")).len(), 2);
+
+        quiet.min_severity = QuietFloor::None;
+        assert!(quiet.filter(&alerts, at("22:00")).is_empty());
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use crate::data::OrderBookData;
@@ -8,6 +8,8 @@ pub enum StrategyType {
     ArbitrageDetector,
     PriceAnomaly,
     VolumeSpike,
+    SpreadTicks,
+    DepthImbalance,
     CrossMarketCorrelation,
 }
 
@@ -24,15 +26,31 @@ impl StrategyType {
             Self::ArbitrageDetector => "Arbitrage Detector",
             Self::PriceAnomaly => "Price Anomaly Scanner",
             Self::VolumeSpike => "Volume Spike Alert",
+            Self::SpreadTicks => "Tick Spread Alert",
+            Self::DepthImbalance => "Depth Imbalance Alert",
             Self::CrossMarketCorrelation => "Cross-Market Correlation",
         }
     }
 
+    /// File-name friendly identifier
+    pub fn slug(&self) -> &'static str {
+        match self {
+            Self::ArbitrageDetector => "arbitrage_detector",
+            Self::PriceAnomaly => "price_anomaly",
+            Self::VolumeSpike => "volume_spike",
+            Self::SpreadTicks => "spread_ticks",
+            Self::DepthImbalance => "depth_imbalance",
+            Self::CrossMarketCorrelation => "cross_market_correlation",
+        }
+    }
+
     pub fn description(&self) -> &'static str {
         match self {
             Self::ArbitrageDetector => "Finds price discrepancies between markets in the same event",
             Self::PriceAnomaly => "Detects unusual price movements in individual markets",
             Self::VolumeSpike => "Alerts on sudden volume increases in individual markets",
+            Self::SpreadTicks => "Alerts when the spread is wider than a number of ticks",
+            Self::DepthImbalance => "Alerts when top-of-book bid and ask size are strongly lopsided",
             Self::CrossMarketCorrelation => "Analyzes correlation between multiple markets",
         }
     }
@@ -42,6 +60,8 @@ impl StrategyType {
             Self::ArbitrageDetector => StrategyScope::Event,
             Self::PriceAnomaly => StrategyScope::SingleMarket,
             Self::VolumeSpike => StrategyScope::SingleMarket,
+            Self::SpreadTicks => StrategyScope::SingleMarket,
+            Self::DepthImbalance => StrategyScope::SingleMarket,
             Self::CrossMarketCorrelation => StrategyScope::MultiMarket,
         }
     }
@@ -55,11 +75,29 @@ impl StrategyType {
             Self::ArbitrageDetector,
             Self::PriceAnomaly,
             Self::VolumeSpike,
+            Self::SpreadTicks,
+            Self::DepthImbalance,
             Self::CrossMarketCorrelation,
         ]
     }
 }
 
+impl std::str::FromStr for StrategyType {
+    type Err = String;
+
+    /// Accepts the slug (`spread_ticks`, or `spread-ticks`) or the display name, ignoring case
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let wanted = name.trim().to_lowercase().replace('-', "_");
+        Self::all_strategies()
+            .into_iter()
+            .find(|strategy_type| strategy_type.slug() == wanted || strategy_type.name().to_lowercase() == name.trim().to_lowercase())
+            .ok_or_else(|| {
+                let valid: Vec<&str> = Self::all_strategies().iter().map(|strategy_type| strategy_type.slug()).collect();
+                format!("unknown strategy '{name}', expected one of: {}", valid.join(", "))
+            })
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum StrategyStatus {
     Stopped,
@@ -76,7 +114,7 @@ pub struct StrategyAlert {
     pub market_data: HashMap<String, serde_json::Value>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum AlertSeverity {
     Low,
     Medium,
@@ -95,20 +133,42 @@ impl AlertSeverity {
     }
 }
 
+/// Sum of one price per market of an event and its shortfall below 1.0, when it falls short.
+/// A market without a price leaves the sum meaningless, so it gives `None`.
+pub fn sum_below_one(prices: &[Option<f64>]) -> Option<(f64, f64)> {
+    let total: f64 = prices.iter().copied().sum::<Option<f64>>()?;
+    (total < 1.0).then_some((total, 1.0 - total))
+}
+
+/// Like `sum_below_one`, but for a sum above 1.0 and its excess
+pub fn sum_above_one(prices: &[Option<f64>]) -> Option<(f64, f64)> {
+    let total: f64 = prices.iter().copied().sum::<Option<f64>>()?;
+    (total > 1.0).then_some((total, total - 1.0))
+}
+
+/// A market whose outcome tokens are mutually exclusive, so exactly one of them pays out 1.0
+#[derive(Debug, Clone)]
+pub struct CategoricalMarket {
+    pub name: String,
+    pub outcomes: Vec<(String, String)>, // (token_id, outcome label)
+}
+
 #[derive(Debug, Clone)]
 pub struct Strategy {
     pub strategy_type: StrategyType,
     pub status: StrategyStatus,
+    pub enabled: bool, // Disabled strategies keep their selection but are skipped even while running
     pub selected_market_ids: Vec<String>, 
     pub selected_market_names: Vec<String>,
     pub selected_event_ids: Vec<String>,
     pub selected_event_names: Vec<String>,
-    pub alerts: Vec<StrategyAlert>,
+    pub alerts: VecDeque<StrategyAlert>, // Oldest first, capped by the engine's `max_alerts`
     pub last_run: Option<DateTime<Utc>>,
     pub run_count: u64,
     // For tracking multiple orderbooks (used by arbitrage detector)
     pub orderbooks: HashMap<String, OrderBookData>,
     pub last_arbitrage_check: Option<DateTime<Utc>>,
+    pub categorical_markets: Vec<CategoricalMarket>, // Markets of the selected events, checked across their own outcome books
 }
 
 impl Strategy {
@@ -116,15 +176,17 @@ impl Strategy {
         Self {
             strategy_type,
             status: StrategyStatus::Stopped,
+            enabled: true,
             selected_market_ids: Vec::new(),
             selected_market_names: Vec::new(),
             selected_event_ids: Vec::new(),
             selected_event_names: Vec::new(),
-            alerts: Vec::new(),
+            alerts: VecDeque::new(),
             last_run: None,
             run_count: 0,
             orderbooks: HashMap::new(),
             last_arbitrage_check: None,
+            categorical_markets: Vec::new(),
         }
     }
 
@@ -147,6 +209,13 @@ impl Strategy {
         }
     }
 
+    /// Keeps only the newest `max_alerts`, dropping from the front
+    pub fn push_alerts(&mut self, alerts: Vec<StrategyAlert>, max_alerts: usize) {
+        self.alerts.extend(alerts);
+        let excess = self.alerts.len().saturating_sub(max_alerts);
+        self.alerts.drain(..excess);
+    }
+
     pub fn update_orderbook(&mut self, orderbook: OrderBookData) {
         self.orderbooks.insert(orderbook.token_id.clone(), orderbook);
     }
@@ -167,64 +236,154 @@ impl Strategy {
             }
 
             // Check if the sum of all "Yes" prices is < 1.0
-            let yes_prices: Vec<f64> = event_markets
+            let yes_prices: Vec<Option<f64>> = event_markets
                 .iter()
-                .filter_map(|token_id| {
+                .map(|token_id| {
                     self.orderbooks.get(token_id).and_then(|ob| {
                         ob.bids.first().map(|bid| bid.price)
                     })
                 })
                 .collect();
 
-            if yes_prices.len() == event_markets.len() {
-                let total_yes_price: f64 = yes_prices.iter().sum();
-                if total_yes_price < 1.0 {
-                    let arbitrage_amount = 1.0 - total_yes_price;
-                    let alert = StrategyAlert {
-                        timestamp: now,
-                        strategy: "Arbitrage Detector".to_string(),
-                        severity: if arbitrage_amount > 0.1 { AlertSeverity::High } else { AlertSeverity::Medium },
-                        message: format!(
-                            "Arbitrage opportunity detected! Sum of YES prices: {total_yes_price:.4} (opportunity: ${arbitrage_amount:.4})"
-                        ),
-                        market_data: HashMap::new(),
-                    };
-                    alerts.push(alert);
-                }
+            if let Some((total_yes_price, arbitrage_amount)) = sum_below_one(&yes_prices) {
+                let alert = StrategyAlert {
+                    timestamp: now,
+                    strategy: "Arbitrage Detector".to_string(),
+                    severity: if arbitrage_amount > 0.1 { AlertSeverity::High } else { AlertSeverity::Medium },
+                    message: format!(
+                        "Arbitrage opportunity detected! Sum of YES prices: {total_yes_price:.4} (opportunity: ${arbitrage_amount:.4})"
+                    ),
+                    market_data: HashMap::new(),
+                };
+                alerts.push(alert);
             }
 
             // Check if the sum of all "No" prices is < 1.0
-            let no_prices: Vec<f64> = event_markets
+            let no_prices: Vec<Option<f64>> = event_markets
                 .iter()
-                .filter_map(|token_id| {
+                .map(|token_id| {
                     self.orderbooks.get(token_id).and_then(|ob| {
                         ob.asks.first().map(|ask| ask.price)
                     })
                 })
                 .collect();
 
-            if no_prices.len() == event_markets.len() {
-                let total_no_price: f64 = no_prices.iter().sum();
-                if total_no_price < 1.0 {
-                    let arbitrage_amount = 1.0 - total_no_price;
-                    let alert = StrategyAlert {
-                        timestamp: now,
-                        strategy: "Arbitrage Detector".to_string(),
-                        severity: if arbitrage_amount > 0.1 { AlertSeverity::High } else { AlertSeverity::Medium },
-                        message: format!(
-                            "Arbitrage opportunity detected! Sum of NO prices: {total_no_price:.4} (opportunity: ${arbitrage_amount:.4})"
-                        ),
-                        market_data: HashMap::new(),
-                    };
-                    alerts.push(alert);
-                }
+            if let Some((total_no_price, arbitrage_amount)) = sum_below_one(&no_prices) {
+                let alert = StrategyAlert {
+                    timestamp: now,
+                    strategy: "Arbitrage Detector".to_string(),
+                    severity: if arbitrage_amount > 0.1 { AlertSeverity::High } else { AlertSeverity::Medium },
+                    message: format!(
+                        "Arbitrage opportunity detected! Sum of NO prices: {total_no_price:.4} (opportunity: ${arbitrage_amount:.4})"
+                    ),
+                    market_data: HashMap::new(),
+                };
+                alerts.push(alert);
             }
         }
 
+        alerts.extend(self.check_categorical_arbitrage(now));
+
         self.last_arbitrage_check = Some(now);
         alerts
     }
 
+    /// Whether any tracked categorical market has an outcome with this token
+    pub fn tracks_categorical_token(&self, token_id: &str) -> bool {
+        self.categorical_markets
+            .iter()
+            .any(|market| market.outcomes.iter().any(|(id, _)| id == token_id))
+    }
+
+    /// Within one market, buying every outcome below 1.0 or selling every outcome above 1.0 locks in the difference
+    fn check_categorical_arbitrage(&self, now: DateTime<Utc>) -> Vec<StrategyAlert> {
+        let mut alerts = Vec::new();
+
+        for market in &self.categorical_markets {
+            if market.outcomes.len() < 2 {
+                continue;
+            }
+
+            // Every outcome needs a two-sided book before the sums mean anything
+            let quotes: Option<Vec<(&str, &str, f64, f64)>> = market.outcomes
+                .iter()
+                .map(|(token_id, outcome)| {
+                    let orderbook = self.orderbooks.get(token_id)?;
+                    let best_bid = orderbook.bids.first()?.price;
+                    let best_ask = orderbook.asks.first()?.price;
+                    Some((token_id.as_str(), outcome.as_str(), best_bid, best_ask))
+                })
+                .collect();
+            let Some(quotes) = quotes else { continue };
+
+            let total_mid: f64 = quotes.iter().map(|(_, _, bid, ask)| (bid + ask) / 2.0).sum();
+            let total_ask: f64 = quotes.iter().map(|(_, _, _, ask)| ask).sum();
+            let total_bid: f64 = quotes.iter().map(|(_, _, bid, _)| bid).sum();
+
+            // An outcome is mispriced when its leg sits on the wrong side of its share of the normalized midpoints
+            let fair_price = |bid: f64, ask: f64| if total_mid > 0.0 { (bid + ask) / 2.0 / total_mid } else { 0.0 };
+
+            if total_ask < 1.0 {
+                let cheap: Vec<String> = quotes
+                    .iter()
+                    .filter(|(_, _, bid, ask)| *ask < fair_price(*bid, *ask))
+                    .map(|(_, outcome, _, ask)| format!("{outcome} @ {ask:.4}"))
+                    .collect();
+                alerts.push(Self::categorical_alert(now, market, &quotes, "asks", total_ask, &cheap));
+            }
+
+            if total_bid > 1.0 {
+                let rich: Vec<String> = quotes
+                    .iter()
+                    .filter(|(_, _, bid, ask)| *bid > fair_price(*bid, *ask))
+                    .map(|(_, outcome, bid, _)| format!("{outcome} @ {bid:.4}"))
+                    .collect();
+                alerts.push(Self::categorical_alert(now, market, &quotes, "bids", total_bid, &rich));
+            }
+        }
+
+        alerts
+    }
+
+    fn categorical_alert(
+        now: DateTime<Utc>,
+        market: &CategoricalMarket,
+        quotes: &[(&str, &str, f64, f64)],
+        side: &str,
+        total: f64,
+        mispriced: &[String],
+    ) -> StrategyAlert {
+        let (action, edge) = if side == "asks" { ("buy", 1.0 - total) } else { ("sell", total - 1.0) };
+        let mispriced_summary = if mispriced.is_empty() { "spread evenly".to_string() } else { mispriced.join(", ") };
+
+        let mut market_data = HashMap::new();
+        market_data.insert("market".to_string(), serde_json::json!(market.name));
+        market_data.insert(format!("sum_best_{side}"), serde_json::json!(total));
+        market_data.insert("edge".to_string(), serde_json::json!(edge));
+        market_data.insert("mispriced_outcomes".to_string(), serde_json::json!(mispriced));
+        market_data.insert("outcomes".to_string(), serde_json::json!(quotes
+            .iter()
+            .map(|(token_id, outcome, bid, ask)| serde_json::json!({
+                "outcome": outcome,
+                "token_id": token_id,
+                "best_bid": bid,
+                "best_ask": ask,
+            }))
+            .collect::<Vec<_>>()));
+
+        StrategyAlert {
+            timestamp: now,
+            strategy: "Arbitrage Detector".to_string(),
+            severity: if edge > 0.1 { AlertSeverity::High } else { AlertSeverity::Medium },
+            message: format!(
+                "Categorical arbitrage in '{}': sum of best {side} {total:.4} across {} outcomes ({action} all, edge ${edge:.4}). Mispriced: {mispriced_summary}",
+                market.name,
+                quotes.len(),
+            ),
+            market_data,
+        }
+    }
+
     fn get_event_markets(&self, event_id: &str) -> Vec<String> {
         // For now, we'll use a simple approach - return all selected market IDs
         // In a more sophisticated implementation, we'd need to track which markets
@@ -236,3 +395,36 @@ impl Strategy {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alert(message: &str) -> StrategyAlert {
+        StrategyAlert {
+            timestamp: Utc::now(),
+            strategy: "Spread Ticks".to_string(),
+            severity: AlertSeverity::Medium,
+            message: message.to_string(),
+            market_data: HashMap::new(),
+        }
+    }
+
+    fn messages(strategy: &Strategy) -> Vec<&str> {
+        strategy.alerts.iter().map(|alert| alert.message.as_str()).collect()
+    }
+
+    #[test]
+    fn alerts_over_the_cap_drop_the_oldest() {
+        let mut strategy = Strategy::new(StrategyType::SpreadTicks);
+        strategy.push_alerts(vec![alert("1"), alert("2")], 3);
+        assert_eq!(messages(&strategy), vec!["1", "2"]);
+
+        strategy.push_alerts(vec![alert("3"), alert("4")], 3);
+        assert_eq!(messages(&strategy), vec!["2", "3", "4"]);
+
+        // A single batch larger than the cap keeps only its newest alerts
+        strategy.push_alerts((5..=9).map(|n| alert(&n.to_string())).collect(), 3);
+        assert_eq!(messages(&strategy), vec!["7", "8", "9"]);
+    }
+}
@@ -0,0 +1,81 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fs, path::PathBuf};
+
+use super::strategy::{Strategy, StrategyType};
+
+/// Persisted per-strategy setup, keyed by `StrategyType::slug`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SavedStrategy {
+    strategy: String,
+    enabled: bool,
+    selected_market_ids: Vec<String>,
+    selected_market_names: Vec<String>,
+    selected_event_ids: Vec<String>,
+    selected_event_names: Vec<String>,
+}
+
+/// Saves and restores strategy enablement and selections as JSON
+#[derive(Debug, Clone)]
+pub struct StrategyStore {
+    pub path: PathBuf,
+}
+
+impl StrategyStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Applies the saved setup to `strategies`; a missing file leaves them untouched.
+    /// Entries naming an unknown strategy are skipped and reported in the error.
+    pub fn load(&self, strategies: &mut HashMap<StrategyType, Strategy>) -> Result<()> {
+        if !self.path.exists() {
+            return Ok(());
+        }
+
+        let saved: Vec<SavedStrategy> = serde_json::from_str(&fs::read_to_string(&self.path)?)
+            .map_err(|e| anyhow!("Invalid strategy config {}: {e}", self.path.display()))?;
+        let mut unknown = Vec::new();
+        for entry in saved {
+            let strategy = entry.strategy.parse::<StrategyType>().ok()
+                .and_then(|strategy_type| strategies.get_mut(&strategy_type));
+            let Some(strategy) = strategy else {
+                unknown.push(entry.strategy);
+                continue;
+            };
+            strategy.enabled = entry.enabled;
+            strategy.selected_market_ids = entry.selected_market_ids;
+            strategy.selected_market_names = entry.selected_market_names;
+            strategy.selected_event_ids = entry.selected_event_ids;
+            strategy.selected_event_names = entry.selected_event_names;
+        }
+
+        // The known strategies are restored either way
+        if !unknown.is_empty() {
+            return Err(anyhow!("Invalid strategy config {}: unknown strategy '{}'", self.path.display(), unknown.join("', '")));
+        }
+        Ok(())
+    }
+
+    pub fn save(&self, strategies: &HashMap<StrategyType, Strategy>) -> Result<()> {
+        // Stable order so the file diffs cleanly between saves
+        let saved: Vec<SavedStrategy> = StrategyType::all_strategies()
+            .iter()
+            .filter_map(|strategy_type| strategies.get(strategy_type))
+            .map(|strategy| SavedStrategy {
+                strategy: strategy.strategy_type.slug().to_string(),
+                enabled: strategy.enabled,
+                selected_market_ids: strategy.selected_market_ids.clone(),
+                selected_market_names: strategy.selected_market_names.clone(),
+                selected_event_ids: strategy.selected_event_ids.clone(),
+                selected_event_names: strategy.selected_event_names.clone(),
+            })
+            .collect();
+
+        if let Some(parent) = self.path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.path, serde_json::to_string_pretty(&saved)?)?;
+        Ok(())
+    }
+}
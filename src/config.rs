@@ -3,29 +3,119 @@
 /// API endpoints
 pub const POLYMARKET_HOST: &str = "https://clob.polymarket.com";
 pub const POLYMARKET_GAMMA_HOST: &str = "https://gamma-api.polymarket.com";
+pub const POLYMARKET_WS_HOST: &str = "wss://ws-subscriptions-clob.polymarket.com"; // Channel path `/ws/{market,user}` is appended
 
 /// Network settings
 pub const POLYGON_CHAIN_ID: u64 = 137;
+pub const PROXY_ENV_VARS: [&str; 4] = ["HTTPS_PROXY", "https_proxy", "ALL_PROXY", "all_proxy"]; // Checked in order when --proxy isn't given
 
 /// Application limits
 pub const MAX_EVENTS: usize = 5000; // Limit to prevent excessive memory usage
+pub const EVENTS_PAGE_SIZE: u32 = 500; // Events requested per gamma API page
+pub const GAMMA_MAX_PAGE_SIZE: u32 = 500; // Largest page the gamma events endpoint accepts
 pub const MAX_PRICE_HISTORY_POINTS: usize = 300; // Store last 300 points
+pub const ORDERBOOK_PRICE_HISTORY_POINTS: usize = 500;
+pub const DEFAULT_PRICE_HISTORY_WINDOW_MINS: u64 = 30; // Realtime chart keeps the last 30 minutes, see `--history-window`
 
 /// Update intervals (in milliseconds)
 pub const TICK_RATE_MS: u64 = 1;
 pub const DATA_UPDATE_RATE_MS: u64 = 50;
 pub const UI_UPDATE_RATE_MS: u64 = 1000;
+pub const DEFAULT_IDLE_TIMEOUT_SECS: u64 = 300; // No key input for this long switches to low-power refresh
 pub const PRICE_HISTORY_UPDATE_INTERVAL_MS: u64 = 60_000; // 1 minute
+pub const OUTCOME_PAIR_UPDATE_INTERVAL_MS: u64 = 2_000; // Sibling outcome book refresh
+pub const DEFAULT_HISTORY_REFRESH_SECS: u64 = 60; // API price history refetch for the loaded market, independent of book loads
+pub const ARBITRAGE_SCAN_INTERVAL_SECS: u64 = 30; // Arbitrage dashboard rescan, one book per market per scan
+
+/// API retry settings
+pub const API_RETRY_ATTEMPTS: u32 = 3; // Total tries including the first
+pub const API_RETRY_BASE_DELAY_MS: u64 = 200; // Doubled after every failed try
+
+/// REST rate limiting
+pub const DEFAULT_REQUESTS_PER_SECOND: f64 = 10.0;
+pub const RATE_LIMIT_BACKOFF_BASE_MS: u64 = 1_000; // First pause after a 429, doubled while they continue
+pub const RATE_LIMIT_BACKOFF_MAX_MS: u64 = 30_000;
 
 /// WebSocket settings
+pub const BINANCE_WS_ENDPOINT: &str = "wss://stream.binance.com:9443/ws"; // Default crypto price source
+pub const COINBASE_WS_URL: &str = "wss://ws-feed.exchange.coinbase.com";
 pub const WS_MAX_ATTEMPTS: u32 = 20;
 pub const WS_RECONNECT_DELAY_SECS: u64 = 10;
+pub const WS_PING_INTERVAL_SECS: u64 = 10; // Keepalive pings; their pongs tell a quiet market from a stalled socket
+pub const DEFAULT_WS_STALE_TIMEOUT_SECS: u64 = 30; // A socket silent this long, pongs included, falls back to REST polling
+pub const HEALTH_WS_DOWN_TIMEOUT_SECS: u64 = 120; // /healthz answers 503 once an open market's WebSocket has been down this long
+pub const HEALTH_HEARTBEAT_TIMEOUT_SECS: u64 = 10; // ...or once the main loop hasn't run an update this long
+pub const USER_FILLS_MAX: usize = 20; // Recent own fills kept for the side panel
+pub const RECENT_TRADES_MAX: usize = 50; // Market trades kept for the ticker tape
+pub const BOOK_SNAPSHOT_STALE_SECS: u64 = 60; // Resync via REST if no full book arrives this long
+pub const BOOK_RESYNC_COOLDOWN_SECS: u64 = 5; // Minimum gap between forced resyncs
+pub const DEFAULT_BOOK_JUMP_TICKS: f64 = 3.0; // A `book` snapshot moving the top of book further than this suggests missed deltas
+pub const BOOK_JUMP_FLAG_SECS: u64 = 5; // How long the header keeps the jump flag
+pub const MIN_TICK_SIZE: f64 = 0.0001; // Smallest tick size accepted from the API; also the fallback when it can't be fetched
+pub const DEFAULT_PRICE_EPSILON: f64 = 0.00005; // Price level matching tolerance when the tick size is unknown
+pub const SIZE_EPSILON: f64 = 0.000001; // Sizes closer than this are equal; summing bucketed levels adds float noise far below it
 
 /// UI settings
-pub const HIGHLIGHT_DURATION_MS: u128 = 1000; // Highlight changes for 1 second
+pub const DEFAULT_HIGHLIGHT_DURATION_MS: u64 = 1000; // How long a level flashes green/red after its size changes
+pub const DEFAULT_LARGE_TRADE_SIZE: f64 = 1000.0; // Trades of at least this many shares flash the header
+pub const LARGE_TRADE_FLASH_MS: u128 = 3000;
+pub const IMBALANCE_HISTORY_POINTS: usize = 40; // Samples in the header's imbalance sparkline
+pub const IMBALANCE_SAMPLE_MS: u128 = 1000; // At most one imbalance sample per this long
+pub const DEFAULT_TRADE_ACTIVITY_WINDOW_SECS: u64 = 300; // Rolling window for trades/min and $/min in the header
 pub const CHART_NUM_DATES: u32 = 5;
+pub const SELECTOR_PIN_CONTEXT_ROWS: usize = 3; // Rows shown above the loaded market when the selector reopens on it
+pub const PRICE_HISTORY_FIDELITIES: [u32; 5] = [1, 5, 15, 60, 1440]; // Minutes per point offered in the Price History tab
+pub const DEFAULT_PRICE_HISTORY_FIDELITY: u32 = 60;
+pub const AGGREGATION_BUCKET_TICKS: [u32; 4] = [1, 5, 10, 50]; // Bin sizes offered by the aggregation toggle, 1 = off
+pub const DEPTH_CHART_ZOOM_TICKS: [u32; 6] = [10, 20, 50, 100, 200, 500]; // Depth chart widths (ticks across the spread) offered by zoom keys
+pub const DEFAULT_DEPTH_CHART_TICKS: u32 = 20;
+pub const DEPTH_CHART_PAN_TICKS: i64 = 1; // Ticks moved per arrow key press when the depth chart is locked
+pub const MIN_LIQUIDITY_THRESHOLDS: [u32; 5] = [100, 1_000, 10_000, 100_000, 1_000_000]; // USDC steps for the tradable-only market filter
+pub const DEFAULT_MIN_LIQUIDITY: u32 = 1_000;
+pub const RESOLVING_SOON_HOURS: i64 = 24; // Window for the resolving-soon market filter
+pub const DEFAULT_RESOLUTION_WARNING_MINS: u64 = 60; // Viewed and strategy markets ending this soon are warned about once
+pub const RESOLUTION_CHECK_INTERVAL_SECS: u64 = 30;
+pub const LIQUID_MAX_SPREAD: f64 = 0.10; // Probed books wider than this count as untradable
+pub const LIQUIDITY_PROBE_WINDOW: usize = 30; // Markets from the cursor down considered for book probing
+pub const LIQUIDITY_PROBE_BATCH: usize = 3; // Books fetched per probe so navigation stays responsive
+pub const LIQUIDITY_PROBE_INTERVAL_MS: u64 = 500;
+pub const OUTCOME_PRICE_REFRESH_SECS: u64 = 5; // Token selector prices are refetched this often while it is open
+pub const DEFAULT_DEPTH_BPS: f64 = 200.0; // Header depth window around the midpoint; 100bps of a 0.50 mid is half a cent
+pub const DEPTH_IMBALANCE_LEVELS: usize = 5; // Price levels per side summed by the depth imbalance strategy
+pub const LEVEL_FRESH_SECS: u64 = 5; // Levels younger than this are bold in the age view
+pub const LEVEL_RESTING_SECS: u64 = 60; // Levels older than this are dimmed in the age view
+pub const TWAP_WINDOW_SECS: u64 = 5 * 60; // Trailing window of the price chart TWAP overlay
+pub const CANDLE_INTERVALS_SECS: [u64; 5] = [1, 5, 15, 60, 300]; // Candle widths offered for the realtime price chart
+pub const CHART_EXPORT_DIR: &str = "charts"; // Where exported chart images are written
+pub const SCREEN_DUMP_DIR: &str = "screens"; // Where plain-text screen captures are written
+pub const SESSION_EXPORT_DIR: &str = "sessions"; // Bug report bundles, one folder per export
+pub const SESSION_EXPORT_LOG_LINES: usize = 2000; // Log lines at the end of the log file copied into a bundle
+pub const UNKNOWN_WS_PAYLOADS_KEPT: usize = 20; // Unparseable market channel payloads kept for bug reports
+pub const BOOK_EXPORT_DIR: &str = "exports"; // Markdown book tables land here when the clipboard is unavailable
+pub const CHART_EXPORT_WIDTH: u32 = 1200;
+pub const CHART_EXPORT_HEIGHT: u32 = 900;
+pub const UPDATE_INTERVAL_STEPS: [f64; 6] = [0.1, 0.25, 0.5, 1.0, 2.0, 5.0]; // Seconds offered by the settings overlay
+pub const ORDERBOOK_DEPTH_STEPS: [usize; 5] = [5, 10, 20, 30, 50]; // Orders per side offered by the settings overlay
+pub const RSI_PERIOD: usize = 14;
+pub const RSI_OVERBOUGHT: f64 = 70.0;
+pub const RSI_OVERSOLD: f64 = 30.0;
 
 /// Default CLI values
 pub const DEFAULT_UPDATE_INTERVAL: f64 = 0.1;
 pub const DEFAULT_ORDERBOOK_DEPTH: usize = 30;
+pub const MAX_ORDERBOOK_DEPTH: usize = 100; // Largest depth a settings file may ask for
 pub const DEFAULT_PRIVATE_KEY_ENV: &str = "PK";
+pub const DEFAULT_MARKET_ROW_FORMAT: &str = "{question}"; // Market list row template, see `--market-row`
+pub const DEFAULT_START_TAB: &str = "markets"; // Market selector tab shown at startup
+pub const DEFAULT_START_VIEW: &str = "orderbook"; // Tab shown once a market is loaded
+pub const PREWARM_DEBOUNCE_MS: u64 = 400; // The selector highlight must rest this long before its market is pre-warmed
+pub const MID_LOG_FLUSH_SECS: u64 = 1; // Midpoint CSV rows are buffered at most this long
+pub const DEFAULT_ALERT_LOG_DIR: &str = "alerts";
+pub const DEFAULT_STRATEGY_CONFIG_PATH: &str = "strategies.json";
+pub const DEFAULT_KEYMAP_PATH: &str = "keymap.json";
+pub const DEFAULT_SETTINGS_PATH: &str = "settings.json";
+pub const DEFAULT_LOG_LEVEL: &str = "info";
+pub const DEFAULT_LOG_FILE: &str = "polymarket.log";
+pub const DEFAULT_SPREAD_ALERT_TICKS: f64 = 10.0;
+pub const DEFAULT_DEPTH_IMBALANCE_RATIO: f64 = 3.0;
+pub const DEFAULT_MAX_STRATEGY_ALERTS: usize = 100; // Alerts kept in memory per strategy, oldest dropped first
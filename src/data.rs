@@ -1,6 +1,8 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
+use cli_log::*;
+use std::collections::VecDeque;
 use std::time::Instant;
-use crate::config::{HIGHLIGHT_DURATION_MS, MAX_PRICE_HISTORY_POINTS};
+use crate::config::{BOOK_JUMP_FLAG_SECS, DEFAULT_HIGHLIGHT_DURATION_MS, DEPTH_IMBALANCE_LEVELS, IMBALANCE_HISTORY_POINTS, IMBALANCE_SAMPLE_MS, LARGE_TRADE_FLASH_MS, MAX_PRICE_HISTORY_POINTS, MIN_TICK_SIZE, ORDERBOOK_PRICE_HISTORY_POINTS, RECENT_TRADES_MAX, DEFAULT_PRICE_EPSILON};
 
 #[derive(Debug, Clone)]
 pub struct SimpleOrder {
@@ -9,6 +11,8 @@ pub struct SimpleOrder {
     pub previous_size: f64,
     pub change_direction: OrderChangeDirection,
     pub change_timestamp: Option<Instant>,
+    pub highlight_duration: std::time::Duration, // How long the change highlight lasts from change_timestamp
+    pub first_seen: Instant, // When the price level appeared, kept across size updates
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -26,41 +30,98 @@ impl SimpleOrder {
             previous_size: size,
             change_direction: OrderChangeDirection::Increase,
             change_timestamp: Some(Instant::now()),
+            highlight_duration: std::time::Duration::from_millis(DEFAULT_HIGHLIGHT_DURATION_MS),
+            first_seen: Instant::now(),
         }
     }
     
     pub fn update_size(&mut self, new_size: f64) {
-        self.previous_size = self.size;
-        
-        if new_size > self.size {
+        let previous_size = self.size;
+        self.size = new_size;
+        self.mark_size_change(previous_size, Instant::now());
+    }
+
+    /// Growth highlights green and shrinkage red from `now`; an unchanged size keeps the running highlight
+    fn mark_size_change(&mut self, previous_size: f64, now: Instant) {
+        self.previous_size = previous_size;
+        if self.size > previous_size {
             self.change_direction = OrderChangeDirection::Increase;
-            self.change_timestamp = Some(Instant::now());
-        } else if new_size < self.size {
+            self.change_timestamp = Some(now);
+        } else if self.size < previous_size {
             self.change_direction = OrderChangeDirection::Decrease;
-            self.change_timestamp = Some(Instant::now());
+            self.change_timestamp = Some(now);
         }
-        
-        self.size = new_size;
     }
     
     pub fn should_highlight(&self) -> bool {
-        if let Some(timestamp) = self.change_timestamp {
-            timestamp.elapsed().as_millis() < HIGHLIGHT_DURATION_MS
-        } else {
-            false
-        }
+        self.is_highlighted_at(Instant::now())
+    }
+
+    pub fn is_highlighted_at(&self, now: Instant) -> bool {
+        self.change_timestamp
+            .is_some_and(|timestamp| now.saturating_duration_since(timestamp) < self.highlight_duration)
     }
     
+    /// How long the price level has been resting in the book
+    pub fn age(&self) -> std::time::Duration {
+        self.first_seen.elapsed()
+    }
+
     pub fn clear_highlight_if_expired(&mut self) {
-        if let Some(timestamp) = self.change_timestamp {
-            if timestamp.elapsed().as_millis() >= HIGHLIGHT_DURATION_MS {
-                self.change_direction = OrderChangeDirection::None;
-                self.change_timestamp = None;
-            }
+        if self.change_timestamp.is_some() && !self.is_highlighted_at(Instant::now()) {
+            self.change_direction = OrderChangeDirection::None;
+            self.change_timestamp = None;
         }
     }
 }
 
+/// Folds levels at the same price (within `epsilon`) into one holding their summed size.
+/// `levels` must be sorted by price; the first level at a price keeps its place.
+pub fn merge_duplicate_levels(levels: &mut Vec<SimpleOrder>, epsilon: f64) {
+    levels.dedup_by(|level, kept| {
+        let duplicate = (level.price - kept.price).abs() < epsilon;
+        if duplicate {
+            kept.size += level.size;
+        }
+        duplicate
+    });
+}
+
+/// Compares a new set of levels against `previous` (one side of the book) and sets each level's highlight and age.
+/// Levels at a known price carry their age and highlight, re-highlighting if the size moved;
+/// new prices highlight as growth. Removed levels simply drop out. Highlights started here last `highlight`.
+/// Against an empty `previous`, as when a market opens, nothing changed yet and nothing is highlighted.
+pub fn diff_levels(levels: &mut [SimpleOrder], previous: &[SimpleOrder], epsilon: f64, now: Instant, highlight: std::time::Duration) {
+    if previous.is_empty() {
+        for level in levels {
+            level.previous_size = level.size;
+            level.change_direction = OrderChangeDirection::None;
+            level.change_timestamp = None;
+            level.highlight_duration = highlight;
+        }
+        return;
+    }
+    for level in levels {
+        match previous.iter().find(|existing| (existing.price - level.price).abs() < epsilon) {
+            Some(existing) => {
+                level.first_seen = existing.first_seen;
+                level.change_direction = existing.change_direction.clone();
+                level.change_timestamp = existing.change_timestamp;
+                level.highlight_duration = existing.highlight_duration;
+                if level.size != existing.size {
+                    level.highlight_duration = highlight;
+                }
+                level.mark_size_change(existing.size, now);
+            }
+            None => {
+                level.previous_size = 0.0;
+                level.change_direction = OrderChangeDirection::Increase;
+                level.change_timestamp = Some(now);
+                level.highlight_duration = highlight;
+            }
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct OrderBookData {
@@ -72,10 +133,63 @@ pub struct OrderBookData {
     pub last_updated: DateTime<Utc>,
     pub chart_center_price: Option<f64>,
     pub chart_needs_recentering: bool,
+    pub chart_locked: bool, // When true the depth chart keeps chart_center_price instead of following the mid
     pub price_history: PriceHistory,
+    pub open_midpoint: f64, // Midpoint when the token was first loaded, 0.0 until both sides are known
+    pub recent_trades: VecDeque<TradeTick>, // Oldest first, bounded by RECENT_TRADES_MAX
+    pub tape_started: DateTime<Utc>, // When trades for this token started being recorded
+    pub trade_activity: Option<TradeActivity>, // None until the tape covers the activity window
+    pub imbalance_history: VecDeque<(Instant, f64)>, // Top-of-book size imbalance samples, oldest first
+    pub metadata: Option<MarketMetadata>, // None when the token isn't in the loaded market list
+    pub crossed: bool, // Best bid at or above best ask as of the last recorded midpoint
+    pub snapshot_jump_at: Option<Instant>, // Last `book` snapshot that disagreed with the delta-built top of book
+    pub large_trade: Option<(TradeTick, Instant)>, // Last print at or above the large-trade size, with when it arrived
+}
+
+/// Half a tick separates distinct levels regardless of the market's granularity; 0 means the tick isn't known yet
+pub fn price_epsilon(tick_size: f64) -> f64 {
+    if tick_size > 0.0 { tick_size / 2.0 } else { DEFAULT_PRICE_EPSILON }
+}
+
+/// Clamps a tick size to MIN_TICK_SIZE, so it can always be divided by and taken the log of
+pub fn sanitize_tick_size(tick_size: f64) -> f64 {
+    if tick_size.is_finite() {
+        tick_size.max(MIN_TICK_SIZE)
+    } else {
+        MIN_TICK_SIZE
+    }
 }
 
 impl OrderBookData {
+    pub fn price_epsilon(&self) -> f64 {
+        price_epsilon(self.tick_size)
+    }
+
+    /// A book with no levels yet, for tokens streamed or fetched in the background
+    pub fn empty(token_id: String, market_question: String) -> Self {
+        Self {
+            token_id,
+            market_question,
+            bids: Vec::new(),
+            asks: Vec::new(),
+            tick_size: 0.0,
+            last_updated: Utc::now(),
+            chart_center_price: None,
+            chart_needs_recentering: false,
+            chart_locked: false,
+            price_history: PriceHistory::new(ORDERBOOK_PRICE_HISTORY_POINTS),
+            open_midpoint: 0.0,
+            recent_trades: VecDeque::new(),
+            tape_started: Utc::now(),
+            trade_activity: None,
+            imbalance_history: VecDeque::new(),
+            metadata: None,
+            crossed: false,
+            snapshot_jump_at: None,
+            large_trade: None,
+        }
+    }
+
     pub fn get_spread(&self) -> f64 {
         if let (Some(best_bid), Some(best_ask)) = (self.bids.first(), self.asks.first()) {
             best_ask.price - best_bid.price
@@ -91,6 +205,232 @@ impl OrderBookData {
             0.0
         }
     }
+
+    /// USDC resting on each side of the displayed book, as `(bids, asks)`
+    pub fn get_side_notionals(&self) -> (f64, f64) {
+        let notional = |orders: &[SimpleOrder]| orders.iter().map(|order| order.price * order.size).sum();
+        (notional(&self.bids), notional(&self.asks))
+    }
+
+    /// Every tick size the API or stream reports goes through here
+    pub fn set_tick_size(&mut self, tick_size: f64) {
+        let sanitized = sanitize_tick_size(tick_size);
+        if sanitized != tick_size {
+            warn!("Tick size {tick_size} for {} clamped to {sanitized}", crate::utils::short_id(&self.token_id, 12));
+        }
+        self.tick_size = sanitized;
+    }
+
+    /// The tick size to compute with; a book whose tick isn't known yet keeps 0 in `tick_size`
+    pub fn effective_tick_size(&self) -> f64 {
+        sanitize_tick_size(self.tick_size)
+    }
+
+    pub fn price_decimal_places(&self) -> usize {
+        crate::utils::price_decimal_places(self.tick_size)
+    }
+
+    /// Shares within `bps` of the midpoint on each side, as `(bids, asks)`; unlike the totals it
+    /// ignores size parked far from the market
+    pub fn depth_within_bps(&self, bps: f64) -> (f64, f64) {
+        let midpoint = if self.is_crossed() { 0.0 } else { self.get_midpoint() };
+        (
+            crate::utils::depth_within_bps(&self.bids, midpoint, bps),
+            crate::utils::depth_within_bps(&self.asks, midpoint, bps),
+        )
+    }
+
+    /// Best bid at or above best ask, which a healthy book never shows
+    pub fn is_crossed(&self) -> bool {
+        match (self.bids.first(), self.asks.first()) {
+            (Some(best_bid), Some(best_ask)) => best_bid.price >= best_ask.price,
+            _ => false,
+        }
+    }
+
+    pub fn best_prices(&self) -> (Option<f64>, Option<f64>) {
+        (self.bids.first().map(|bid| bid.price), self.asks.first().map(|ask| ask.price))
+    }
+
+    /// Compares the top of book after a full snapshot with `previous`, the one built from deltas.
+    /// A move of more than `max_ticks` on either side means deltas were probably missed.
+    pub fn check_snapshot_jump(&mut self, previous: (Option<f64>, Option<f64>), max_ticks: f64) {
+        if max_ticks <= 0.0 || self.tick_size <= 0.0 {
+            return;
+        }
+        let (bid, ask) = self.best_prices();
+        let jump = |before: Option<f64>, after: Option<f64>| match (before, after) {
+            (Some(before), Some(after)) => ((after - before) / self.tick_size).abs(),
+            _ => 0.0,
+        };
+        let (bid_jump, ask_jump) = (jump(previous.0, bid), jump(previous.1, ask));
+        if bid_jump > max_ticks || ask_jump > max_ticks {
+            warn!(
+                "Book snapshot for {} moved the top of book by {bid_jump:.0} ticks (bid) / {ask_jump:.0} ticks (ask); price_change deltas may have been missed",
+                crate::utils::short_id(&self.token_id, 12),
+            );
+            self.snapshot_jump_at = Some(Instant::now());
+        }
+    }
+
+    pub fn snapshot_jump_flagged(&self) -> bool {
+        self.snapshot_jump_at.is_some_and(|at| at.elapsed().as_secs() < BOOK_JUMP_FLAG_SECS)
+    }
+
+    /// Sets the opening midpoint once a two-sided, uncrossed book is available
+    pub fn record_open_midpoint_if_missing(&mut self) {
+        if self.open_midpoint <= 0.0 && !self.is_crossed() {
+            self.open_midpoint = self.get_midpoint();
+        }
+    }
+
+    /// Size imbalance over the top levels, from -1 (all asks) to +1 (all bids); `None` for an empty book
+    pub fn size_imbalance(&self) -> Option<f64> {
+        let bid_size: f64 = self.bids.iter().take(DEPTH_IMBALANCE_LEVELS).map(|bid| bid.size).sum();
+        let ask_size: f64 = self.asks.iter().take(DEPTH_IMBALANCE_LEVELS).map(|ask| ask.size).sum();
+        let total = bid_size + ask_size;
+        (total > 0.0).then(|| (bid_size - ask_size) / total)
+    }
+
+    /// Samples the imbalance into its ring buffer, at most once per IMBALANCE_SAMPLE_MS
+    fn record_imbalance(&mut self) {
+        let due = self.imbalance_history.back()
+            .is_none_or(|(at, _)| at.elapsed().as_millis() >= IMBALANCE_SAMPLE_MS);
+        if let (true, Some(imbalance)) = (due, self.size_imbalance()) {
+            self.imbalance_history.push_back((Instant::now(), imbalance));
+            while self.imbalance_history.len() > IMBALANCE_HISTORY_POINTS {
+                self.imbalance_history.pop_front();
+            }
+        }
+    }
+
+    /// Adds the midpoint to the price history unless the book is one-sided or crossed, and samples the imbalance.
+    /// Crossed books come from bad deltas and their midpoint would spike the chart until the resync.
+    pub fn record_midpoint(&mut self) {
+        let crossed = self.is_crossed();
+        if crossed && !self.crossed {
+            warn!(
+                "Crossed book for {}: best bid {} >= best ask {}, not recording midpoints until it uncrosses",
+                crate::utils::short_id(&self.token_id, 12),
+                self.bids.first().map_or(0.0, |bid| bid.price),
+                self.asks.first().map_or(0.0, |ask| ask.price),
+            );
+        }
+        self.crossed = crossed;
+
+        let midpoint = self.get_midpoint();
+        if !crossed && midpoint > 0.0 {
+            self.price_history.add_price(midpoint);
+            self.record_imbalance();
+        }
+    }
+
+    /// Also starts the header flash when the trade is at least `large_trade_size` shares (0 disables)
+    pub fn record_trade(&mut self, trade: TradeTick, large_trade_size: f64, activity_window: Duration) {
+        if large_trade_size > 0.0 && trade.size >= large_trade_size {
+            self.large_trade = Some((trade.clone(), Instant::now()));
+        }
+        self.recent_trades.push_back(trade);
+        while self.recent_trades.len() > RECENT_TRADES_MAX {
+            self.recent_trades.pop_front();
+        }
+        self.refresh_trade_activity(activity_window, Utc::now());
+    }
+
+    /// Trades and notional per minute over the last `window`. Stays `None` until the tape has been
+    /// recording for the whole window; a full tape only reaches back to its oldest trade, so its span is used.
+    pub fn refresh_trade_activity(&mut self, window: Duration, now: DateTime<Utc>) {
+        let window_start = now - window;
+        let covered_from = match self.recent_trades.front() {
+            Some(oldest) if self.recent_trades.len() >= RECENT_TRADES_MAX => oldest.timestamp.max(window_start),
+            _ if self.tape_started <= window_start => window_start,
+            _ => {
+                self.trade_activity = None;
+                return;
+            }
+        };
+
+        let minutes = (now - covered_from).num_milliseconds() as f64 / 60_000.0;
+        if minutes <= 0.0 {
+            self.trade_activity = None;
+            return;
+        }
+        let (count, notional) = self.recent_trades.iter()
+            .filter(|trade| trade.timestamp >= covered_from)
+            .fold((0usize, 0.0), |(count, notional), trade| (count + 1, notional + trade.price * trade.size));
+        self.trade_activity = Some(TradeActivity {
+            trades_per_min: count as f64 / minutes,
+            notional_per_min: notional / minutes,
+        });
+    }
+
+    /// The large trade while its flash lasts
+    pub fn flashing_large_trade(&self) -> Option<&TradeTick> {
+        self.large_trade.as_ref()
+            .filter(|(_, at)| at.elapsed().as_millis() < LARGE_TRADE_FLASH_MS)
+            .map(|(trade, _)| trade)
+    }
+
+    /// Absolute and percentage midpoint change since the token was first loaded
+    pub fn get_midpoint_change(&self) -> Option<(f64, f64)> {
+        let midpoint = self.get_midpoint();
+        if self.open_midpoint <= 0.0 || midpoint <= 0.0 {
+            return None;
+        }
+        let change = midpoint - self.open_midpoint;
+        Some((change, change / self.open_midpoint * 100.0))
+    }
+}
+
+/// How busy the tape is right now, per minute over the activity window
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TradeActivity {
+    pub trades_per_min: f64,
+    pub notional_per_min: f64, // USDC
+}
+
+/// A single executed trade from the market channel
+#[derive(Debug, Clone)]
+pub struct TradeTick {
+    pub timestamp: DateTime<Utc>,
+    pub price: f64,
+    pub size: f64,
+    pub is_buy: bool,
+}
+
+/// Top-of-book snapshot for a single token
+#[derive(Debug, Clone)]
+pub struct TokenQuote {
+    pub label: String,
+    pub token_id: String,
+    pub best_bid: f64,
+    pub best_ask: f64,
+}
+
+impl TokenQuote {
+    pub fn get_midpoint(&self) -> f64 {
+        crate::utils::get_midpoint(self.best_bid, self.best_ask)
+    }
+}
+
+/// Descriptive gamma fields of the loaded market, kept for the info panel
+#[derive(Debug, Clone, Default)]
+pub struct MarketMetadata {
+    pub question: String,
+    pub outcome: Option<String>,
+    pub event_title: Option<String>,
+    pub category: Option<String>,
+    pub end_date: Option<String>, // As reported by gamma, usually RFC 3339
+    pub resolution_source: Option<String>,
+    pub description: Option<String>,
+}
+
+/// Both outcomes of a binary market, viewed from the currently loaded token
+#[derive(Debug, Clone)]
+pub struct OutcomePair {
+    pub primary_token_id: String,
+    pub primary_outcome: String,
+    pub sibling: TokenQuote,
 }
 
 #[derive(Debug, Clone)]
@@ -111,10 +451,21 @@ pub struct PricePoint {
     pub price: f64,
 }
 
+/// Open, high, low and close of the prices seen in one fixed interval
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Candle {
+    pub start: i64, // Unix seconds, a multiple of the interval
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+}
+
 #[derive(Debug, Clone)]
 pub struct PriceHistory {
     pub points: Vec<PricePoint>,
     pub max_points: usize,
+    pub max_age: Option<Duration>, // Points older than this are evicted on insert
 }
 
 impl PriceHistory {
@@ -122,8 +473,16 @@ impl PriceHistory {
         Self {
             points: Vec::with_capacity(max_points),
             max_points,
+            max_age: None,
         }
     }
+
+    /// Bound the history by both point count and age
+    pub fn with_max_age(max_points: usize, max_age: std::time::Duration) -> Self {
+        let mut history = Self::new(max_points);
+        history.max_age = Duration::from_std(max_age).ok();
+        history
+    }
     
     pub fn add_price(&mut self, price: f64) {
         let now = Utc::now();
@@ -145,6 +504,16 @@ impl PriceHistory {
         if self.points.len() > self.max_points {
             self.points.remove(0);
         }
+
+        self.prune_expired(now);
+    }
+
+    /// Drop points that fall outside the retention window
+    pub fn prune_expired(&mut self, now: DateTime<Utc>) {
+        if let Some(max_age) = self.max_age {
+            let cutoff = now - max_age;
+            self.points.retain(|p| p.timestamp >= cutoff);
+        }
     }
     
     pub fn get_price_range(&self) -> Option<(f64, f64)> {
@@ -210,3 +579,157 @@ impl CryptoPrice {
 
 // Backward compatibility alias
 pub type BitcoinPrice = CryptoPrice;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn price_history_evicts_points_outside_the_window() {
+        let mut history = PriceHistory::with_max_age(ORDERBOOK_PRICE_HISTORY_POINTS, std::time::Duration::from_secs(30 * 60));
+        let now = Utc::now();
+        history.points.push(PricePoint { timestamp: now - Duration::minutes(45), price: 0.40 });
+        history.points.push(PricePoint { timestamp: now - Duration::minutes(29), price: 0.45 });
+
+        history.add_price(0.50);
+
+        let prices: Vec<f64> = history.points.iter().map(|point| point.price).collect();
+        assert_eq!(prices, vec![0.45, 0.50]);
+    }
+
+    #[test]
+    fn price_history_without_window_keeps_old_points() {
+        let mut history = PriceHistory::new(ORDERBOOK_PRICE_HISTORY_POINTS);
+        history.points.push(PricePoint { timestamp: Utc::now() - Duration::days(2), price: 0.40 });
+
+        history.add_price(0.50);
+
+        assert_eq!(history.points.len(), 2);
+    }
+
+    fn book(bids: &[(f64, f64)], asks: &[(f64, f64)]) -> OrderBookData {
+        let mut book = OrderBookData::empty("token".to_string(), "Question?".to_string());
+        book.bids = bids.iter().map(|&(price, size)| SimpleOrder::new(price, size)).collect();
+        book.asks = asks.iter().map(|&(price, size)| SimpleOrder::new(price, size)).collect();
+        book
+    }
+
+    #[test]
+    fn crossed_book_records_no_midpoint() {
+        let mut book = book(&[(0.55, 10.0)], &[(0.50, 10.0)]);
+
+        book.record_midpoint();
+
+        assert!(book.crossed);
+        assert!(book.price_history.points.is_empty());
+
+        book.asks = vec![SimpleOrder::new(0.60, 10.0)];
+        book.record_midpoint();
+
+        assert!(!book.crossed);
+        assert_eq!(book.price_history.points.len(), 1);
+    }
+
+    const HIGHLIGHT: std::time::Duration = std::time::Duration::from_millis(500);
+
+    /// Levels as they stand once their initial highlight has run out
+    fn settled(levels: &[(f64, f64)], now: Instant) -> Vec<SimpleOrder> {
+        levels.iter().map(|&(price, size)| {
+            let mut level = SimpleOrder::new(price, size);
+            level.change_direction = OrderChangeDirection::None;
+            level.change_timestamp = None;
+            level.first_seen = now;
+            level
+        }).collect()
+    }
+
+    fn diffed(previous: &[SimpleOrder], levels: &[(f64, f64)], now: Instant) -> Vec<SimpleOrder> {
+        let mut levels: Vec<SimpleOrder> = levels.iter().map(|&(price, size)| SimpleOrder::new(price, size)).collect();
+        diff_levels(&mut levels, previous, DEFAULT_PRICE_EPSILON, now, HIGHLIGHT);
+        levels
+    }
+
+    #[test]
+    fn grown_level_highlights_as_increase() {
+        let start = Instant::now();
+        let previous = settled(&[(0.50, 100.0)], start);
+
+        let levels = diffed(&previous, &[(0.50, 150.0)], start + HIGHLIGHT);
+
+        assert_eq!(levels[0].change_direction, OrderChangeDirection::Increase);
+        assert_eq!(levels[0].previous_size, 100.0);
+        assert_eq!(levels[0].first_seen, start);
+    }
+
+    #[test]
+    fn shrunk_level_highlights_as_decrease() {
+        let start = Instant::now();
+        let previous = settled(&[(0.50, 100.0)], start);
+
+        let levels = diffed(&previous, &[(0.50, 40.0)], start + HIGHLIGHT);
+
+        assert_eq!(levels[0].change_direction, OrderChangeDirection::Decrease);
+        assert_eq!(levels[0].previous_size, 100.0);
+    }
+
+    #[test]
+    fn unchanged_level_stays_unhighlighted() {
+        let start = Instant::now();
+        let previous = settled(&[(0.50, 100.0)], start);
+
+        let levels = diffed(&previous, &[(0.50, 100.0)], start + HIGHLIGHT);
+
+        assert_eq!(levels[0].change_direction, OrderChangeDirection::None);
+        assert!(levels[0].change_timestamp.is_none());
+    }
+
+    #[test]
+    fn new_level_highlights_as_increase_and_removed_level_drops_out() {
+        let start = Instant::now();
+        let now = start + HIGHLIGHT;
+        let previous = settled(&[(0.50, 100.0), (0.49, 20.0)], start);
+
+        let levels = diffed(&previous, &[(0.51, 30.0), (0.50, 100.0)], now);
+
+        let prices: Vec<f64> = levels.iter().map(|level| level.price).collect();
+        assert_eq!(prices, vec![0.51, 0.50]);
+        assert_eq!(levels[0].change_direction, OrderChangeDirection::Increase);
+        assert_eq!(levels[0].previous_size, 0.0);
+        assert_eq!(levels[1].change_direction, OrderChangeDirection::None);
+    }
+
+    #[test]
+    fn levels_of_a_newly_opened_book_are_not_highlighted() {
+        let levels = diffed(&[], &[(0.50, 100.0), (0.49, 20.0)], Instant::now());
+
+        assert!(levels.iter().all(|level| level.change_direction == OrderChangeDirection::None));
+        assert!(levels.iter().all(|level| level.change_timestamp.is_none()));
+    }
+
+    #[test]
+    fn highlight_expires_after_its_duration() {
+        let start = Instant::now();
+        let previous = settled(&[(0.50, 100.0)], start);
+
+        let levels = diffed(&previous, &[(0.50, 150.0)], start);
+
+        assert!(levels[0].is_highlighted_at(start + HIGHLIGHT / 2));
+        assert!(!levels[0].is_highlighted_at(start + HIGHLIGHT + std::time::Duration::from_millis(1)));
+    }
+
+    #[test]
+    fn highlight_clears_exactly_at_the_configured_duration() {
+        let duration = std::time::Duration::from_millis(1234);
+        let mut level = SimpleOrder::new(0.50, 100.0);
+        level.highlight_duration = duration;
+        let start = level.change_timestamp.unwrap();
+
+        assert!(level.is_highlighted_at(start + duration - std::time::Duration::from_nanos(1)));
+        assert!(!level.is_highlighted_at(start + duration));
+
+        level.change_timestamp = Instant::now().checked_sub(duration);
+        level.clear_highlight_if_expired();
+        assert_eq!(level.change_direction, OrderChangeDirection::None);
+        assert!(level.change_timestamp.is_none());
+    }
+}
@@ -0,0 +1,85 @@
+//! REST data the app reads, behind a trait so a canned source can stand in for the CLOB
+
+use anyhow::{anyhow, Result};
+use futures_util::future::LocalBoxFuture;
+use polymarket_rs_client::{ClobClient, Event, OrderBookSummary, PriceHistoryResponse};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+
+/// The CLOB and gamma calls `App` makes
+pub trait MarketDataSource {
+    fn get_gamma_events(&self, offset: Option<u32>, limit: Option<u32>) -> LocalBoxFuture<'_, Result<Vec<Event>>>;
+
+    fn get_order_book<'a>(&'a self, token_id: &'a str) -> LocalBoxFuture<'a, Result<OrderBookSummary>>;
+
+    fn get_price_history<'a>(&'a self, token_id: &'a str, interval: &'a str, fidelity: u32) -> LocalBoxFuture<'a, Result<PriceHistoryResponse>>;
+
+    fn get_tick_size<'a>(&'a self, token_id: &'a str) -> LocalBoxFuture<'a, Result<Decimal>>;
+}
+
+impl MarketDataSource for ClobClient {
+    fn get_gamma_events(&self, offset: Option<u32>, limit: Option<u32>) -> LocalBoxFuture<'_, Result<Vec<Event>>> {
+        Box::pin(ClobClient::get_gamma_events(self, offset, limit))
+    }
+
+    fn get_order_book<'a>(&'a self, token_id: &'a str) -> LocalBoxFuture<'a, Result<OrderBookSummary>> {
+        Box::pin(ClobClient::get_order_book(self, token_id))
+    }
+
+    fn get_price_history<'a>(&'a self, token_id: &'a str, interval: &'a str, fidelity: u32) -> LocalBoxFuture<'a, Result<PriceHistoryResponse>> {
+        Box::pin(ClobClient::get_price_history(self, token_id, interval, fidelity))
+    }
+
+    fn get_tick_size<'a>(&'a self, token_id: &'a str) -> LocalBoxFuture<'a, Result<Decimal>> {
+        Box::pin(ClobClient::get_tick_size(self, token_id))
+    }
+}
+
+/// Canned responses for driving `App` without a network; unknown tokens return an error like the API would
+#[derive(Debug, Clone, Default)]
+pub struct MockMarketData {
+    pub events: Vec<Event>,
+    pub books: HashMap<String, OrderBookSummary>,
+    pub price_histories: HashMap<String, PriceHistoryResponse>,
+    pub tick_sizes: HashMap<String, Decimal>,
+}
+
+impl MockMarketData {
+    pub fn with_book(mut self, token_id: &str, book: OrderBookSummary) -> Self {
+        self.books.insert(token_id.to_string(), book);
+        self
+    }
+
+    pub fn with_events(mut self, events: Vec<Event>) -> Self {
+        self.events = events;
+        self
+    }
+}
+
+impl MarketDataSource for MockMarketData {
+    /// Pages through `events` like gamma's offset/limit pagination
+    fn get_gamma_events(&self, offset: Option<u32>, limit: Option<u32>) -> LocalBoxFuture<'_, Result<Vec<Event>>> {
+        let offset = offset.unwrap_or(0) as usize;
+        let limit = limit.map_or(self.events.len(), |limit| limit as usize);
+        let page = self.events.iter().skip(offset).take(limit).cloned().collect();
+        Box::pin(async move { Ok(page) })
+    }
+
+    fn get_order_book<'a>(&'a self, token_id: &'a str) -> LocalBoxFuture<'a, Result<OrderBookSummary>> {
+        let book = self.books.get(token_id).cloned().ok_or_else(|| anyhow!("No orderbook exists for token {token_id}"));
+        Box::pin(async move { book })
+    }
+
+    /// Tokens without a canned history get an empty one
+    fn get_price_history<'a>(&'a self, token_id: &'a str, _interval: &'a str, _fidelity: u32) -> LocalBoxFuture<'a, Result<PriceHistoryResponse>> {
+        let history = self.price_histories.get(token_id).cloned()
+            .unwrap_or(PriceHistoryResponse { history: Vec::new() });
+        Box::pin(async move { Ok(history) })
+    }
+
+    /// Polymarket's common 0.01 tick unless one is canned
+    fn get_tick_size<'a>(&'a self, token_id: &'a str) -> LocalBoxFuture<'a, Result<Decimal>> {
+        let tick_size = self.tick_sizes.get(token_id).copied().unwrap_or(Decimal::new(1, 2));
+        Box::pin(async move { Ok(tick_size) })
+    }
+}
@@ -4,6 +4,9 @@ pub mod bot;
 pub mod cli;
 pub mod config;
 pub mod data;
+pub mod data_source;
+pub mod logging;
+pub mod proxy;
 pub mod ui;
 pub mod websocket;
 pub mod utils;
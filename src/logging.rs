@@ -0,0 +1,56 @@
+//! Log sink selection
+
+use anyhow::{Context, Result};
+use cli_log::*;
+use crossterm::{
+    event::DisableMouseCapture,
+    execute,
+    terminal::{disable_raw_mode, LeaveAlternateScreen},
+};
+use std::{backtrace::Backtrace, fs::OpenOptions, io, panic, sync::Mutex, thread};
+use tracing_subscriber::EnvFilter;
+
+use crate::config::{DEFAULT_LOG_FILE, DEFAULT_LOG_LEVEL};
+
+/// Routes `log` records (the `cli_log` macros) into a file at the given level.
+/// The logger is process-global, so WebSocket threads with their own runtimes share the sink.
+pub fn init_file_logging(level: Option<&str>, file: Option<&str>) -> Result<()> {
+    let level = level.unwrap_or(DEFAULT_LOG_LEVEL);
+    let path = file.unwrap_or(DEFAULT_LOG_FILE);
+
+    let filter = EnvFilter::try_new(level)
+        .with_context(|| format!("Invalid log level '{level}'"))?;
+    let log_file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open log file '{path}'"))?;
+
+    // Also installs the `log` -> `tracing` bridge so existing macros reach this subscriber
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(Mutex::new(log_file))
+        .with_ansi(false)
+        .with_thread_names(true)
+        .try_init()
+        .map_err(|e| anyhow::anyhow!("Failed to initialize logging: {e}"))
+}
+
+/// Logs the panic message and a backtrace. A panic on the main (UI) thread ends the app, so the terminal is
+/// restored before the default hook prints; it runs at the panic site, so raw mode is left even when the panic
+/// comes from inside `terminal.draw`. Worker threads (WebSocket and price feeds) only log, since the TUI keeps running.
+pub fn install_panic_hook() {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let backtrace = Backtrace::force_capture();
+        let thread = thread::current();
+        let thread_name = thread.name().unwrap_or("unnamed");
+        error!("Application panicked on thread '{thread_name}': {info}\n{backtrace}");
+
+        if thread.name() == Some("main") {
+            disable_raw_mode().ok();
+            execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture).ok();
+            default_hook(info);
+        }
+    }));
+}
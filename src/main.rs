@@ -15,38 +15,102 @@ use std::{
     panic::AssertUnwindSafe
 };
 use cli_log::*;
-use clap::Parser;
+use futures_util::FutureExt;
 
 // Import from our local library modules
 use polymarket::{App, Cli, render_ui};
 
-#[tokio::main]
-async fn main() -> Result<()> {
+/// Builds the runtime by hand so the environment is final before any worker thread exists
+fn main() -> Result<()> {
     // Load environment variables from .env file
     dotenv().ok();
 
-    init_cli_log!();
+    let (cli, explicit_args) = Cli::parse_with_explicit_args();
+
+    // Explicit log options take over from cli_log's environment-driven setup
+    if cli.log_level.is_some() || cli.log_file.is_some() {
+        polymarket::logging::init_file_logging(cli.log_level.as_deref(), cli.log_file.as_deref())?;
+    } else {
+        init_cli_log!();
+    }
     info!("Starting Polymarket Orderbook Viewer...");
 
-    let cli = Cli::parse();
+    if let Some(proxy) = polymarket::proxy::init_proxy(cli.proxy.as_deref()) {
+        info!("Routing outbound connections through proxy {proxy}");
+        if cli.proxy.is_some() {
+            // The CLOB client's HTTP stack reads the standard proxy variables, so hand it the explicit URL too.
+            // SAFETY: the process is still single-threaded, the tokio runtime is only built below
+            unsafe { std::env::set_var("HTTPS_PROXY", proxy) };
+        }
+    }
+
+    tokio::runtime::Runtime::new()?.block_on(run(cli, explicit_args))
+}
+
+async fn run(cli: Cli, explicit_args: Vec<String>) -> Result<()> {
+    if cli.check_config {
+        return check_config(&cli);
+    }
+
+    if let Some(ref path) = cli.dump_markets {
+        return dump_markets(&cli, path).await;
+    }
 
-    // Gracefully handle panics and restore the terminal
-    let result = AssertUnwindSafe(run_tui_app(cli)).await;
+    // The hook logs and restores the terminal at the panic site; catching here keeps the exit orderly
+    polymarket::logging::install_panic_hook();
+    let result = AssertUnwindSafe(run_tui_app(cli, explicit_args)).catch_unwind().await;
 
     // Restore terminal state
     disable_raw_mode().ok();
     execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture).ok();
 
-    if let Err(panic) = result {
-        eprintln!("\n\nApplication panicked: {panic:?}\n\n");
-        return Err(anyhow::anyhow!("Application panicked"));
+    match result {
+        Ok(app_result) => app_result,
+        Err(_) => Err(anyhow::anyhow!("Application panicked, see the log for the backtrace")),
     }
+}
+
+
+/// Validates every config file the app would read, printing one line per file
+fn check_config(cli: &Cli) -> Result<()> {
+    let results = [
+        (&cli.settings, polymarket::app::settings::Settings::load(&cli.settings).map(|_| ())),
+        (&cli.keymap, polymarket::app::keymap::Keymap::load(&cli.keymap).map(|_| ())),
+        (&cli.strategy_config, polymarket::bot::StrategyStore::new(&cli.strategy_config)
+            .load(&mut polymarket::bot::BotEngine::new().strategies)),
+    ];
 
+    let mut invalid = 0;
+    for (path, result) in results {
+        match result {
+            Ok(()) if !std::path::Path::new(path).exists() => println!("{path}: not found, defaults apply"),
+            Ok(()) => println!("{path}: ok"),
+            Err(e) => {
+                invalid += 1;
+                println!("{e}");
+            }
+        }
+    }
+    if invalid > 0 {
+        return Err(anyhow::anyhow!("{invalid} invalid config file(s)"));
+    }
     Ok(())
 }
 
+/// Headless catalog export: load every page of markets, write JSON and exit
+async fn dump_markets(cli: &Cli, path: &str) -> Result<()> {
+    let mut app = App::new(cli.interval, cli.depth, &cli.private_key_env, cli.app_options(Default::default())).await?;
+    app.max_events = cli.max_events;
+    app.events_page_size = cli.page_size;
+    app.include_closed_markets = cli.include_closed;
+
+    app.load_markets().await?;
+    let count = app.dump_markets(path)?;
+    println!("Wrote {count} markets to {path}");
+    Ok(())
+}
 
-async fn run_tui_app(cli: Cli) -> Result<()> {
+async fn run_tui_app(cli: Cli, explicit_args: Vec<String>) -> Result<()> {
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -55,10 +119,78 @@ async fn run_tui_app(cli: Cli) -> Result<()> {
     let mut terminal = Terminal::new(backend)?;
 
     // Create app
-    let mut app = App::new(cli.interval, cli.depth, &cli.private_key_env).await?;
+    let (start_view, start_problems) = polymarket::app::StartView::parse(&cli.start_tab, &cli.start_view);
+    let mut app = App::new(cli.interval, cli.depth, &cli.private_key_env, cli.app_options(start_view)).await?;
+    app.price_history_max_age = (cli.history_window > 0).then(|| Duration::from_secs(cli.history_window * 60));
+    if !start_problems.is_empty() {
+        let problems = start_problems.join("; ");
+        warn!("Ignoring start options: {problems}");
+        app.set_status_message(format!("{problems}, using the default"));
+    }
+    if cli.log_alerts {
+        app.bot_engine.alert_logger = Some(polymarket::bot::AlertCsvLogger::new(&cli.alert_log_dir));
+    }
+    if let Some(path) = &cli.log_mid {
+        let max_bytes = cli.log_mid_max_mb.map(|mb| mb * 1024 * 1024);
+        let logger = polymarket::app::mid_log::MidpointCsvLogger::new(path, max_bytes)
+            .map_err(|e| anyhow::anyhow!("Cannot open midpoint log {path}: {e}"))?;
+        app.mid_logger = Some(logger);
+    }
+    if cli.alert_bell || cli.alert_command.is_some() {
+        app.bot_engine.alert_sound = Some(polymarket::bot::AlertSound::new(cli.alert_bell, cli.alert_command.clone()));
+    }
+    app.bot_engine.quiet_hours = cli.quiet_hours.clone().map(|quiet_hours| polymarket::bot::QuietHours {
+        min_severity: cli.quiet_severity.clone(),
+        ..quiet_hours
+    });
+    app.bot_engine.spread_alert_ticks = cli.spread_alert_ticks;
+    app.bot_engine.depth_imbalance_ratio = cli.depth_imbalance_ratio;
+    app.bot_engine.max_alerts = cli.max_alerts.max(1);
+    app.bot_engine.load_strategy_store(polymarket::bot::StrategyStore::new(&cli.strategy_config));
+    app.max_events = cli.max_events;
+    app.events_page_size = cli.page_size;
+    app.include_closed_markets = cli.include_closed;
+    app.depth_chart_ticks = cli.depth_ticks;
+    app.book_jump_ticks = cli.book_jump_ticks;
+    app.large_trade_size = cli.large_trade_size;
+    app.highlight_duration = Duration::from_millis(cli.highlight_ms);
+    app.trade_activity_window = chrono::Duration::seconds(cli.activity_window as i64);
+    app.depth_bps = cli.depth_bps;
+    if let Some(path) = &cli.log_file {
+        app.log_file = path.into();
+    }
+    app.prewarm = cli.prewarm;
+    app.user_channel_enabled = !cli.no_user_channel;
+    app.market_row_format = cli.market_row.clone();
+    app.resolution_warning = chrono::Duration::minutes(cli.resolution_warning as i64);
+    app.ws_stale_timeout = (cli.stale_timeout > 0).then(|| Duration::from_secs(cli.stale_timeout));
+    app.market_price_history_refresh = (cli.history_refresh > 0).then(|| Duration::from_secs(cli.history_refresh));
+    app.idle_timeout = (cli.idle_timeout > 0).then(|| Duration::from_secs(cli.idle_timeout));
+    app.idle_dim = cli.idle_dim;
+    if let Some(port) = cli.http_port {
+        polymarket::app::health::start_health_server(cli.http_bind, port, std::sync::Arc::clone(&app.health))
+            .map_err(|e| anyhow::anyhow!("Cannot serve the health endpoint on {}:{port}: {e}", cli.http_bind))?;
+    }
+    app.crypto_feed = polymarket::websocket::CryptoFeed {
+        source: cli.crypto_source,
+        stream_urls: cli.crypto_streams.iter().cloned().collect(),
+    };
 
-    // Load initial data
-    app.load_markets().await?;
+    // Load initial data, redrawing after every page so progress is visible
+    app.load_markets_with_progress(|app| {
+        if let Err(e) = terminal.draw(|f| render_ui(f, app)) {
+            warn!("Failed to draw loading progress: {e}");
+        }
+    }).await?;
+    // After loading so a keymap error isn't cleared with the loading errors
+    app.load_keymap(&cli.keymap);
+    app.settings_path = cli.settings.clone().into();
+    load_saved_settings(&mut app, &cli, &explicit_args).await;
+    if let Some(ref strategy_type) = cli.strategy {
+        app.auto_start_strategy(strategy_type, &cli.strategy_markets, &cli.strategy_events)?;
+    }
+    // Saved arbitrage events need the loaded catalog to find their outcome tokens
+    app.sync_categorical_arbitrage_markets();
 
     // If token ID is provided, use it directly
     if let Some(token_id) = cli.token_id {
@@ -67,6 +199,8 @@ async fn run_tui_app(cli: Cli) -> Result<()> {
         app.load_orderbook(&token_id).await?;
         // Start WebSocket for this specific token
         app.start_websocket_for_token(&token_id);
+    } else if cli.auto && !app.auto_select_top_market().await? {
+        warn!("--auto found no market with a token id, staying in the selector");
     }
 
     // Main loop
@@ -88,22 +222,50 @@ async fn run_tui_app(cli: Cli) -> Result<()> {
     res
 }
 
+/// Saved settings replace the command line defaults, but flags given explicitly still win
+async fn load_saved_settings(app: &mut App, cli: &Cli, explicit_args: &[String]) {
+    let mut settings = match polymarket::app::settings::Settings::load(&app.settings_path) {
+        Ok(Some(settings)) => settings,
+        Ok(None) => return,
+        Err(e) => {
+            warn!("Failed to load settings from {}: {e}", app.settings_path.display());
+            app.error_message = Some(format!("Failed to load settings: {e}"));
+            return;
+        }
+    };
+
+    let explicit = |id: &str| explicit_args.iter().any(|arg| arg == id);
+    if explicit("interval") {
+        settings.update_interval_secs = cli.interval;
+    }
+    if explicit("depth") {
+        settings.depth = cli.depth;
+    }
+    if explicit("depth_ticks") {
+        settings.depth_chart_ticks = cli.depth_ticks;
+    }
+    app.apply_settings(&settings).await;
+}
 
 async fn run_app<B: Backend>(
     terminal: &mut Terminal<B>,
     app: &mut App,
 ) -> Result<()> {
     let tick_rate = Duration::from_millis(polymarket::config::TICK_RATE_MS);
+    let idle_tick_rate = Duration::from_millis(polymarket::config::DATA_UPDATE_RATE_MS);
     let mut last_data_update = Instant::now();
     let data_update_rate = Duration::from_millis(polymarket::config::DATA_UPDATE_RATE_MS);
     let mut last_ui_update = Instant::now();
     let ui_update_rate = Duration::from_millis(polymarket::config::UI_UPDATE_RATE_MS);
 
     loop {
-        let timeout = tick_rate;
+        // Idle, there's no input latency to protect, so wait for the next data update instead of spinning
+        let idle = app.is_idle();
+        let timeout = if idle { idle_tick_rate } else { tick_rate };
 
         if crossterm::event::poll(timeout)? {
             if let Event::Key(key) = event::read()? {
+                app.register_input();
                 if !app.handle_key_input(key.code).await? {
                     return Ok(()); // Exit requested
                 }
@@ -116,10 +278,20 @@ async fn run_app<B: Backend>(
         // Force UI update at least once per second for fading effects
         let force_redraw = last_ui_update.elapsed() >= ui_update_rate;
 
-        // Redraw immediately if needed for instant feedback or if it's been a second
-        if app.needs_redraw || force_redraw {
-            terminal.draw(|f| render_ui(f, app))?;
+        // Redraw immediately if needed for instant feedback or if it's been a second; idle, only the latter
+        if force_redraw {
+            app.refresh_trade_activity();
+        }
+
+        if (app.needs_redraw && !app.is_idle()) || force_redraw {
+            let frame = terminal.draw(|f| render_ui(f, app))?;
             app.needs_redraw = false;
+            if app.screen_dump_requested {
+                app.save_screen_dump(frame.buffer);
+            }
+            if app.session_export_requested {
+                app.save_session_export(frame.buffer);
+            }
             if force_redraw {
                 last_ui_update = Instant::now();
             }
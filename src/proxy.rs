@@ -0,0 +1,100 @@
+//! Outbound HTTP(S) proxy support
+
+use anyhow::{anyhow, bail, Context, Result};
+use cli_log::*;
+use std::sync::OnceLock;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+use tokio_tungstenite::{
+    client_async_tls, connect_async,
+    tungstenite::{handshake::client::Response, http::Uri},
+    MaybeTlsStream, WebSocketStream,
+};
+
+use crate::config::PROXY_ENV_VARS;
+
+static PROXY_URL: OnceLock<Option<String>> = OnceLock::new();
+
+/// Resolves the proxy once: the explicit URL, else the first of the standard proxy env vars
+pub fn init_proxy(explicit: Option<&str>) -> Option<&'static str> {
+    PROXY_URL.get_or_init(|| {
+        explicit.map(str::to_string).or_else(|| {
+            PROXY_ENV_VARS.iter()
+                .find_map(|var| std::env::var(var).ok())
+                .filter(|url| !url.is_empty())
+        })
+    }).as_deref()
+}
+
+pub fn proxy_url() -> Option<&'static str> {
+    PROXY_URL.get().and_then(Option::as_deref)
+}
+
+/// `connect_async`, tunnelled through the configured proxy with HTTP CONNECT when there is one
+pub async fn connect_websocket(url: &str) -> Result<(WebSocketStream<MaybeTlsStream<TcpStream>>, Response)> {
+    let Some(proxy) = proxy_url() else {
+        return Ok(connect_async(url).await?);
+    };
+
+    let target: Uri = url.parse().with_context(|| format!("Invalid WebSocket URL '{url}'"))?;
+    let target_host = target.host().ok_or_else(|| anyhow!("WebSocket URL '{url}' has no host"))?;
+    let target_port = target.port_u16().unwrap_or(if target.scheme_str() == Some("ws") { 80 } else { 443 });
+
+    let stream = open_tunnel(proxy, target_host, target_port).await?;
+    Ok(client_async_tls(url, stream).await?)
+}
+
+async fn open_tunnel(proxy: &str, target_host: &str, target_port: u16) -> Result<TcpStream> {
+    let proxy_uri: Uri = proxy.parse().with_context(|| format!("Invalid proxy URL '{proxy}'"))?;
+    if proxy_uri.scheme_str().is_some_and(|scheme| scheme != "http") {
+        bail!("Only http:// proxies are supported for WebSockets, got '{proxy}'");
+    }
+    let authority = proxy_uri.authority().ok_or_else(|| anyhow!("Proxy URL '{proxy}' has no host"))?;
+    let proxy_port = authority.port_u16().unwrap_or(80);
+
+    debug!("Tunnelling {target_host}:{target_port} through proxy {}:{proxy_port}", authority.host());
+    let mut stream = TcpStream::connect((authority.host(), proxy_port)).await
+        .with_context(|| format!("Failed to connect to proxy {}:{proxy_port}", authority.host()))?;
+
+    let mut request = format!("CONNECT {target_host}:{target_port} HTTP/1.1\r\nHost: {target_host}:{target_port}\r\n");
+    if let Some((credentials, _)) = authority.as_str().rsplit_once('@') {
+        request.push_str(&format!("Proxy-Authorization: Basic {}\r\n", base64_encode(credentials.as_bytes())));
+    }
+    request.push_str("\r\n");
+    stream.write_all(request.as_bytes()).await?;
+
+    // Read the proxy's reply headers byte by byte so nothing of the TLS handshake is consumed
+    let mut response = Vec::new();
+    while !response.ends_with(b"\r\n\r\n") {
+        if response.len() > 8192 {
+            bail!("Proxy response headers too long");
+        }
+        let byte = stream.read_u8().await.context("Proxy closed the connection during CONNECT")?;
+        response.push(byte);
+    }
+
+    let status_line = String::from_utf8_lossy(&response).lines().next().unwrap_or_default().to_string();
+    if status_line.split_whitespace().nth(1) != Some("200") {
+        bail!("Proxy refused CONNECT to {target_host}:{target_port}: {status_line}");
+    }
+    Ok(stream)
+}
+
+fn base64_encode(input: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(input.len().div_ceil(3) * 4);
+    for chunk in input.chunks(3) {
+        let bytes = [chunk[0], chunk.get(1).copied().unwrap_or(0), chunk.get(2).copied().unwrap_or(0)];
+        let triple = (bytes[0] as u32) << 16 | (bytes[1] as u32) << 8 | bytes[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(triple >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
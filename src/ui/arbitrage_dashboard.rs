@@ -0,0 +1,66 @@
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Row, Table},
+    Frame,
+};
+
+use crate::app::App;
+
+pub fn render_arbitrage_dashboard(f: &mut Frame, app: &App, area: Rect) {
+    let header_cells = ["Event", "Markets", "Bid sum", "Ask sum", "Edge"]
+        .iter()
+        .map(|h| Cell::from(*h).style(Style::default().add_modifier(Modifier::BOLD)));
+    let header = Row::new(header_cells).height(1).bottom_margin(1);
+
+    let format_sum = |sum: Option<f64>| sum.map_or_else(|| "-".to_string(), |sum| format!("{sum:.4}"));
+
+    let rows: Vec<Row> = app.arbitrage_opportunities.iter().map(|opportunity| {
+        // Same threshold the detector uses for a high-severity alert
+        let color = if opportunity.edge > 0.1 { Color::LightRed } else { Color::Yellow };
+        Row::new(vec![
+            Cell::from(opportunity.event_title.clone()),
+            Cell::from(opportunity.markets.to_string()),
+            Cell::from(format_sum(opportunity.bid_sum)),
+            Cell::from(format_sum(opportunity.ask_sum)),
+            Cell::from(format!("${:.4}", opportunity.edge)),
+        ]).style(Style::default().fg(color))
+    }).collect();
+
+    let title = match app.last_arbitrage_scan {
+        None => "Arbitrage Dashboard (scanning...)".to_string(),
+        Some(_) if app.arbitrage_scan.is_some() => format!(
+            "Arbitrage Dashboard - {} of {} events crossed (rescanning...)",
+            app.arbitrage_opportunities.len(),
+            app.arbitrage_events_scanned,
+        ),
+        Some(_) if app.arbitrage_events_scanned == 0 => {
+            "Arbitrage Dashboard - no multi-market events selected in the Arbitrage Detector".to_string()
+        }
+        Some(scanned) => format!(
+            "Arbitrage Dashboard - {} of {} events crossed (scanned {}s ago)",
+            app.arbitrage_opportunities.len(),
+            app.arbitrage_events_scanned,
+            scanned.elapsed().as_secs(),
+        ),
+    };
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Percentage(52),
+            Constraint::Percentage(12),
+            Constraint::Percentage(12),
+            Constraint::Percentage(12),
+            Constraint::Percentage(12),
+        ]
+    )
+        .header(header)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .title_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)))
+        .column_spacing(1);
+
+    f.render_widget(table, area);
+}
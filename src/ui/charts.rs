@@ -1,6 +1,6 @@
 use cli_log::warn;
 use ratatui::{
-    layout::{Alignment, Rect},
+    layout::{Alignment, Constraint, Rect},
     style::{Color, Style},
     text::Span,
     widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph},
@@ -9,73 +9,104 @@ use ratatui::{
 };
 
 use crate::{
-    app::App, 
-    config::CHART_NUM_DATES,
+    app::{market_compare::index_to_100, App, DepthChartMode, NumberFormat, PriceFormat}, 
+    config::{CHART_NUM_DATES, RSI_PERIOD, RSI_OVERBOUGHT, RSI_OVERSOLD, TWAP_WINDOW_SECS},
     data::{CryptoPrice, OrderBookData}, 
+    utils::{aggregate_orders, ohlc_candles, relative_strength_series, twap_series},
     websocket::CryptoSymbol
 };
 
-pub fn render_orderbook_plot(f: &mut Frame, orderbook: &mut OrderBookData, area: Rect) {
-    let bids = &orderbook.bids;
-    let asks = &orderbook.asks;
+/// Cumulative depth curves the depth chart plots, shared with the SVG export
+pub struct DepthChartData {
+    pub bid_data: Vec<(f64, f64)>,
+    pub ask_data: Vec<(f64, f64)>,
+    pub min_price: f64,
+    pub max_price: f64,
+    pub max_depth: f64,
+    pub best_bid: f64,
+    pub best_ask: f64,
+    pub best_bid_level: Option<(f64, f64)>, // (price, size or notional) of the top bid bucket
+    pub best_ask_level: Option<(f64, f64)>,
+}
+
+/// Builds the depth curves around the spread, recentering the chart unless it is locked.
+/// None when the book has nothing to plot.
+pub fn build_depth_chart_data(orderbook: &mut OrderBookData, show_notional: bool, aggregation_ticks: u32, ticks_around_spread: u32, mode: DepthChartMode) -> Option<DepthChartData> {
+    // An unknown (zero) tick would make the tick grid below infinite
+    let tick_size = orderbook.effective_tick_size();
+    let bids = &aggregate_orders(&orderbook.bids, tick_size, aggregation_ticks, false);
+    let asks = &aggregate_orders(&orderbook.asks, tick_size, aggregation_ticks, true);
 
     let best_bid = bids.first().map(|b| b.price).unwrap_or(0.5);
     let best_ask = asks.first().map(|a| a.price).unwrap_or(0.5);
 
-    let ticks_around_spread = 20;
-
-    let (min_tick, max_tick) = if best_bid > 0.0 && best_ask > 0.0 && best_ask > best_bid {
-        let mid_price = (best_bid + best_ask) / 2.0;
-        let mid_tick = (mid_price / orderbook.tick_size).round() as i64;
-        let half_range = ticks_around_spread / 2;
-        let start_tick = (mid_tick - half_range as i64).max(0);
-        let end_tick = mid_tick + half_range as i64;
-        let max_valid_tick = (1.0 / orderbook.tick_size).floor() as i64;
+    let locked_center = if orderbook.chart_locked { orderbook.chart_center_price } else { None };
+
+    // A crossed book still centers on its midpoint; the full-range fallback is only for one-sided books
+    let (min_tick, max_tick) = if locked_center.is_some() || (best_bid > 0.0 && best_ask > 0.0) {
+        // A locked view keeps its pinned center instead of following the mid
+        let mid_price = locked_center.unwrap_or((best_bid + best_ask) / 2.0);
+        let mid_tick = (mid_price / tick_size).round() as i64;
+        let half_range = (ticks_around_spread / 2).max(1) as i64;
+        // Zooming out never extends the window past the 0..1 price range
+        let start_tick = (mid_tick - half_range).max(0);
+        let end_tick = mid_tick + half_range;
+        let max_valid_tick = (1.0 / tick_size).floor() as i64;
         let constrained_end_tick = end_tick.min(max_valid_tick);
-        orderbook.chart_center_price = Some(mid_tick as f64 * orderbook.tick_size);
+        if !orderbook.chart_locked {
+            orderbook.chart_center_price = Some(mid_tick as f64 * tick_size);
+        }
         orderbook.chart_needs_recentering = false;
         (start_tick, constrained_end_tick)
     } else {
         warn!("Invalid orderbook state: best_bid: {best_bid}, best_ask: {best_ask}");
         let all_prices: Vec<f64> = bids.iter().chain(asks.iter()).map(|o| o.price).filter(|&p| (0.0..=1.0).contains(&p)).collect();
-        if all_prices.is_empty() { return; }
+        if all_prices.is_empty() { return None; }
         let min_order_price = all_prices.iter().copied().fold(f64::INFINITY, f64::min);
         let max_order_price = all_prices.iter().copied().fold(f64::NEG_INFINITY, f64::max);
-        let min_tick = ((min_order_price / orderbook.tick_size).floor() as i64 - 10).max(0);
-        let max_valid_tick = (1.0 / orderbook.tick_size).floor() as i64;
-        let max_tick = ((max_order_price / orderbook.tick_size).ceil() as i64 + 10).min(max_valid_tick);
+        let min_tick = ((min_order_price / tick_size).floor() as i64 - 10).max(0);
+        let max_valid_tick = (1.0 / tick_size).floor() as i64;
+        let max_tick = ((max_order_price / tick_size).ceil() as i64 + 10).min(max_valid_tick);
         let mid_tick = (min_tick + max_tick) / 2;
-        orderbook.chart_center_price = Some(mid_tick as f64 * orderbook.tick_size);
+        orderbook.chart_center_price = Some(mid_tick as f64 * tick_size);
         orderbook.chart_needs_recentering = false;
         (min_tick, max_tick)
     };
 
     if max_tick <= min_tick { 
         warn!("Invalid tick range: min_tick: {min_tick}, max_tick: {max_tick}");
-        return; 
+        return None;
     }
 
-    let min_price = min_tick as f64 * orderbook.tick_size;
+    let min_price = min_tick as f64 * tick_size;
     let num_ticks = (max_tick - min_tick) as usize;
     let mut bid_depths = vec![0.0; num_ticks];
     let mut ask_depths = vec![0.0; num_ticks];
 
+    // Levels mode plots each level's own size, where the cumulative sums restart at every level
+    let cumulative = mode == DepthChartMode::Cumulative;
+
     // Build bid depths (cumulative from highest price down)
     let mut cumulative_bid_size = 0.0;
     let mut sorted_bids = bids.to_vec();
     sorted_bids.sort_by(|a, b| b.price.partial_cmp(&a.price).unwrap_or(std::cmp::Ordering::Equal));
     for bid in sorted_bids.iter() {
         if !(0.0..=1.0).contains(&bid.price) { continue; }
-        cumulative_bid_size += bid.size;
-        let tick_index = ((bid.price - min_price) / orderbook.tick_size).round() as usize;
+        if !cumulative {
+            cumulative_bid_size = 0.0;
+        }
+        cumulative_bid_size += if show_notional { bid.price * bid.size } else { bid.size };
+        let tick_index = ((bid.price - min_price) / tick_size).round() as usize;
         if tick_index < num_ticks {
             bid_depths[tick_index] = cumulative_bid_size;
         }
     }
     // Fill gaps in bid depths (propagate cumulative sizes down)
-    for i in (0..num_ticks - 1).rev() {
-        if bid_depths[i] < bid_depths[i + 1] {
-            bid_depths[i] = bid_depths[i + 1];
+    if cumulative {
+        for i in (0..num_ticks - 1).rev() {
+            if bid_depths[i] < bid_depths[i + 1] {
+                bid_depths[i] = bid_depths[i + 1];
+            }
         }
     }
 
@@ -85,62 +116,115 @@ pub fn render_orderbook_plot(f: &mut Frame, orderbook: &mut OrderBookData, area:
     sorted_asks.sort_by(|a, b| a.price.partial_cmp(&b.price).unwrap_or(std::cmp::Ordering::Equal));
     for ask in sorted_asks.iter() {
         if !(0.0..=1.0).contains(&ask.price) { continue; }
-        cumulative_ask_size += ask.size;
-        let tick_index = ((ask.price - min_price) / orderbook.tick_size).round() as usize;
+        if !cumulative {
+            cumulative_ask_size = 0.0;
+        }
+        cumulative_ask_size += if show_notional { ask.price * ask.size } else { ask.size };
+        let tick_index = ((ask.price - min_price) / tick_size).round() as usize;
         if tick_index < num_ticks {
             ask_depths[tick_index] = cumulative_ask_size;
         }
     }
     // Fill gaps in ask depths (propagate cumulative sizes up)
-    for i in 1..num_ticks {
-        if ask_depths[i] < ask_depths[i - 1] {
-            ask_depths[i] = ask_depths[i - 1];
+    if cumulative {
+        for i in 1..num_ticks {
+            if ask_depths[i] < ask_depths[i - 1] {
+                ask_depths[i] = ask_depths[i - 1];
+            }
         }
     }
 
     // Create block-style data points
-    let half_tick = orderbook.tick_size / 2.0;
-    
-    let mut bid_data: Vec<(f64, f64)> = Vec::new();
-    for (i, &depth) in bid_depths.iter().enumerate() {
-        let price = (min_tick as f64 + i as f64) * orderbook.tick_size;
-        // Only include bid data points at or below the best bid
-        if depth > 0.0 && price <= best_bid {
-            // For bids: go from tick center to left edge (bid width extends left)
-            bid_data.push((price - half_tick, depth));
-            bid_data.push((price, depth));
+    let half_tick = tick_size / 2.0;
+    let (bid_data, ask_data) = if cumulative {
+        let mut bid_data: Vec<(f64, f64)> = Vec::new();
+        for (i, &depth) in bid_depths.iter().enumerate() {
+            let price = (min_tick as f64 + i as f64) * tick_size;
+            // Only include bid data points at or below the best bid
+            if depth > 0.0 && price <= best_bid {
+                // For bids: go from tick center to left edge (bid width extends left)
+                bid_data.push((price - half_tick, depth));
+                bid_data.push((price, depth));
+            }
+        }
+        // Add line from best bid to 0 at the spread
+        if !bid_data.is_empty() {
+            bid_data.push((best_bid, bid_data.last().unwrap().1));
+            bid_data.push((best_bid, 0.0));
         }
-    }
-    // Add line from best bid to 0 at the spread
-    if !bid_data.is_empty() {
-        bid_data.push((best_bid, bid_data.last().unwrap().1));
-        bid_data.push((best_bid, 0.0));
-    }
 
-    let mut ask_data: Vec<(f64, f64)> = Vec::new();
-    for (i, &depth) in ask_depths.iter().enumerate() {
-        let price = (min_tick as f64 + i as f64) * orderbook.tick_size;
-        // Only include ask data points at or above the best ask
-        if depth > 0.0 && price >= best_ask {
-            // For asks: go from tick center to right edge (ask width extends right)
-            ask_data.push((price, depth));
-            ask_data.push((price + half_tick, depth));
+        let mut ask_data: Vec<(f64, f64)> = Vec::new();
+        for (i, &depth) in ask_depths.iter().enumerate() {
+            let price = (min_tick as f64 + i as f64) * tick_size;
+            // Only include ask data points at or above the best ask
+            if depth > 0.0 && price >= best_ask {
+                // For asks: go from tick center to right edge (ask width extends right)
+                ask_data.push((price, depth));
+                ask_data.push((price + half_tick, depth));
+            }
         }
-    }
-    // Add line from best ask to 0 at the spread
-    if !ask_data.is_empty() {
-        ask_data.insert(0, (best_ask, 0.0));
-        ask_data.insert(1, (best_ask, ask_data[2].1));
-    }
+        // Add line from best ask to 0 at the spread
+        if !ask_data.is_empty() {
+            ask_data.insert(0, (best_ask, 0.0));
+            ask_data.insert(1, (best_ask, ask_data[2].1));
+        }
+        (bid_data, ask_data)
+    } else {
+        // One bar per level, dropping to zero on both sides so nothing is drawn between levels
+        let bars = |depths: &[f64], keep: &dyn Fn(f64) -> bool| -> Vec<(f64, f64)> {
+            depths.iter().enumerate()
+                .map(|(i, &depth)| ((min_tick as f64 + i as f64) * tick_size, depth))
+                .filter(|&(price, depth)| depth > 0.0 && keep(price))
+                .flat_map(|(price, depth)| [
+                    (price - half_tick, 0.0), (price - half_tick, depth), (price + half_tick, depth), (price + half_tick, 0.0),
+                ])
+                .collect()
+        };
+        (bars(&bid_depths, &|price| price <= best_bid), bars(&ask_depths, &|price| price >= best_ask))
+    };
 
-    if bid_data.is_empty() && ask_data.is_empty() { return; }
+    if bid_data.is_empty() && ask_data.is_empty() { return None; }
 
     let max_depth = bid_data.iter().chain(ask_data.iter())
         .map(|(_, depth)| *depth)
         .max_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
         .unwrap_or(1.0);
 
-    if max_depth <= 0.0 { return; }
+    if max_depth <= 0.0 { return None; }
+
+    let level_depth = |order: &crate::data::SimpleOrder| if show_notional { order.price * order.size } else { order.size };
+    Some(DepthChartData {
+        bid_data,
+        ask_data,
+        min_price: min_tick as f64 * tick_size,
+        max_price: max_tick as f64 * tick_size,
+        max_depth,
+        best_bid,
+        best_ask,
+        best_bid_level: bids.first().map(|bid| (bid.price, level_depth(bid))),
+        best_ask_level: asks.first().map(|ask| (ask.price, level_depth(ask))),
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn render_orderbook_plot(f: &mut Frame, orderbook: &mut OrderBookData, price_format: PriceFormat, number_format: NumberFormat, show_notional: bool, aggregation_ticks: u32, ticks_around_spread: u32, mode: DepthChartMode, area: Rect) {
+    let Some(DepthChartData {
+        bid_data, ask_data, min_price: min_price_display, max_price: max_price_display,
+        max_depth, best_bid, best_ask, best_bid_level, best_ask_level,
+    }) = build_depth_chart_data(orderbook, show_notional, aggregation_ticks, ticks_around_spread, mode) else {
+        return;
+    };
+
+    let decimal_places = orderbook.price_decimal_places();
+
+    // Vertical markers at the touch, labeled in the legend with price and size there
+    let in_view = |price: f64| (min_price_display..=max_price_display).contains(&price);
+    let marker = |side: &str, (price, depth): (f64, f64)| (
+        format!("{side} {} · {}", price_format.format(price, decimal_places), number_format.format(depth, 0)),
+        [(price, 0.0), (price, max_depth)],
+    );
+    let bid_marker = best_bid_level.filter(|(price, _)| in_view(*price)).map(|level| marker("Bid", level));
+    let ask_marker = best_ask_level.filter(|(price, _)| in_view(*price)).map(|level| marker("Ask", level));
 
     let mut datasets = Vec::new();
     
@@ -162,39 +246,87 @@ pub fn render_orderbook_plot(f: &mut Frame, orderbook: &mut OrderBookData, area:
             .data(&ask_data));
     }
 
-    let min_price_display = min_tick as f64 * orderbook.tick_size;
-    let max_price_display = max_tick as f64 * orderbook.tick_size;
-
-    let decimal_places = if orderbook.tick_size >= 1.0 { 0 } else { (-orderbook.tick_size.log10().floor() as usize).min(6) };
+    for ((label, line), color) in [(&bid_marker, Color::LightGreen), (&ask_marker, Color::LightRed)]
+        .into_iter()
+        .filter_map(|(marker, color)| marker.as_ref().map(|marker| (marker, color)))
+    {
+        datasets.push(Dataset::default()
+            .name(label.as_str())
+            .marker(symbols::Marker::Dot)
+            .style(Style::default().fg(color))
+            .graph_type(GraphType::Line)
+            .data(line));
+    }
 
     let chart = Chart::new(datasets)
-        .block(Block::default().title(format!("Orderbook Depth - Spread: {:.4}", best_ask - best_bid)).borders(Borders::ALL))
+        .block(Block::default().title(format!(
+            "Orderbook Depth - Spread: {} [{}] [±{} ticks]{}{}",
+            price_format.format(best_ask - best_bid, 4),
+            if orderbook.chart_locked { "Locked" } else { "Auto" },
+            ticks_around_spread / 2,
+            if aggregation_ticks > 1 { format!(" [Bin: {aggregation_ticks} ticks]") } else { String::new() },
+            if mode == DepthChartMode::Levels { " [Levels]" } else { "" },
+        )).borders(Borders::ALL))
+        // Narrow charts drop the legend rather than letting it cover the curves and axis labels
+        .hidden_legend_constraints((Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)))
         .x_axis(
             Axis::default()
                 .title("Price")
                 .style(Style::default().fg(Color::Gray))
                 .bounds([min_price_display, max_price_display])
                 .labels(vec![
-                    Span::from(format!("{min_price_display:.decimal_places$}")),
-                    Span::from(format!("{:.decimal_places$}", (min_price_display + max_price_display) / 2.0, decimal_places = decimal_places)),
-                    Span::from(format!("{max_price_display:.decimal_places$}")),
+                    Span::from(price_format.format(min_price_display, decimal_places)),
+                    Span::from(price_format.format((min_price_display + max_price_display) / 2.0, decimal_places)),
+                    Span::from(price_format.format(max_price_display, decimal_places)),
                 ]),
         )
         .y_axis(
             Axis::default()
-                .title("Depth")
+                .title(if show_notional { "Depth ($)" } else { "Depth (shares)" })
                 .style(Style::default().fg(Color::Gray))
                 .bounds([0.0, max_depth])
                 .labels(vec![
                     Span::from("0"),
-                    Span::from(format!("{:.0}", max_depth / 2.0)),
-                    Span::from(format!("{max_depth:.0}")),
+                    Span::from(number_format.format(max_depth / 2.0, 0)),
+                    Span::from(number_format.format(max_depth, 0)),
                 ]),
         );
     f.render_widget(chart, area);
 }
 
-pub fn render_price_history_chart(f: &mut Frame, orderbook: &OrderBookData, area: Rect) {
+/// The full probability range when pinned, so both price charts read as absolute odds; the data's range otherwise
+fn price_axis_bounds(min_price: f64, max_price: f64, pinned: bool) -> (f64, f64) {
+    if pinned { (0.0, 1.0) } else { (min_price, max_price) }
+}
+
+/// A candle as two vertical segments at the middle of its interval
+struct CandleSegments {
+    start: i64,
+    wick: [(f64, f64); 2], // Low to high
+    body: [(f64, f64); 2], // Open to close
+    color: Color,
+}
+
+/// The newest candles that fit `columns`, oldest first
+fn candle_segments(orderbook: &OrderBookData, interval_secs: u64, columns: usize) -> Vec<CandleSegments> {
+    let candles = ohlc_candles(&orderbook.price_history.points, interval_secs as i64);
+    let half = interval_secs as f64 / 2.0;
+    candles[candles.len().saturating_sub(columns)..].iter()
+        .map(|candle| {
+            let t = candle.start as f64 + half;
+            CandleSegments {
+                start: candle.start,
+                wick: [(t, candle.low), (t, candle.high)],
+                body: [(t, candle.open), (t, candle.close)],
+                color: if candle.close >= candle.open { Color::Green } else { Color::Red },
+            }
+        })
+        .collect()
+}
+
+/// With `candle_interval_secs` the midpoint line is replaced by OHLC candles of that width
+#[allow(clippy::too_many_arguments)]
+pub fn render_price_history_chart(f: &mut Frame, orderbook: &OrderBookData, price_format: PriceFormat, sibling_label: Option<&str>, show_twap: bool, pin_price_axis: bool, candle_interval_secs: Option<u64>, area: Rect) {
     let price_points: Vec<(f64, f64)> = orderbook.price_history.points.iter()
         .map(|p| (p.timestamp.timestamp() as f64, p.price))
         .collect();
@@ -206,20 +338,114 @@ pub fn render_price_history_chart(f: &mut Frame, orderbook: &OrderBookData, area
         return;
     }
 
-    let (min_time, max_time) = orderbook.price_history.get_time_range().unwrap();
-    let (min_price, max_price) = orderbook.price_history.get_price_range().unwrap();
+    let (mut min_time, max_time) = orderbook.price_history.get_time_range().unwrap();
+    let (mut min_price, mut max_price) = orderbook.price_history.get_price_range().unwrap();
 
-    let datasets = vec![Dataset::default()
-        .name("Price")
-        .marker(symbols::Marker::Dot)
-        .style(Style::default().fg(Color::Cyan))
-        .graph_type(GraphType::Line)
-        .data(&price_points)];
+    // One column per candle; the y axis labels take about ten of the inner width
+    let candles = candle_interval_secs
+        .map(|interval_secs| candle_segments(orderbook, interval_secs, (area.width as usize).saturating_sub(12)))
+        .unwrap_or_default();
+    if let Some(start) = candles.first().and_then(|candle| chrono::DateTime::from_timestamp(candle.start, 0)) {
+        min_time = min_time.max(start);
+    }
+
+    // Implied sibling price for binary markets; the two lines should mirror around 0.5
+    let sibling_points: Vec<(f64, f64)> = if sibling_label.is_some() {
+        price_points.iter().map(|(t, p)| (*t, 1.0 - p)).collect()
+    } else {
+        Vec::new()
+    };
+    for (_, p) in &sibling_points {
+        min_price = min_price.min(*p);
+        max_price = max_price.max(*p);
+    }
+
+    // Session high/low as dashed reference lines, drawn as evenly spaced dots
+    let session_high = price_points.iter().map(|(_, p)| *p).fold(f64::NEG_INFINITY, f64::max);
+    let session_low = price_points.iter().map(|(_, p)| *p).fold(f64::INFINITY, f64::min);
+    let dash_count = 40;
+    let (start, end) = (min_time.timestamp() as f64, max_time.timestamp() as f64);
+    let dash_times: Vec<f64> = (0..=dash_count)
+        .map(|i| start + (end - start) * i as f64 / dash_count as f64)
+        .collect();
+    let high_line: Vec<(f64, f64)> = dash_times.iter().map(|t| (*t, session_high)).collect();
+    let low_line: Vec<(f64, f64)> = dash_times.iter().map(|t| (*t, session_low)).collect();
+    let last_point = [*price_points.last().unwrap()];
+    let (min_price, max_price) = price_axis_bounds(min_price, max_price, pin_price_axis);
+
+    // The TWAP is an average of the same prices, so it never widens the y range
+    let twap_points = if show_twap {
+        twap_series(&orderbook.price_history.points, TWAP_WINDOW_SECS as f64)
+    } else {
+        Vec::new()
+    };
+    let current_twap = twap_points.last().map(|(_, twap)| *twap);
+
+    let mut datasets = vec![
+        Dataset::default()
+            .name(format!("High {}", price_format.format(session_high, 4)))
+            .marker(symbols::Marker::Dot)
+            .style(Style::default().fg(Color::Green))
+            .graph_type(GraphType::Scatter)
+            .data(&high_line),
+        Dataset::default()
+            .name(format!("Low {}", price_format.format(session_low, 4)))
+            .marker(symbols::Marker::Dot)
+            .style(Style::default().fg(Color::Red))
+            .graph_type(GraphType::Scatter)
+            .data(&low_line),
+        Dataset::default()
+            .name("Last")
+            .marker(symbols::Marker::Block)
+            .style(Style::default().fg(Color::Yellow))
+            .graph_type(GraphType::Scatter)
+            .data(&last_point),
+    ];
+    if candles.is_empty() {
+        datasets.insert(2, Dataset::default()
+            .name("Price")
+            .marker(symbols::Marker::Dot)
+            .style(Style::default().fg(Color::Cyan))
+            .graph_type(GraphType::Line)
+            .data(&price_points));
+    }
+    // Unnamed, so the legend doesn't list every candle
+    for candle in &candles {
+        datasets.push(Dataset::default()
+            .marker(symbols::Marker::Braille)
+            .style(Style::default().fg(candle.color))
+            .graph_type(GraphType::Line)
+            .data(&candle.wick));
+        datasets.push(Dataset::default()
+            .marker(symbols::Marker::Block)
+            .style(Style::default().fg(candle.color))
+            .graph_type(GraphType::Line)
+            .data(&candle.body));
+    }
+    if let Some(label) = sibling_label {
+        datasets.push(Dataset::default()
+            .name(format!("Implied {label}"))
+            .marker(symbols::Marker::Dot)
+            .style(Style::default().fg(Color::Magenta))
+            .graph_type(GraphType::Line)
+            .data(&sibling_points));
+    }
+    if current_twap.is_some() {
+        datasets.push(Dataset::default()
+            .name(format!("TWAP {}m", TWAP_WINDOW_SECS / 60))
+            .marker(symbols::Marker::Braille)
+            .style(Style::default().fg(Color::LightYellow))
+            .graph_type(GraphType::Line)
+            .data(&twap_points));
+    }
 
+    let twap_title = current_twap
+        .map(|twap| format!(" | TWAP {}m: {}", TWAP_WINDOW_SECS / 60, price_format.format(twap, 4)))
+        .unwrap_or_default();
     let chart = Chart::new(datasets)
         .block(
             Block::default()
-                .title(format!("Price History - Current: {:.4}", orderbook.price_history.current_price().unwrap_or(orderbook.get_midpoint())))
+                .title(format!("Price History - Current: {}{twap_title}", price_format.format(orderbook.price_history.current_price().unwrap_or(orderbook.get_midpoint()), 4)))
                 .borders(Borders::ALL),
         )
         .x_axis(
@@ -238,9 +464,78 @@ pub fn render_price_history_chart(f: &mut Frame, orderbook: &OrderBookData, area
                 .style(Style::default().fg(Color::Gray))
                 .bounds([min_price, max_price])
                 .labels(vec![
-                    Span::from(format!("{min_price:.4}")),
-                    Span::from(format!("{:.4}", (min_price + max_price) / 2.0)),
-                    Span::from(format!("{max_price:.4}")),
+                    Span::from(price_format.format(min_price, 4)),
+                    Span::from(price_format.format((min_price + max_price) / 2.0, 4)),
+                    Span::from(price_format.format(max_price, 4)),
+                ]),
+        );
+    f.render_widget(chart, area);
+}
+
+pub fn render_rsi_chart(f: &mut Frame, orderbook: &OrderBookData, area: Rect) {
+    let rsi_points = relative_strength_series(&orderbook.price_history.points, RSI_PERIOD);
+
+    if rsi_points.len() < 2 {
+        let no_data = Paragraph::new("Collecting RSI data...").style(Style::default().fg(Color::Gray)).alignment(Alignment::Center);
+        f.render_widget(Block::default().title(format!("RSI ({RSI_PERIOD})")).borders(Borders::ALL), area);
+        f.render_widget(no_data, area);
+        return;
+    }
+
+    let min_time = rsi_points.first().unwrap().0;
+    let max_time = rsi_points.last().unwrap().0;
+    let current = rsi_points.last().unwrap().1;
+
+    // Horizontal overbought/oversold bands across the visible window
+    let overbought = [(min_time, RSI_OVERBOUGHT), (max_time, RSI_OVERBOUGHT)];
+    let oversold = [(min_time, RSI_OVERSOLD), (max_time, RSI_OVERSOLD)];
+
+    let datasets = vec![
+        Dataset::default()
+            .marker(symbols::Marker::Braille)
+            .style(Style::default().fg(Color::Red))
+            .graph_type(GraphType::Line)
+            .data(&overbought),
+        Dataset::default()
+            .marker(symbols::Marker::Braille)
+            .style(Style::default().fg(Color::Green))
+            .graph_type(GraphType::Line)
+            .data(&oversold),
+        Dataset::default()
+            .name("RSI")
+            .marker(symbols::Marker::Braille)
+            .style(Style::default().fg(Color::Magenta))
+            .graph_type(GraphType::Line)
+            .data(&rsi_points),
+    ];
+
+    let title_color = if current >= RSI_OVERBOUGHT {
+        Color::Red
+    } else if current <= RSI_OVERSOLD {
+        Color::Green
+    } else {
+        Color::White
+    };
+
+    let chart = Chart::new(datasets)
+        .block(
+            Block::default()
+                .title(Span::styled(format!("RSI ({RSI_PERIOD}) - Current: {current:.1}"), Style::default().fg(title_color)))
+                .borders(Borders::ALL),
+        )
+        .x_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::Gray))
+                .bounds([min_time, max_time]),
+        )
+        .y_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::Gray))
+                .bounds([0.0, 100.0])
+                .labels(vec![
+                    Span::from("0"),
+                    Span::from("50"),
+                    Span::from("100"),
                 ]),
         );
     f.render_widget(chart, area);
@@ -341,19 +636,32 @@ pub fn render_market_price_history(f: &mut Frame, app: &App, area: Rect) {
             chart_data.push((point.t as f64, point.p));
         }
         
-        if chart_data.is_empty() {
-            let no_data = Paragraph::new("No price history data available")
+        let selection = format!("{} @ {}m", app.price_history_range.interval(), app.price_history_fidelity);
+
+        // Short ranges on quiet markets can come back with fewer points than a line needs
+        if chart_data.len() < 2 {
+            let message = format!(
+                "Only {} price point(s) for {selection} - press t for a wider range or g for a finer fidelity",
+                chart_data.len()
+            );
+            let no_data = Paragraph::new(message)
                 .style(Style::default().fg(Color::Gray))
                 .alignment(Alignment::Center);
-            f.render_widget(Block::default().title("Market Price History").borders(Borders::ALL), area);
+            f.render_widget(Block::default().title(format!("Market Price History [{selection}]")).borders(Borders::ALL), area);
             f.render_widget(no_data, area);
             return;
         }
 
         // Calculate price range
         let prices: Vec<f64> = chart_data.iter().map(|(_, price)| *price).collect();
-        let min_price = prices.iter().fold(f64::INFINITY, |a, &b| a.min(b));
-        let max_price = prices.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
+        let mut min_price = prices.iter().fold(f64::INFINITY, |a, &b| a.min(b));
+        let mut max_price = prices.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
+        if max_price <= min_price {
+            // Flat series, pad so the line is not drawn on the border
+            min_price = (min_price - 0.01).max(0.0);
+            max_price = (max_price + 0.01).min(1.0);
+        }
+        let (min_price, max_price) = price_axis_bounds(min_price, max_price, app.pin_price_axis);
         
         // Calculate time range and convert to dates
         let times: Vec<f64> = chart_data.iter().map(|(time, _)| *time).collect();
@@ -386,7 +694,7 @@ pub fn render_market_price_history(f: &mut Frame, app: &App, area: Rect) {
         let chart = Chart::new(datasets)
             .block(
                 Block::default()
-                    .title(format!("Market Price History - {market_name}"))
+                    .title(format!("Market Price History - {market_name} [{selection}]"))
                     .borders(Borders::ALL),
             )
             .x_axis(
@@ -402,9 +710,9 @@ pub fn render_market_price_history(f: &mut Frame, app: &App, area: Rect) {
                     .style(Style::default().fg(Color::Gray))
                     .bounds([min_price, max_price])
                     .labels(vec![
-                        Span::from(format!("{min_price:.3}")),
-                        Span::from(format!("{:.3}", (min_price + max_price) / 2.0)),
-                        Span::from(format!("{max_price:.3}")),
+                        Span::from(app.price_format.format(min_price, 3)),
+                        Span::from(app.price_format.format((min_price + max_price) / 2.0, 3)),
+                        Span::from(app.price_format.format(max_price, 3)),
                     ]),
             );
         f.render_widget(chart, area);
@@ -416,3 +724,97 @@ pub fn render_market_price_history(f: &mut Frame, app: &App, area: Rect) {
         f.render_widget(no_data, area);
     }
 }
+
+/// Both compared markets on a shared time axis, as prices or indexed to 100 at the start of their overlap
+pub fn render_market_comparison(f: &mut Frame, app: &App, area: Rect) {
+    let selection = format!("{} @ {}m", app.price_history_range.interval(), app.price_history_fidelity);
+    let Some(comparison) = app.market_comparison.as_ref() else {
+        f.render_widget(Block::default().title("Compare Markets").borders(Borders::ALL), area);
+        return;
+    };
+
+    let (first, second) = if app.compare_normalized {
+        (index_to_100(&comparison.first.points), index_to_100(&comparison.second.points))
+    } else {
+        (comparison.first.points.clone(), comparison.second.points.clone())
+    };
+
+    if first.len() < 2 || second.len() < 2 {
+        let message = format!(
+            "No overlapping price history for {selection} - press t for a wider range or g for a finer fidelity"
+        );
+        let no_data = Paragraph::new(message)
+            .style(Style::default().fg(Color::Gray))
+            .alignment(Alignment::Center);
+        f.render_widget(Block::default().title(format!("Compare Markets [{selection}]")).borders(Borders::ALL), area);
+        f.render_widget(no_data, area);
+        return;
+    }
+
+    let all_points = || first.iter().chain(second.iter());
+    let min_time = all_points().map(|(t, _)| *t).fold(f64::INFINITY, f64::min);
+    let max_time = all_points().map(|(t, _)| *t).fold(f64::NEG_INFINITY, f64::max);
+    let mut min_value = all_points().map(|(_, v)| *v).fold(f64::INFINITY, f64::min);
+    let mut max_value = all_points().map(|(_, v)| *v).fold(f64::NEG_INFINITY, f64::max);
+    if max_value <= min_value {
+        // Flat series, pad so the lines are not drawn on the border
+        let pad = if app.compare_normalized { 1.0 } else { 0.01 };
+        min_value -= pad;
+        max_value += pad;
+    }
+
+    let dates: Vec<Span> = (0..CHART_NUM_DATES)
+        .filter_map(|i| {
+            let timestamp = min_time + i as f64 / (CHART_NUM_DATES - 1) as f64 * (max_time - min_time);
+            chrono::DateTime::from_timestamp(timestamp as i64, 0)
+        })
+        .map(|date| Span::from(date.format("%d/%m %H:%M").to_string()))
+        .collect();
+    let format_value = |value: f64| if app.compare_normalized {
+        format!("{value:.1}")
+    } else {
+        app.price_format.format(value, 3)
+    };
+
+    let datasets = vec![
+        Dataset::default()
+            .name(comparison.first.question.clone())
+            .marker(symbols::Marker::Braille)
+            .style(Style::default().fg(Color::Cyan))
+            .graph_type(GraphType::Line)
+            .data(&first),
+        Dataset::default()
+            .name(comparison.second.question.clone())
+            .marker(symbols::Marker::Braille)
+            .style(Style::default().fg(Color::Magenta))
+            .graph_type(GraphType::Line)
+            .data(&second),
+    ];
+
+    let mode = if app.compare_normalized { "indexed to 100" } else { "price" };
+    let chart = Chart::new(datasets)
+        .block(
+            Block::default()
+                .title(format!("Compare Markets - {mode} [{selection}]"))
+                .borders(Borders::ALL),
+        )
+        .x_axis(
+            Axis::default()
+                .title("Time")
+                .style(Style::default().fg(Color::Gray))
+                .bounds([min_time, max_time])
+                .labels(dates),
+        )
+        .y_axis(
+            Axis::default()
+                .title(if app.compare_normalized { "Index" } else { "Price" })
+                .style(Style::default().fg(Color::Gray))
+                .bounds([min_value, max_value])
+                .labels(vec![
+                    Span::from(format_value(min_value)),
+                    Span::from(format_value((min_value + max_value) / 2.0)),
+                    Span::from(format_value(max_value)),
+                ]),
+        );
+    f.render_widget(chart, area);
+}
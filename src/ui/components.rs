@@ -1,12 +1,15 @@
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
-    widgets::{Block, Borders, Paragraph, Tabs},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Tabs, Wrap},
     Frame,
 };
+use chrono::{DateTime, Utc};
+use crossterm::event::KeyCode;
 
-use crate::app::App;
-use crate::data::{OrderBookData};
+use crate::app::{settings::SettingField, App, NumberFormat, PriceFormat};
+use crate::data::{MarketMetadata, OrderBookData, OutcomePair};
 
 pub fn render_tab_bar(f: &mut Frame, app: &App, area: Rect) {
     let tab_titles = vec!["Orderbook", "Price History"];
@@ -45,13 +48,22 @@ pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         .split(popup_layout[1])[1]
 }
 
-pub fn render_combined_market_header(f: &mut Frame, orderbook: &OrderBookData, ws_status: &str, area: Rect) {
+/// One block character per value, scaled between `min` and `max`
+pub fn sparkline(values: impl IntoIterator<Item = f64>, min: f64, max: f64) -> String {
+    const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let range = max - min;
+    values.into_iter()
+        .map(|value| {
+            let scaled = if range > 0.0 { ((value - min) / range).clamp(0.0, 1.0) } else { 0.5 };
+            BLOCKS[(scaled * (BLOCKS.len() - 1) as f64).round() as usize]
+        })
+        .collect()
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn render_combined_market_header(f: &mut Frame, orderbook: &OrderBookData, ws_status: &str, price_format: PriceFormat, number_format: NumberFormat, show_imbalance: bool, depth_bps: f64, area: Rect) {
     // Calculate decimal places based on tick size
-    let decimal_places = if orderbook.tick_size >= 1.0 {
-        0
-    } else {
-        (-orderbook.tick_size.log10().floor() as usize).min(6)
-    };
+    let decimal_places = orderbook.price_decimal_places();
 
     // Truncate market question if too long
     let market_question = if orderbook.market_question.len() > 60 {
@@ -60,18 +72,80 @@ pub fn render_combined_market_header(f: &mut Frame, orderbook: &OrderBookData, w
         orderbook.market_question.clone()
     };
 
+    // A crossed book is flagged rather than showing a negative spread
+    let spread = if orderbook.is_crossed() {
+        "CROSSED (resyncing)".to_string()
+    } else {
+        price_format.format(orderbook.get_spread(), decimal_places)
+    };
+
     // Create a combined info line with market name and key stats
     let combined_info = format!(
-        "{market_question} | Spread: {spread:.decimal_places$} | Tick: {tick_size:.decimal_places$} | Updated: {last_updated} | {ws_status}",
+        "{market_question} | Spread: {spread} | Tick: {tick_size} | Updated: {last_updated} | {ws_status}",
         market_question = market_question,
-        spread = orderbook.get_spread(),
-        tick_size = orderbook.tick_size,
+        tick_size = price_format.format(orderbook.tick_size, decimal_places),
         last_updated = orderbook.last_updated.format("%H:%M:%S UTC"),
-        ws_status = ws_status,
-        decimal_places = decimal_places
+        ws_status = ws_status
     );
     
-    let header = Paragraph::new(combined_info)
+    // Capital sitting in the displayed levels on each side
+    let (bid_notional, ask_notional) = orderbook.get_side_notionals();
+    let book_value = format!(
+        " | Book: ${} bid / ${} ask",
+        number_format.format(bid_notional, 0),
+        number_format.format(ask_notional, 0),
+    );
+    let (bid_depth, ask_depth) = orderbook.depth_within_bps(depth_bps);
+    let near_depth = format!(
+        " | ±{depth_bps:.0}bps: {} bid / {} ask",
+        number_format.format(bid_depth, 0),
+        number_format.format(ask_depth, 0),
+    );
+
+    // Midpoint change since the token was first loaded
+    let change_span = match orderbook.get_midpoint_change() {
+        Some((change, change_pct)) => Span::styled(
+            format!(
+                " | Since open: {sign}{change} ({change_pct:+.2}%)",
+                sign = if change >= 0.0 { "+" } else { "" },
+                change = price_format.format(change, decimal_places),
+            ),
+            Style::default().fg(if change >= 0.0 { Color::Green } else { Color::Red }),
+        ),
+        None => Span::raw(" | Since open: -"),
+    };
+    
+    // How busy the tape is over the activity window, unlike the static total volume
+    let activity = match orderbook.trade_activity {
+        Some(activity) => format!(
+            " | Tape: {:.1} trades/min, ${}/min",
+            activity.trades_per_min,
+            number_format.format(activity.notional_per_min, 0),
+        ),
+        None => " | Tape: -".to_string(),
+    };
+
+    let mut spans = vec![Span::raw(combined_info), Span::raw(book_value), Span::raw(near_depth), change_span, Span::raw(activity)];
+    // Buy/sell pressure over the last samples: tall blocks lean to the bid, short ones to the ask
+    if let (true, Some(&(_, latest))) = (show_imbalance, orderbook.imbalance_history.back()) {
+        let history = sparkline(orderbook.imbalance_history.iter().map(|&(_, imbalance)| imbalance), -1.0, 1.0);
+        spans.push(Span::styled(
+            format!(" | Imbalance: {history} {latest:+.2}"),
+            Style::default().fg(if latest >= 0.0 { Color::Green } else { Color::Red }),
+        ));
+    }
+    if let Some(trade) = orderbook.flashing_large_trade() {
+        let (side, color) = if trade.is_buy { ("BUY", Color::Green) } else { ("SELL", Color::Red) };
+        spans.push(Span::styled(
+            format!(" | BIG {side} {} @ {}", number_format.format(trade.size, 0), price_format.format(trade.price, decimal_places)),
+            Style::default().bg(color).fg(Color::Black).add_modifier(Modifier::BOLD),
+        ));
+    }
+    if orderbook.snapshot_jump_flagged() {
+        spans.push(Span::styled(" | SNAPSHOT JUMP", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)));
+    }
+
+    let header = Paragraph::new(Line::from(spans))
         .style(Style::default().fg(Color::Green))
         .alignment(Alignment::Center)
         .block(Block::default()
@@ -80,3 +154,329 @@ pub fn render_combined_market_header(f: &mut Frame, orderbook: &OrderBookData, w
     
     f.render_widget(header, area);
 }
+
+/// Two-row header with both outcomes of a binary market and their implied sums
+pub fn render_outcome_pair_header(f: &mut Frame, orderbook: &OrderBookData, pair: &OutcomePair, price_format: PriceFormat, area: Rect) {
+    let decimal_places = orderbook.price_decimal_places();
+
+    let primary_bid = orderbook.bids.first().map(|o| o.price).unwrap_or(0.0);
+    let primary_ask = orderbook.asks.first().map(|o| o.price).unwrap_or(0.0);
+    let sibling = &pair.sibling;
+    let fmt = |price: f64| if price > 0.0 { price_format.format(price, decimal_places) } else { "-".to_string() };
+
+    let quotes_line = Line::from(vec![
+        Span::styled(format!("{}: ", pair.primary_outcome), Style::default().add_modifier(Modifier::BOLD)),
+        Span::styled(format!("Bid {}", fmt(primary_bid)), Style::default().fg(Color::Green)),
+        Span::raw(" / "),
+        Span::styled(format!("Ask {}", fmt(primary_ask)), Style::default().fg(Color::Red)),
+        Span::raw("    "),
+        Span::styled(format!("{}: ", sibling.label), Style::default().add_modifier(Modifier::BOLD)),
+        Span::styled(format!("Bid {}", fmt(sibling.best_bid)), Style::default().fg(Color::Green)),
+        Span::raw(" / "),
+        Span::styled(format!("Ask {}", fmt(sibling.best_ask)), Style::default().fg(Color::Red)),
+    ]);
+
+    // Selling both outcomes above 1.0 or buying both below 1.0 is a mispricing
+    let sum_span = |label: &str, a: f64, b: f64, mispriced: fn(f64) -> bool| {
+        if a > 0.0 && b > 0.0 {
+            let sum = a + b;
+            let color = if mispriced(sum) { Color::Red } else { Color::Gray };
+            Span::styled(format!("{label}: {}", price_format.format(sum, decimal_places)), Style::default().fg(color))
+        } else {
+            Span::styled(format!("{label}: -"), Style::default().fg(Color::DarkGray))
+        }
+    };
+    let sums_line = Line::from(vec![
+        sum_span("Bid sum", primary_bid, sibling.best_bid, |sum| sum > 1.0),
+        Span::raw(" | "),
+        sum_span("Ask sum", primary_ask, sibling.best_ask, |sum| sum < 1.0),
+    ]);
+
+    let header = Paragraph::new(vec![quotes_line, sums_line])
+        .alignment(Alignment::Center)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .title("Outcome Pair"));
+
+    f.render_widget(header, area);
+}
+
+/// Overlay with the descriptive fields of the loaded market; absent fields show as "-"
+pub fn render_market_info_panel(f: &mut Frame, metadata: Option<&MarketMetadata>, area: Rect) {
+    f.render_widget(Clear, area);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Market Details (i/Esc: close)")
+        .title_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+
+    let Some(metadata) = metadata else {
+        let empty = Paragraph::new("No details available for this token")
+            .style(Style::default().fg(Color::Gray))
+            .alignment(Alignment::Center)
+            .block(block);
+        f.render_widget(empty, area);
+        return;
+    };
+
+    let field = |label: &str, value: Option<&str>| Line::from(vec![
+        Span::styled(format!("{label:<12}"), Style::default().fg(Color::Gray)),
+        match value {
+            Some(value) => Span::raw(value.to_string()),
+            None => Span::styled("-", Style::default().fg(Color::DarkGray)),
+        },
+    ]);
+
+    // Show the end date in UTC with the time remaining when it parses, else as gamma sent it
+    let end_date = metadata.end_date.as_deref().map(|raw| match DateTime::parse_from_rfc3339(raw) {
+        Ok(date) => {
+            let date = date.with_timezone(&Utc);
+            let remaining = date.signed_duration_since(Utc::now());
+            let relative = if remaining.num_days() > 0 {
+                format!("in {}d", remaining.num_days())
+            } else if remaining.num_hours() > 0 {
+                format!("in {}h", remaining.num_hours())
+            } else if remaining.num_seconds() > 0 {
+                "within the hour".to_string()
+            } else {
+                "passed".to_string()
+            };
+            format!("{} ({relative})", date.format("%Y-%m-%d %H:%M UTC"))
+        }
+        Err(_) => raw.to_string(),
+    });
+
+    let lines = vec![
+        Line::from(Span::styled(metadata.question.clone(), Style::default().add_modifier(Modifier::BOLD))),
+        Line::from(""),
+        field("Outcome", metadata.outcome.as_deref()),
+        field("Event", metadata.event_title.as_deref()),
+        field("Category", metadata.category.as_deref()),
+        field("Ends", end_date.as_deref()),
+        field("Resolution", metadata.resolution_source.as_deref()),
+        Line::from(""),
+        Line::from(Span::styled("Description", Style::default().fg(Color::Gray))),
+        Line::from(metadata.description.clone().unwrap_or_else(|| "-".to_string())),
+    ];
+
+    let panel = Paragraph::new(lines)
+        .wrap(Wrap { trim: true })
+        .block(block);
+    f.render_widget(panel, area);
+}
+
+/// Editable list of view settings; the selected row's value is changed in place
+pub fn render_settings_panel(f: &mut Frame, app: &App, area: Rect) {
+    f.render_widget(Clear, area);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Settings (↑↓: select | ◄►/Enter: change | R: reset to defaults | ,/Esc: save and close)")
+        .title_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+
+    let settings = app.current_settings();
+    let lines: Vec<Line> = SettingField::all()
+        .iter()
+        .enumerate()
+        .map(|(idx, field)| {
+            let selected = idx == app.selected_setting;
+            let row_style = if selected {
+                Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            Line::from(vec![
+                Span::styled(if selected { "> " } else { "  " }, row_style.fg(Color::Yellow)),
+                Span::styled(format!("{:<20}", field.label()), row_style.fg(Color::Gray)),
+                Span::styled(field.value(&settings), row_style.fg(Color::White)),
+            ])
+        })
+        .collect();
+
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Text entry for a single number, checked against its bounds when submitted
+#[derive(Debug, Clone, PartialEq)]
+pub struct NumericInput {
+    pub label: String,
+    pub text: String,
+    pub min: f64,
+    pub max: f64,
+    pub decimals: usize, // Digits allowed after the point; 0 accepts whole numbers only
+    pub error: Option<String>, // Why the last Enter was refused
+    pub prefilled: bool, // `text` is still the starting value, which the first typed character replaces
+}
+
+/// What a key did to a `NumericInput`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NumericInputEvent {
+    Editing,
+    Submitted(f64),
+    Cancelled,
+}
+
+impl NumericInput {
+    /// Starts from `value`, so Enter alone keeps it and typing replaces it
+    pub fn new(label: &str, value: f64, min: f64, max: f64, decimals: usize) -> Self {
+        let mut text = format!("{value:.decimals$}");
+        if text.contains('.') {
+            text = text.trim_end_matches('0').trim_end_matches('.').to_string();
+        }
+        Self { label: label.to_string(), text, min, max, decimals, error: None, prefilled: true }
+    }
+
+    /// Digits, one decimal point and a leading minus when `min` allows it; anything else is ignored
+    pub fn handle_key(&mut self, key_code: KeyCode) -> NumericInputEvent {
+        match key_code {
+            KeyCode::Esc => return NumericInputEvent::Cancelled,
+            KeyCode::Enter => match self.parse() {
+                Ok(value) => return NumericInputEvent::Submitted(value),
+                Err(e) => self.error = Some(e),
+            },
+            KeyCode::Backspace => {
+                self.text.pop();
+                self.prefilled = false;
+                self.error = None;
+            }
+            KeyCode::Char(ch) if self.accepts(ch) => {
+                if std::mem::take(&mut self.prefilled) {
+                    self.text.clear();
+                }
+                self.text.push(ch);
+                self.error = None;
+            }
+            _ => {}
+        }
+        NumericInputEvent::Editing
+    }
+
+    /// Judged against the text the character would be added to, which is empty while the value is prefilled
+    fn accepts(&self, ch: char) -> bool {
+        let text = if self.prefilled { "" } else { self.text.as_str() };
+        match ch {
+            '-' => text.is_empty() && self.min < 0.0,
+            '.' => self.decimals > 0 && !text.contains('.'),
+            '0'..='9' => text.split_once('.').is_none_or(|(_, fraction)| fraction.len() < self.decimals),
+            _ => false,
+        }
+    }
+
+    pub fn parse(&self) -> Result<f64, String> {
+        let value: f64 = self.text.parse().map_err(|_| "Enter a number".to_string())?;
+        if value < self.min || value > self.max {
+            return Err(format!("Must be between {} and {}", self.min, self.max));
+        }
+        Ok(value)
+    }
+}
+
+/// Small overlay with the label, the typed text and the validation error, if any
+pub fn render_numeric_input(f: &mut Frame, input: &NumericInput, area: Rect) {
+    f.render_widget(Clear, area);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!("{} (Enter: apply | Esc: cancel)", input.label))
+        .title_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+
+    // Shown selected while typing would replace it
+    let text_style = if input.prefilled {
+        Style::default().fg(Color::Black).bg(Color::White)
+    } else {
+        Style::default().fg(Color::White).add_modifier(Modifier::BOLD)
+    };
+    let mut lines = vec![Line::from(vec![
+        Span::styled(input.text.clone(), text_style),
+        Span::styled("_", Style::default().fg(Color::Yellow)),
+    ])];
+    lines.push(match &input.error {
+        Some(error) => Line::from(Span::styled(error.clone(), Style::default().fg(Color::Red))),
+        None => Line::from(Span::styled(format!("{} to {}", input.min, input.max), Style::default().fg(Color::DarkGray))),
+    });
+
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_keys(input: &mut NumericInput, keys: &str) {
+        for ch in keys.chars() {
+            assert_eq!(input.handle_key(KeyCode::Char(ch)), NumericInputEvent::Editing);
+        }
+    }
+
+    #[test]
+    fn typing_replaces_the_prefilled_value() {
+        let mut input = NumericInput::new("Depth", 50.0, 1.0, 10_000.0, 1);
+        assert_eq!(input.text, "50");
+        type_keys(&mut input, "25");
+        assert_eq!(input.text, "25");
+    }
+
+    #[test]
+    fn enter_alone_keeps_the_prefilled_value() {
+        let mut input = NumericInput::new("Depth", 12.5, 1.0, 10_000.0, 1);
+        assert_eq!(input.handle_key(KeyCode::Enter), NumericInputEvent::Submitted(12.5));
+    }
+
+    #[test]
+    fn backspace_edits_the_prefilled_value() {
+        let mut input = NumericInput::new("Depth", 150.0, 1.0, 10_000.0, 0);
+        input.handle_key(KeyCode::Backspace);
+        assert_eq!(input.text, "15");
+        type_keys(&mut input, "5");
+        assert_eq!(input.handle_key(KeyCode::Enter), NumericInputEvent::Submitted(155.0));
+    }
+
+    #[test]
+    fn digits_stop_at_the_decimals_limit() {
+        let mut input = NumericInput::new("Price", 0.0, 0.0, 1.0, 2);
+        type_keys(&mut input, "0.1234");
+        assert_eq!(input.text, "0.12");
+
+        let mut whole = NumericInput::new("Depth", 0.0, 0.0, 100.0, 0);
+        type_keys(&mut whole, "4.5");
+        assert_eq!(whole.text, "45");
+    }
+
+    #[test]
+    fn minus_needs_a_negative_minimum_and_an_empty_text() {
+        let mut positive = NumericInput::new("Depth", 5.0, 1.0, 100.0, 0);
+        type_keys(&mut positive, "-3");
+        assert_eq!(positive.text, "3");
+
+        let mut signed = NumericInput::new("Offset", 5.0, -10.0, 10.0, 0);
+        type_keys(&mut signed, "-3-");
+        assert_eq!(signed.text, "-3");
+        assert_eq!(signed.handle_key(KeyCode::Enter), NumericInputEvent::Submitted(-3.0));
+    }
+
+    #[test]
+    fn values_outside_the_bounds_are_refused() {
+        let mut input = NumericInput::new("Depth", 50.0, 1.0, 100.0, 0);
+        type_keys(&mut input, "0");
+        assert_eq!(input.handle_key(KeyCode::Enter), NumericInputEvent::Editing);
+        assert_eq!(input.error.as_deref(), Some("Must be between 1 and 100"));
+
+        input.handle_key(KeyCode::Backspace);
+        assert!(input.error.is_none());
+        type_keys(&mut input, "101");
+        assert_eq!(input.handle_key(KeyCode::Enter), NumericInputEvent::Editing);
+        assert!(input.error.is_some());
+    }
+
+    #[test]
+    fn an_empty_text_is_not_a_number() {
+        let mut input = NumericInput::new("Depth", 5.0, 1.0, 100.0, 0);
+        input.handle_key(KeyCode::Backspace);
+        assert_eq!(input.handle_key(KeyCode::Enter), NumericInputEvent::Editing);
+        assert_eq!(input.error.as_deref(), Some("Enter a number"));
+    }
+
+    #[test]
+    fn esc_cancels() {
+        let mut input = NumericInput::new("Depth", 5.0, 1.0, 100.0, 0);
+        type_keys(&mut input, "7");
+        assert_eq!(input.handle_key(KeyCode::Esc), NumericInputEvent::Cancelled);
+    }
+}
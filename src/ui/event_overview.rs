@@ -0,0 +1,71 @@
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Row, Table},
+    Frame,
+};
+
+use crate::app::App;
+
+pub fn render_event_overview(f: &mut Frame, app: &App, area: Rect) {
+    let event_title = app.filtered_events.get(app.selected_event)
+        .map(|&idx| app.events[idx].title.clone())
+        .unwrap_or_default();
+
+    let yes_sum = app.event_overview_yes_sum();
+    // Sum of YES midpoints below 1.0 means the outcomes are underpriced as a set
+    let arbitrage = yes_sum.is_some_and(|sum| sum < 1.0);
+    let row_style = if arbitrage {
+        Style::default().fg(Color::Red)
+    } else {
+        Style::default()
+    };
+
+    let header_cells = ["Market", "Bid", "Ask", "Mid"]
+        .iter()
+        .map(|h| Cell::from(*h).style(Style::default().add_modifier(Modifier::BOLD)));
+    let header = Row::new(header_cells).height(1).bottom_margin(1);
+
+    let format_price = |price: f64| if price > 0.0 { format!("{price:.4}") } else { "-".to_string() };
+
+    let mut rows: Vec<Row> = app.event_overview.iter().map(|quote| {
+        Row::new(vec![
+            Cell::from(quote.label.clone()),
+            Cell::from(format_price(quote.best_bid)),
+            Cell::from(format_price(quote.best_ask)),
+            Cell::from(format_price(quote.get_midpoint())),
+        ]).style(row_style)
+    }).collect();
+
+    let sum_text = yes_sum.map_or_else(|| "-".to_string(), |sum| format!("{sum:.4}"));
+    rows.push(Row::new(vec![
+        Cell::from("Sum of YES midpoints"),
+        Cell::from(""),
+        Cell::from(""),
+        Cell::from(sum_text),
+    ]).style(row_style.add_modifier(Modifier::BOLD)).top_margin(1));
+
+    let title = if app.event_overview.is_empty() {
+        format!("Event Overview - {event_title} (loading...)")
+    } else {
+        format!("Event Overview - {event_title} ({} markets)", app.event_overview.len())
+    };
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Percentage(64),
+            Constraint::Percentage(12),
+            Constraint::Percentage(12),
+            Constraint::Percentage(12),
+        ]
+    )
+        .header(header)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .title_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)))
+        .column_spacing(1);
+
+    f.render_widget(table, area);
+}
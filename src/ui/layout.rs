@@ -1,23 +1,55 @@
 use ratatui::{
-    layout::{Alignment, Constraint, Direction, Layout},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     widgets::{Block, Borders, Clear, Paragraph, Wrap},
     Frame,
 };
 
-use crate::app::App;
-use super::{selectors::{render_market_selector, render_token_selector, render_event_market_selector, render_event_token_selector}, orderbook::render_orderbook, charts::render_market_price_history, components::{render_tab_bar, centered_rect}};
+use crate::app::{App, MarketSelectorTab};
+use crate::config::ARBITRAGE_SCAN_INTERVAL_SECS;
+use super::{selectors::{render_market_selector, render_token_selector, render_event_market_selector, render_event_token_selector, render_quick_search}, orderbook::render_orderbook, charts::{render_market_price_history, render_market_comparison}, components::{render_tab_bar, centered_rect, render_market_info_panel, render_settings_panel, render_numeric_input}, event_overview::render_event_overview, arbitrage_dashboard::render_arbitrage_dashboard};
+
+// Every top-level view is a margin around header, content and footer rows
+const LAYOUT_MARGIN: u16 = 1;
+const HEADER_HEIGHT: u16 = 3;
+const FOOTER_HEIGHT: u16 = 3;
+const MIN_CONTENT_HEIGHT: u16 = 10;
+const MIN_CONTENT_WIDTH: u16 = 60; // Two side-by-side order tables plus the charts stay legible down to this
+pub const MIN_TERMINAL_HEIGHT: u16 = 2 * LAYOUT_MARGIN + HEADER_HEIGHT + MIN_CONTENT_HEIGHT + FOOTER_HEIGHT;
+pub const MIN_TERMINAL_WIDTH: u16 = 2 * LAYOUT_MARGIN + MIN_CONTENT_WIDTH;
+
+/// The normal layouts over-constrain below this size
+pub fn terminal_too_small(area: Rect) -> bool {
+    area.width < MIN_TERMINAL_WIDTH || area.height < MIN_TERMINAL_HEIGHT
+}
+
+fn render_size_hint(f: &mut Frame, area: Rect) {
+    let message = Paragraph::new(format!(
+        "Terminal too small (need {MIN_TERMINAL_WIDTH}x{MIN_TERMINAL_HEIGHT}, have {}x{})",
+        area.width, area.height
+    ))
+        .style(Style::default().fg(Color::Yellow))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    f.render_widget(message, area);
+}
 
 pub fn render_ui(f: &mut Frame, app: &mut App) {
+    let area = f.area();
+    if terminal_too_small(area) {
+        render_size_hint(f, area);
+        return;
+    }
+
     if app.show_strategy_runner {
         // Strategy runner view - full screen
         let chunks = Layout::default()
             .direction(Direction::Vertical)
-            .margin(1)
+            .margin(LAYOUT_MARGIN)
             .constraints([
-                Constraint::Length(3),
-                Constraint::Min(10),
-                Constraint::Length(3),
+                Constraint::Length(HEADER_HEIGHT),
+                Constraint::Min(MIN_CONTENT_HEIGHT),
+                Constraint::Length(FOOTER_HEIGHT),
             ])
             .split(f.area());
 
@@ -45,7 +77,7 @@ pub fn render_ui(f: &mut Frame, app: &mut App) {
                     crate::bot::StrategyStatus::Error(_) => "ERROR",
                 })
                 .unwrap_or("UNKNOWN");
-            format!("Status: {} | [S] Start/Stop | [P] Pick Markets/Events | [Backspace] Back | [Q] Quit", strategy_status)
+            format!("Status: {} | [S] Start/Stop | [P] Pick Markets/Events | [↑↓] Select | [D/Del] Remove | [Backspace] Back | [Q] Quit", strategy_status)
         } else {
             "No strategy selected | [Backspace] Back to Strategy Selector | [Q] Quit".to_string()
         };
@@ -58,11 +90,11 @@ pub fn render_ui(f: &mut Frame, app: &mut App) {
         // Strategy selector view
         let chunks = Layout::default()
             .direction(Direction::Vertical)
-            .margin(1)
+            .margin(LAYOUT_MARGIN)
             .constraints([
-                Constraint::Length(3),
-                Constraint::Min(10),
-                Constraint::Length(3),
+                Constraint::Length(HEADER_HEIGHT),
+                Constraint::Min(MIN_CONTENT_HEIGHT),
+                Constraint::Length(FOOTER_HEIGHT),
             ])
             .split(f.area());
 
@@ -77,20 +109,21 @@ pub fn render_ui(f: &mut Frame, app: &mut App) {
         crate::ui::strategies::render_strategy_selector(f, app, chunks[1]);
 
         // Footer
-        let footer = Paragraph::new("↑↓: Navigate | Enter: Select | Backspace: Back | q: Quit")
+        let footer = Paragraph::new("↑↓: Navigate | Enter: Select | e: Enable/Disable | Backspace: Back | q: Quit")
             .style(Style::default().fg(Color::White))
             .alignment(Alignment::Center)
             .block(Block::default().borders(Borders::ALL));
         f.render_widget(footer, chunks[2]);
-    } else if app.show_market_selector || app.show_event_market_selector || app.show_token_selector {
+    } else if app.show_market_selector || app.show_event_market_selector || app.show_token_selector || app.show_event_overview || app.show_arbitrage_dashboard
+        || app.show_market_compare {
         // Show header when in selectors
         let chunks = Layout::default()
             .direction(Direction::Vertical)
-            .margin(1)
+            .margin(LAYOUT_MARGIN)
             .constraints([
-                Constraint::Length(3),
-                Constraint::Min(10),
-                Constraint::Length(3),
+                Constraint::Length(HEADER_HEIGHT),
+                Constraint::Min(MIN_CONTENT_HEIGHT),
+                Constraint::Length(FOOTER_HEIGHT),
             ])
             .split(f.area());
 
@@ -102,7 +135,13 @@ pub fn render_ui(f: &mut Frame, app: &mut App) {
         f.render_widget(header, chunks[0]);
 
         // Main content
-        if app.show_market_selector {
+        if app.show_event_overview {
+            render_event_overview(f, app, chunks[1]);
+        } else if app.show_arbitrage_dashboard {
+            render_arbitrage_dashboard(f, app, chunks[1]);
+        } else if app.show_market_compare {
+            render_market_comparison(f, app, chunks[1]);
+        } else if app.show_market_selector {
             render_market_selector(f, app, chunks[1]);
         } else if app.show_event_market_selector {
             render_event_market_selector(f, app, chunks[1]);
@@ -116,14 +155,32 @@ pub fn render_ui(f: &mut Frame, app: &mut App) {
         }
 
         // Footer
-        let footer_text = if app.show_market_selector {
-            if app.search_mode {
+        let footer_text = if app.show_event_overview {
+            "r: Refresh | Backspace: Back to Markets | q: Quit".to_string()
+        } else if app.show_arbitrage_dashboard {
+            format!("Rescans every {ARBITRAGE_SCAN_INTERVAL_SECS}s | r: Rescan now | Backspace: Back to Markets | q: Quit")
+        } else if app.show_market_compare {
+            format!("C: Indexed to 100 ({}) | t: Range | g: Fidelity | r: Refresh | Backspace: Back to Markets | q: Quit",
+                if app.compare_normalized { "on" } else { "off" })
+        } else if app.show_market_selector {
+            if app.is_unified_search_active() {
+                format!("Search: {} | ↑↓: Navigate | PgUp/PgDn: Fast scroll | Enter: Open event or load market | Esc: Exit search", app.search_query)
+            } else if app.search_mode {
                 format!("Search: {} | ↑↓: Navigate | Tab: Switch tabs | PgUp/PgDn: Fast scroll | Enter: Select | Esc: Exit search | q: Quit", app.search_query)
+            } else if app.market_selector_tab == MarketSelectorTab::AllMarkets {
+                format!("↑↓: Navigate | Tab: Switch tabs | PgUp/PgDn: Fast scroll | Enter: Select | /: Search | L: Tradable only ({}) | +/-: Min ${} | d: Ending soon ({}) | G: Arbitrage | C: Compare | q: Quit",
+                    if app.liquid_only { "on" } else { "off" }, app.min_liquidity, if app.resolving_soon { "on" } else { "off" })
+            } else if app.market_selector_tab == MarketSelectorTab::Events {
+                "↑↓: Navigate | Tab: Switch tabs | PgUp/PgDn: Fast scroll | Enter: Select | /: Search | G: Arbitrage | q: Quit".to_string()
+            } else if app.market_selector_tab == MarketSelectorTab::Categories && app.show_category_markets {
+                "↑↓: Navigate | Tab: Switch tabs | PgUp/PgDn: Fast scroll | Enter: Select | Backspace: Back to Categories | /: Search | q: Quit".to_string()
+            } else if app.market_selector_tab == MarketSelectorTab::Categories {
+                "↑↓: Navigate | Tab: Switch tabs | PgUp/PgDn: Fast scroll | Enter: Open category | /: Search | q: Quit".to_string()
             } else {
                 "↑↓: Navigate | Tab: Switch tabs | PgUp/PgDn: Fast scroll | Enter: Select | /: Search | q: Quit".to_string()
             }
         } else if app.show_event_market_selector {
-            "↑↓: Navigate | Enter: Select | Backspace: Back to Events | q: Quit".to_string()
+            "↑↓: Navigate | Enter: Select | o: Event Overview | Backspace: Back to Events | q: Quit".to_string()
         } else {
             "↑↓: Navigate | PgUp/PgDn: Fast scroll | Enter: Select | Backspace: Back | q: Quit".to_string()
         };
@@ -137,11 +194,11 @@ pub fn render_ui(f: &mut Frame, app: &mut App) {
         // Orderbook view with tabs - use full area with tabs, content, and footer
         let chunks = Layout::default()
             .direction(Direction::Vertical)
-            .margin(1)
+            .margin(LAYOUT_MARGIN)
             .constraints([
-                Constraint::Length(3), // Tab bar
-                Constraint::Min(10),   // Main content
-                Constraint::Length(3), // Footer
+                Constraint::Length(HEADER_HEIGHT),     // Tab bar
+                Constraint::Min(MIN_CONTENT_HEIGHT),   // Main content
+                Constraint::Length(FOOTER_HEIGHT),     // Footer
             ])
             .split(f.area());
 
@@ -158,11 +215,38 @@ pub fn render_ui(f: &mut Frame, app: &mut App) {
             }
         }
 
-        let footer = Paragraph::new("◄►/hl: Switch tabs | m: Market Selector | r: Refresh | q: Quit")
+        let footer_text = if app.quick_search_mode {
+            format!("Quick switch: {} | ↑↓: Navigate | Enter: Load market | Esc: Cancel", app.search_query)
+        } else if app.selected_tab == crate::app::SelectedTab::PriceHistory {
+            format!("◄►/hl: Switch tabs | t: Range ({}) | g: Fidelity ({}m) | f: Format ({}) | P: Axis ({}) | m: Market Selector | q: Quit",
+                    app.price_history_range.interval(), app.price_history_fidelity, app.price_format.name(),
+                    if app.pin_price_axis { "0..1" } else { "auto" })
+        } else {
+            format!("◄►/hl: Switch tabs | m: Market Selector | /: Quick switch | f: Format ({}) | n: Shares/$ | x: Bin ({}t) | y: Copy ID | i: Info | b: Both outcomes | v: Overlay | 1/2/3: Charts | w: TWAP | K: Candles | P: Pin axis | z/Z/u: Freeze/diff/clear | A: Age | I: Imbalance | B: Depth window | [/]: Zoom depth | V: Depth mode | c: Lock depth | ,: Settings | r: Refresh | W: Reconnect | U: User channel | q: Quit", app.price_format.name(), app.aggregation_ticks)
+        };
+        let footer = Paragraph::new(footer_text)
             .style(Style::default().fg(Color::White))
             .alignment(Alignment::Center)
             .block(Block::default().borders(Borders::ALL));
         f.render_widget(footer, chunks[2]);
+
+        if app.show_market_info {
+            let metadata = app.orderbook.as_ref().and_then(|orderbook| orderbook.metadata.as_ref());
+            render_market_info_panel(f, metadata, centered_rect(70, 60, f.area()));
+        }
+
+        // Quick-switch search overlay
+        if app.quick_search_mode {
+            render_quick_search(f, app, centered_rect(70, 60, f.area()));
+        }
+    }
+
+    if app.show_settings {
+        render_settings_panel(f, app, centered_rect(60, 50, f.area()));
+    }
+
+    if let Some((_, input)) = &app.numeric_input {
+        render_numeric_input(f, input, centered_rect(40, 15, f.area()));
     }
 
     // Error overlay
@@ -194,4 +278,33 @@ pub fn render_ui(f: &mut Frame, app: &mut App) {
                 .style(Style::default().fg(Color::Green)));
         f.render_widget(status_block, area);
     }
+
+    if app.idle_dim && app.is_idle() {
+        let area = f.area();
+        f.buffer_mut().set_style(area, Style::default().add_modifier(Modifier::DIM));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn tiny_terminal_shows_the_size_message() {
+        let mut terminal = Terminal::new(TestBackend::new(10, 3)).unwrap();
+        assert!(terminal_too_small(terminal.get_frame().area()));
+
+        terminal.draw(|f| render_size_hint(f, f.area())).unwrap();
+
+        let text: String = terminal.backend().buffer().content().iter().map(|cell| cell.symbol()).collect();
+        assert!(text.contains("Terminal"));
+    }
+
+    #[test]
+    fn minimum_size_keeps_the_normal_layout() {
+        assert!(!terminal_too_small(Rect::new(0, 0, MIN_TERMINAL_WIDTH, MIN_TERMINAL_HEIGHT)));
+        assert!(terminal_too_small(Rect::new(0, 0, MIN_TERMINAL_WIDTH - 1, MIN_TERMINAL_HEIGHT)));
+        assert!(terminal_too_small(Rect::new(0, 0, MIN_TERMINAL_WIDTH, MIN_TERMINAL_HEIGHT - 1)));
+    }
 }
@@ -5,6 +5,8 @@ pub mod orderbook;
 pub mod charts;
 pub mod components;
 pub mod strategies;
+pub mod event_overview;
+pub mod arbitrage_dashboard;
 
 // Re-export the main UI function
 pub use layout::render_ui;
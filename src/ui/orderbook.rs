@@ -3,57 +3,82 @@
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
-    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, List, ListItem, Paragraph, Row, Table},
     Frame,
 };
 
 use cli_log::*;
 
-use crate::{app::App};
+use crate::app::{freeze_frame::{diff_book_side, LevelChange, LevelDiff}, App, ChartKind, NumberFormat, PriceFormat};
 use crate::data::{ OrderBookData, SimpleOrder, OrderChangeDirection};
-use super::{charts::{render_orderbook_plot, render_price_history_chart, render_crypto_chart_with_data}, components::render_combined_market_header};
-use crate::websocket::CryptoSymbol;
+use super::{charts::{render_orderbook_plot, render_price_history_chart, render_rsi_chart, render_crypto_chart_with_data}, components::{render_combined_market_header, render_outcome_pair_header}};
+use crate::utils::{aggregate_orders, price_decimal_places};
+use crate::config::{LEVEL_FRESH_SECS, LEVEL_RESTING_SECS};
+use std::time::Duration;
+use crate::websocket::{CryptoSymbol, UserOrderMessage, UserTradeMessage};
+
+// Crypto charts share at most this much of the chart column, and only while the market's own charts keep their minimum
+const CRYPTO_CHARTS_MAX_PERCENT: u16 = 40;
+const MIN_CRYPTO_CHART_HEIGHT: u16 = 6;
+const MIN_MARKET_CHART_HEIGHT: u16 = 8; // Price history (with its RSI panel) and depth chart each
+
+/// How many of `wanted` crypto charts fit in a chart column `height` rows tall next to `market_charts`
+/// of the market's own, and the rows each gets. Never hands out a chart with fewer than MIN_CRYPTO_CHART_HEIGHT rows.
+fn crypto_chart_heights(height: u16, wanted: usize, market_charts: u16) -> (usize, u16) {
+    if market_charts == 0 {
+        // Alone in the column, they split all of it
+        let count = wanted.min((height / MIN_CRYPTO_CHART_HEIGHT) as usize);
+        return if count == 0 { (0, 0) } else { (count, height / count as u16) };
+    }
+    let share = (u32::from(height) * u32::from(CRYPTO_CHARTS_MAX_PERCENT) / 100) as u16;
+    let budget = share.min(height.saturating_sub(market_charts * MIN_MARKET_CHART_HEIGHT));
+    let count = wanted.min((budget / MIN_CRYPTO_CHART_HEIGHT) as usize);
+    if count == 0 {
+        return (0, 0);
+    }
+    (count, budget / count as u16)
+}
 
 pub fn render_orderbook(f: &mut Frame, app: &mut App, area: Rect) {
+    let price_format = app.price_format;
+    let number_format = app.number_format;
+    let show_notional = app.show_notional;
+    let show_level_age = app.show_level_age;
+    let aggregation_ticks = app.aggregation_ticks;
     if let Some(ref mut orderbook) = app.orderbook {
+        // The overlay only applies to the token it was enabled for
+        let sibling_label = app.sibling_overlay.as_ref()
+            .filter(|(token_id, _)| *token_id == orderbook.token_id)
+            .map(|(_, outcome)| outcome.as_str());
+        let pair_height = if app.outcome_pair.is_some() { 4 } else { 0 };
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(3),  // Combined market info and statistics header
-                Constraint::Min(0),     // Orderbook and plot
+                Constraint::Length(3),           // Combined market info and statistics header
+                Constraint::Length(pair_height), // Both outcomes of a binary market
+                Constraint::Min(0),              // Orderbook and plot
+                Constraint::Length(3),           // Recent trades ticker tape
             ])
             .split(area);
 
         // Combined market info and statistics header
-        let ws_status = if app.current_websocket.is_some() {
+        let ws_status = if app.websocket_reconnecting {
+            "🟡 Reconnecting"
+        } else if app.websocket_stalled {
+            "🟠 Stalled, polling API"
+        } else if app.current_websocket.is_some() {
             "🟢 Live"
         } else {
             "🔴 API Only"
         };
-        render_combined_market_header(f, orderbook, ws_status, chunks[0]);
-
-        // Main orderbook content with plot
-        let main_chunks = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([
-                Constraint::Percentage(50), // Orderbook tables
-                Constraint::Percentage(50), // Orderbook plot
-            ])
-            .split(chunks[1]);
-
-        // Orderbook tables (left side)
-        let table_chunks = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-            .split(main_chunks[0]);
-
-        // Bids (left) - BUY orders
-        render_order_side(f, &orderbook.bids, "Bids (BUY Orders)", Color::Green, table_chunks[0], orderbook.tick_size);
-        // Asks (right) - SELL orders
-        render_order_side(f, &orderbook.asks, "Asks (SELL Orders)", Color::Red, table_chunks[1], orderbook.tick_size);
+        render_combined_market_header(f, orderbook, ws_status, price_format, number_format, app.show_imbalance_sparkline, app.depth_bps, chunks[0]);
+        render_trade_tape(f, orderbook, price_format, chunks[3]);
+        if let Some(ref pair) = app.outcome_pair {
+            render_outcome_pair_header(f, orderbook, pair, price_format, chunks[1]);
+        }
 
-        // Charts (right side) - split vertically
-        // Check for crypto charts before borrowing orderbook
+        // Charts the market calls for, less the ones hidden with the number keys
         let market_question = orderbook.market_question.clone();
         let question_lower = market_question.to_lowercase();
         
@@ -68,28 +93,77 @@ pub fn render_orderbook(f: &mut Frame, app: &mut App, area: Rect) {
             relevant_cryptos.push(CryptoSymbol::Solana);
         }
         
-        let crypto_count = relevant_cryptos.len();
-        
-        let chart_chunks = if crypto_count > 0 {
-            // Multiple charts: Crypto charts + Price history + Depth chart
-            // Each crypto gets its own chart, then price history and depth chart
-            // All charts are equal height
-            let constraints = vec![Constraint::Percentage((100 / (crypto_count + 2)).try_into().unwrap()); crypto_count + 2];
-            
+        if app.hidden_charts.contains(&ChartKind::Crypto) {
+            relevant_cryptos.clear();
+        }
+        let show_price_chart = !app.hidden_charts.contains(&ChartKind::PriceHistory);
+        let show_depth_chart = !app.hidden_charts.contains(&ChartKind::Depth);
+        let market_charts = u16::from(show_price_chart) + u16::from(show_depth_chart);
+        let (crypto_count, crypto_height) = crypto_chart_heights(chunks[2].height, relevant_cryptos.len(), market_charts);
+        relevant_cryptos.truncate(crypto_count);
+
+        // Main orderbook content with plot; the tables take the full width when every chart is hidden
+        let tables_percent = if crypto_count + usize::from(market_charts) > 0 { 50 } else { 100 };
+        let main_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(tables_percent), // Orderbook tables
+                Constraint::Fill(1), // Charts
+            ])
+            .split(chunks[2]);
+
+        // Own orders and fills go under the tables when the user channel is connected
+        let left_chunks = if app.user_websocket.is_some() {
             Layout::default()
                 .direction(Direction::Vertical)
-                .constraints(constraints)
-                .split(main_chunks[1])
+                .constraints([Constraint::Percentage(70), Constraint::Percentage(30)])
+                .split(main_chunks[0])
         } else {
-            // Two charts: Price history, Depth chart
             Layout::default()
                 .direction(Direction::Vertical)
-                .constraints([
-                    Constraint::Percentage(50), // Price history chart
-                    Constraint::Percentage(50), // Orderbook depth chart
-                ])
-                .split(main_chunks[1])
+                .constraints([Constraint::Percentage(100)])
+                .split(main_chunks[0])
         };
+        if app.user_websocket.is_some() {
+            render_user_activity(f, &app.open_orders, &app.recent_fills, &orderbook.token_id, price_format, left_chunks[1]);
+        }
+
+        // Orderbook tables (left side)
+        let table_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(left_chunks[0]);
+
+        // Coarser price buckets when aggregation is on, raw levels otherwise; an unknown or bad tick falls back to the minimum
+        let tick_size = orderbook.effective_tick_size();
+        let bids = aggregate_orders(&orderbook.bids, tick_size, aggregation_ticks, false);
+        let asks = aggregate_orders(&orderbook.asks, tick_size, aggregation_ticks, true);
+        let frozen = app.freeze_frame.as_ref()
+            .filter(|frozen| app.show_freeze_diff && frozen.token_id == orderbook.token_id);
+        if let Some(frozen) = frozen {
+            // Bucketed the same way as the live tables so the levels line up
+            let frozen_bids = aggregate_orders(&frozen.bids, tick_size, aggregation_ticks, false);
+            let frozen_asks = aggregate_orders(&frozen.asks, tick_size, aggregation_ticks, true);
+            let since = frozen.last_updated.format("%H:%M:%S").to_string();
+            render_order_side_diff(f, &diff_book_side(&bids, &frozen_bids, true, orderbook.price_epsilon()), true, &since, table_chunks[0], tick_size, price_format, number_format);
+            render_order_side_diff(f, &diff_book_side(&asks, &frozen_asks, false, orderbook.price_epsilon()), false, &since, table_chunks[1], tick_size, price_format, number_format);
+        } else {
+            // Without a WebSocket the book is only as fresh as the last REST poll
+            let live = app.current_websocket.is_some();
+            // Bids (left) - BUY orders
+            render_order_side(f, &bids, true, table_chunks[0], tick_size, price_format, number_format, show_notional, show_level_age, live);
+            // Asks (right) - SELL orders
+            render_order_side(f, &asks, false, table_chunks[1], tick_size, price_format, number_format, show_notional, show_level_age, live);
+        }
+
+        // Crypto charts first, then price history and depth chart sharing the rest equally.
+        // Crypto charts that don't fit at a readable height are left out rather than squeezed.
+        let mut constraints = vec![Constraint::Length(crypto_height); crypto_count];
+        constraints.extend(vec![Constraint::Fill(1); usize::from(market_charts)]);
+        let chart_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(constraints)
+            .split(main_chunks[1]);
 
         // Render crypto charts
         for (i, crypto_symbol) in relevant_cryptos.iter().enumerate() {
@@ -101,12 +175,41 @@ pub fn render_orderbook(f: &mut Frame, app: &mut App, area: Rect) {
         }
         
         let price_history_idx = crypto_count;
-        let orderbook_idx = crypto_count + 1;
+        let orderbook_idx = crypto_count + usize::from(show_price_chart);
         
-        // Price history chart
-        render_price_history_chart(f, orderbook, chart_chunks[price_history_idx]);
-        // Orderbook depth chart
-        render_orderbook_plot(f, orderbook, chart_chunks[orderbook_idx]);
+        if show_price_chart {
+            // Price history chart with the RSI panel underneath
+            let price_chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Percentage(70), // Price history chart
+                    Constraint::Percentage(30), // RSI oscillator
+                ])
+                .split(chart_chunks[price_history_idx]);
+            let candle_interval_secs = app.show_candles.then_some(app.candle_interval_secs);
+            render_price_history_chart(f, orderbook, price_format, sibling_label, app.show_twap, app.pin_price_axis, candle_interval_secs, price_chunks[0]);
+            render_rsi_chart(f, orderbook, price_chunks[1]);
+        }
+        if show_depth_chart {
+            render_orderbook_plot(f, orderbook, price_format, number_format, show_notional, aggregation_ticks, app.depth_chart_ticks, app.depth_chart_mode, chart_chunks[orderbook_idx]);
+        }
+
+        // Keep the last-known book visible but grey it out until a fresh snapshot arrives
+        if app.websocket_reconnecting {
+            f.buffer_mut().set_style(chunks[2], Style::default().fg(Color::DarkGray).bg(Color::Reset));
+            let banner_area = Rect {
+                x: chunks[2].x + chunks[2].width.saturating_sub(30) / 2,
+                y: chunks[2].y + chunks[2].height / 2,
+                width: chunks[2].width.min(30),
+                height: chunks[2].height.min(3),
+            };
+            let banner = Paragraph::new("Reconnecting…")
+                .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::ALL));
+            f.render_widget(ratatui::widgets::Clear, banner_area);
+            f.render_widget(banner, banner_area);
+        }
     } else {
         let placeholder = Paragraph::new("Loading orderbook...")
             .style(Style::default().fg(Color::Yellow))
@@ -116,30 +219,122 @@ pub fn render_orderbook(f: &mut Frame, app: &mut App, area: Rect) {
     }
 }
 
+/// Single-line strip of recent trades, newest on the right
+pub fn render_trade_tape(f: &mut Frame, orderbook: &OrderBookData, price_format: PriceFormat, area: Rect) {
+    let decimal_places = orderbook.price_decimal_places();
+
+    // Walk back from the newest trade until the strip is full
+    let available_width = area.width.saturating_sub(2) as usize;
+    let mut used_width = 0;
+    let mut spans = Vec::new();
+    for trade in orderbook.recent_trades.iter().rev() {
+        let text = format!(" {}×{:.0} ", price_format.format(trade.price, decimal_places), trade.size);
+        let text_width = text.chars().count();
+        if used_width + text_width > available_width {
+            break;
+        }
+        used_width += text_width;
+        let color = if trade.is_buy { Color::Green } else { Color::Red };
+        spans.push(Span::styled(text, Style::default().fg(color)));
+    }
+    spans.reverse();
+
+    let tape = if spans.is_empty() {
+        Paragraph::new("Waiting for trades...").style(Style::default().fg(Color::DarkGray))
+    } else {
+        Paragraph::new(Line::from(spans)).alignment(Alignment::Right)
+    };
+    f.render_widget(tape.block(Block::default().borders(Borders::ALL).title("Recent Trades")), area);
+}
+
+pub fn render_user_activity(
+    f: &mut Frame,
+    open_orders: &[UserOrderMessage],
+    recent_fills: &[UserTradeMessage],
+    token_id: &str,
+    price_format: PriceFormat,
+    area: Rect,
+) {
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+
+    let side_color = |side: &str| if side.eq_ignore_ascii_case("buy") { Color::Green } else { Color::Red };
+
+    let order_items: Vec<ListItem> = open_orders
+        .iter()
+        .filter(|o| o.asset_id == token_id)
+        .map(|order| {
+            let price = price_format.format(order.price.parse::<f64>().unwrap_or(0.0), 4);
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{:<4}", order.side), Style::default().fg(side_color(&order.side))),
+                Span::raw(format!(" {} @ {price} ({} filled)", order.original_size, order.size_matched)),
+            ]))
+        })
+        .collect();
+    let order_items = if order_items.is_empty() { vec![ListItem::new("No open orders")] } else { order_items };
+
+    let fill_items: Vec<ListItem> = recent_fills
+        .iter()
+        .filter(|t| t.asset_id == token_id)
+        .map(|trade| {
+            let price = price_format.format(trade.price.parse::<f64>().unwrap_or(0.0), 4);
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{:<4}", trade.side), Style::default().fg(side_color(&trade.side))),
+                Span::raw(format!(" {} @ {price} ", trade.size)),
+                Span::styled(trade.status.clone(), Style::default().fg(Color::Gray)),
+            ]))
+        })
+        .collect();
+    let fill_items = if fill_items.is_empty() { vec![ListItem::new("No recent fills")] } else { fill_items };
+
+    f.render_widget(List::new(order_items).block(Block::default().borders(Borders::ALL).title("My Open Orders")), chunks[0]);
+    f.render_widget(List::new(fill_items).block(Block::default().borders(Borders::ALL).title("My Recent Fills")), chunks[1]);
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn render_order_side(
     f: &mut Frame,
     orders: &[SimpleOrder],
-    title: &str,
-    color: Color,
+    is_bid: bool,
     area: Rect,
     tick_size: f64,
+    price_format: PriceFormat,
+    number_format: NumberFormat,
+    show_notional: bool,
+    show_level_age: bool,
+    live: bool, // Streamed over the WebSocket; polled books are dimmed between refreshes
 ) {
     // Calculate decimal places based on tick size
-    let decimal_places = if tick_size >= 1.0 {
-        0
+    let decimal_places = price_decimal_places(tick_size);
+    
+    let (title, color) = match (is_bid, live) {
+        (true, true) => ("Bids (BUY Orders)", Color::Green),
+        (true, false) => ("Bids (BUY Orders) - polled", Color::Green),
+        (false, true) => ("Asks (SELL Orders)", Color::Red),
+        (false, false) => ("Asks (SELL Orders) - polled", Color::Red),
+    };
+
+    // Notional mode leads with dollars, keeps shares for reference and makes Total cumulative
+    let header_titles: &[&str] = if show_notional {
+        &["Price", "Size ($)", "Shares", "Cum. ($)"]
     } else {
-        (-tick_size.log10().floor() as usize).min(6)
+        &["Price", "Size", "Total"]
     };
-    
-    let header_cells = ["Price", "Size", "Total"]
+    let header_cells = header_titles
         .iter()
+        .chain(show_level_age.then_some(&"Age"))
         .map(|h| Cell::from(*h).style(Style::default().add_modifier(Modifier::BOLD)));
     let header = Row::new(header_cells).height(1).bottom_margin(1);
 
+    let mut cumulative_notional = 0.0;
     let rows = orders.iter().map(|order| {
-        let price = format!("{price:.decimal_places$}", price = order.price, decimal_places = decimal_places);
-        let size = format!("{:>8.2}", order.size); // Right-aligned with width 8
-        let total = format!("{:>8.2}", order.price * order.size); // Right-aligned with width 8
+        let price = price_format.format(order.price, decimal_places);
+        let notional = order.price * order.size;
+        cumulative_notional += notional;
+        let size = format!("{:>8}", number_format.format(order.size, 2)); // Right-aligned with width 8
+        let total = format!("{:>8}", number_format.format(notional, 2)); // Right-aligned with width 8
         
         // Determine highlight style based on change
         let row_style = if order.should_highlight() {
@@ -148,25 +343,101 @@ pub fn render_order_side(
                 OrderChangeDirection::Decrease => Style::default().bg(Color::Red).fg(Color::White),
                 OrderChangeDirection::None => Style::default(),
             }
+        } else if show_level_age {
+            // Fresh quotes stand out, long-resting ones fade
+            let age = order.age();
+            if age < Duration::from_secs(LEVEL_FRESH_SECS) {
+                Style::default().add_modifier(Modifier::BOLD)
+            } else if age >= Duration::from_secs(LEVEL_RESTING_SECS) {
+                Style::default().fg(Color::DarkGray)
+            } else {
+                Style::default()
+            }
         } else {
             Style::default()
         };
+        // Changes stay bright on a dimmed book so they still pop
+        let row_style = if live || order.should_highlight() { row_style } else { row_style.add_modifier(Modifier::DIM) };
         
+        let mut cells = if show_notional {
+            vec![
+                Cell::from(price).style(row_style),
+                Cell::from(total).style(row_style),
+                Cell::from(size).style(row_style),
+                Cell::from(format!("{:>8}", number_format.format(cumulative_notional, 2))).style(row_style),
+            ]
+        } else {
+            vec![
+                Cell::from(price).style(row_style),
+                Cell::from(size).style(row_style),
+                Cell::from(total).style(row_style),
+            ]
+        };
+        if show_level_age {
+            cells.push(Cell::from(format!("{:>5}", format_level_age(order.age()))).style(row_style));
+        }
+        Row::new(cells)
+    });
+
+    let column_count = header_titles.len() + usize::from(show_level_age);
+    let widths = vec![Constraint::Ratio(1, column_count as u32); column_count];
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .title_style(Style::default().fg(color).add_modifier(Modifier::BOLD)))
+        .column_spacing(1);
+
+    f.render_widget(table, area);
+}
+
+/// Live sizes with their net change since the freeze frame; levels that are gone stay listed, struck through
+#[allow(clippy::too_many_arguments)]
+fn render_order_side_diff(
+    f: &mut Frame,
+    diffs: &[LevelDiff],
+    is_bid: bool,
+    since: &str,
+    area: Rect,
+    tick_size: f64,
+    price_format: PriceFormat,
+    number_format: NumberFormat,
+) {
+    let decimal_places = price_decimal_places(tick_size);
+    let (title, color) = if is_bid {
+        (format!("Bids - change since {since}"), Color::Green)
+    } else {
+        (format!("Asks - change since {since}"), Color::Red)
+    };
+
+    let header = Row::new(["Price", "Size", "Change"]
+        .map(|h| Cell::from(h).style(Style::default().add_modifier(Modifier::BOLD))))
+        .height(1)
+        .bottom_margin(1);
+    let rows = diffs.iter().map(|diff| {
+        let change = match diff.change {
+            LevelChange::Appeared => format!("+{} new", number_format.format(diff.size_change, 2)),
+            LevelChange::Gone => format!("{} gone", number_format.format(diff.size_change, 2)),
+            LevelChange::Unchanged => "-".to_string(),
+            LevelChange::Changed if diff.size_change > 0.0 => format!("+{}", number_format.format(diff.size_change, 2)),
+            LevelChange::Changed => number_format.format(diff.size_change, 2),
+        };
+        let style = match diff.change {
+            LevelChange::Appeared => Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
+            LevelChange::Gone => Style::default().fg(Color::DarkGray).add_modifier(Modifier::CROSSED_OUT),
+            LevelChange::Unchanged => Style::default(),
+            LevelChange::Changed if diff.size_change > 0.0 => Style::default().fg(Color::Green),
+            LevelChange::Changed => Style::default().fg(Color::Red),
+        };
         Row::new(vec![
-            Cell::from(price).style(row_style),
-            Cell::from(size).style(row_style),
-            Cell::from(total).style(row_style),
-        ])
+            Cell::from(price_format.format(diff.price, decimal_places)),
+            Cell::from(format!("{:>8}", number_format.format(diff.size, 2))),
+            Cell::from(format!("{change:>8}")),
+        ]).style(style)
     });
 
-    let table = Table::new(
-        rows,
-        [
-            Constraint::Percentage(33),
-            Constraint::Percentage(33),
-            Constraint::Percentage(34),
-        ]
-    )
+    let table = Table::new(rows, [Constraint::Ratio(1, 3); 3])
         .header(header)
         .block(Block::default()
             .borders(Borders::ALL)
@@ -179,11 +450,7 @@ pub fn render_order_side(
 
 pub fn render_market_stats(f: &mut Frame, orderbook: &OrderBookData, ws_status: &str, area: Rect) {
     // Calculate decimal places based on tick size
-    let decimal_places = if orderbook.tick_size >= 1.0 {
-        0
-    } else {
-        (-orderbook.tick_size.log10().floor() as usize).min(6)
-    };
+    let decimal_places = orderbook.price_decimal_places();
 
     
     let stats_lines = [
@@ -205,3 +472,39 @@ pub fn render_market_stats(f: &mut Frame, orderbook: &OrderBookData, ws_status:
     
     f.render_widget(stats_para, area);
 }
+
+/// Compact resting time for the order tables, e.g. "8s", "3m", "2h"
+fn format_level_age(age: Duration) -> String {
+    let secs = age.as_secs();
+    if secs < 60 {
+        format!("{secs}s")
+    } else if secs < 3600 {
+        format!("{}m", secs / 60)
+    } else {
+        format!("{}h", secs / 3600)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn three_crypto_charts_leave_room_for_the_market_charts() {
+        for height in [30u16, 45, 60] {
+            let (count, crypto_height) = crypto_chart_heights(height, 3, 2);
+
+            assert!((1..=3).contains(&count), "height {height}: {count} charts");
+            assert!(crypto_height >= MIN_CRYPTO_CHART_HEIGHT, "height {height}: {crypto_height} rows");
+            let left = height - count as u16 * crypto_height;
+            assert!(left >= 2 * MIN_MARKET_CHART_HEIGHT, "height {height}: {left} rows for price and depth");
+        }
+        assert_eq!(crypto_chart_heights(45, 3, 2), (3, 6));
+    }
+
+    #[test]
+    fn crypto_charts_that_do_not_fit_are_dropped() {
+        assert_eq!(crypto_chart_heights(20, 3, 2), (0, 0));
+        assert_eq!(crypto_chart_heights(12, 3, 0), (2, 6));
+    }
+}
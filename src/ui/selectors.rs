@@ -2,12 +2,14 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph, Tabs},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Tabs},
     Frame,
 };
 use cli_log::warn;
 
-use crate::app::{App, MarketSelectorTab};
+use crate::app::{App, MarketSelectorTab, resolving_soon::{format_ends_in, resolving_soon_end}, search::SearchResult};
+use crate::config::RESOLVING_SOON_HOURS;
+use crate::utils::truncate_chars;
 
 pub fn render_market_selector(f: &mut Frame, app: &App, area: Rect) {
     // Split area for tabs and content
@@ -20,11 +22,12 @@ pub fn render_market_selector(f: &mut Frame, app: &App, area: Rect) {
         .split(area);
 
     // Render tabs
-    let tab_titles = vec!["All Markets", "Events", "Strategies"];
+    let tab_titles = vec!["All Markets", "Events", "Categories", "Strategies"];
     let selected_tab_index = match app.market_selector_tab {
         MarketSelectorTab::AllMarkets => 0,
         MarketSelectorTab::Events => 1,
-        MarketSelectorTab::Strategies => 2,
+        MarketSelectorTab::Categories => 2,
+        MarketSelectorTab::Strategies => 3,
     };
     
     let tabs = Tabs::new(tab_titles)
@@ -36,33 +39,129 @@ pub fn render_market_selector(f: &mut Frame, app: &App, area: Rect) {
     f.render_widget(tabs, chunks[0]);
     
     // Render content based on selected tab
+    if app.is_unified_search_active() {
+        render_unified_search_results(f, app, chunks[1]);
+        return;
+    }
     match app.market_selector_tab {
         MarketSelectorTab::AllMarkets => render_all_markets_list(f, app, chunks[1]),
         MarketSelectorTab::Events => render_events_list(f, app, chunks[1]),
+        MarketSelectorTab::Categories => render_categories_list(f, app, chunks[1]),
         MarketSelectorTab::Strategies => crate::ui::strategies::render_strategy_selector(f, app, chunks[1]),
     }
 }
 
+pub fn render_quick_search(f: &mut Frame, app: &App, area: Rect) {
+    f.render_widget(Clear, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Search input
+            Constraint::Min(0),    // Matching markets
+        ])
+        .split(area);
+
+    let input = Paragraph::new(format!("/{}", app.search_query))
+        .style(Style::default().fg(Color::Yellow))
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .title("Quick Switch")
+            .title_style(Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)));
+    f.render_widget(input, chunks[0]);
+
+    render_all_markets_list(f, app, chunks[1]);
+}
+
+/// First visible row: the preferred offset when it keeps the selection on screen, else the nearest one that does
+fn list_scroll_offset(selected: usize, preferred: usize, visible_height: usize) -> usize {
+    if selected < preferred {
+        selected
+    } else if selected >= preferred + visible_height {
+        (selected + 1).saturating_sub(visible_height)
+    } else {
+        preferred
+    }
+}
+
+/// Marks the row holding the market whose orderbook is loaded
+fn loaded_marker(is_loaded: bool) -> Span<'static> {
+    if is_loaded {
+        Span::styled("● ", Style::default().fg(Color::Cyan))
+    } else {
+        Span::raw("  ")
+    }
+}
+
+/// Events and markets matching the query in one ranked list, tagged by kind
+fn render_unified_search_results(f: &mut Frame, app: &App, area: Rect) {
+    let visible_height = area.height.saturating_sub(3) as usize; // Account for borders and title
+    let title = format!(
+        "Search: '{}' - {} events, {} markets",
+        app.search_query, app.filtered_events.len(), app.filtered_markets.len(),
+    );
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .title_style(Style::default().fg(Color::Green).add_modifier(Modifier::BOLD));
+
+    if app.unified_results.is_empty() {
+        f.render_widget(List::new(vec![ListItem::new("No events or markets found")]).block(block), area);
+        return;
+    }
+
+    let scroll_offset = list_scroll_offset(app.selected_unified_result, app.unified_scroll_offset, visible_height);
+    let items: Vec<ListItem> = app.unified_results.iter()
+        .enumerate()
+        .skip(scroll_offset)
+        .take(visible_height)
+        .map(|(idx, result)| {
+            let style = if idx == app.selected_unified_result {
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            let (tag, tag_color, text) = match *result {
+                SearchResult::Event(position) => {
+                    let event = &app.events[app.filtered_events[position]];
+                    let market_count = event.markets.as_ref().map_or(0, |markets| markets.len());
+                    ("EVENT  ", Color::Magenta, format!("{} ({market_count} markets)", event.title))
+                }
+                SearchResult::Market(position) => {
+                    ("MARKET ", Color::Cyan, app.markets[app.filtered_markets[position]].question.clone())
+                }
+            };
+            ListItem::new(Line::from(vec![Span::styled(tag, Style::default().fg(tag_color)), Span::styled(text, style)]))
+        })
+        .collect();
+
+    f.render_widget(List::new(items).block(block), area);
+}
+
 fn render_all_markets_list(f: &mut Frame, app: &App, area: Rect) {
     // Calculate visible area for scrolling
     let visible_height = area.height.saturating_sub(3) as usize; // Account for borders and title
     let total_items = app.filtered_markets.len();
     
     if total_items == 0 {
+        let mut title = if app.liquid_only {
+            format!("Markets (0 shown) - {} below ${} hidden", app.hidden_illiquid_markets, app.min_liquidity)
+        } else {
+            "Markets (0 total)".to_string()
+        };
+        if app.resolving_soon {
+            title.push_str(&format!(" - none ending within {RESOLVING_SOON_HOURS}h"));
+        }
         let empty_list = List::new(vec![ListItem::new("No markets found")])
             .block(Block::default()
                 .borders(Borders::ALL)
-                .title("Markets (0 total)"));
+                .title(title));
         f.render_widget(empty_list, area);
         return;
     }
     
-    // Calculate scroll offset to keep selected item visible
-    let scroll_offset = if app.selected_market >= visible_height {
-        app.selected_market - visible_height + 1
-    } else {
-        0
-    };
+    let scroll_offset = list_scroll_offset(app.selected_market, app.market_scroll_offset, visible_height);
+    let loaded_token_id = app.orderbook.as_ref().map(|orderbook| orderbook.token_id.as_str());
     
     let visible_start = scroll_offset;
     let visible_end = std::cmp::min(visible_start + visible_height, total_items);
@@ -84,17 +183,30 @@ fn render_all_markets_list(f: &mut Frame, app: &App, area: Rect) {
             Style::default()
         };
         
-        // Cache the formatted string to avoid repeated allocations
-        let text = market.question.clone();
-        items.push(ListItem::new(Line::from(vec![Span::styled(text, style)])));
+        let ends_in = resolving_soon_end(market).map(|end| format!("  {}", format_ends_in(end)));
+        // Borders and the loaded marker take four columns, the resolving-soon suffix what it needs
+        let row_width = (area.width as usize).saturating_sub(4 + ends_in.as_ref().map_or(0, |suffix| suffix.chars().count()));
+        let text = truncate_chars(&app.market_row_format.render(market, app.known_spread(market), app.number_format), row_width);
+        let is_loaded = market.token_ids.iter().any(|t| Some(t.as_str()) == loaded_token_id);
+        let mut spans = vec![loaded_marker(is_loaded), Span::styled(text, style)];
+        if let Some(ends_in) = ends_in {
+            spans.push(Span::styled(ends_in, Style::default().fg(Color::Magenta)));
+        }
+        items.push(ListItem::new(Line::from(spans)));
     }
 
     // Cache the title string
-    let title = if app.search_mode {
+    let mut title = if app.search_mode {
         format!("Markets - Search: '{}' ({}/{})", app.search_query, app.filtered_markets.len(), app.markets.len())
     } else {
         format!("Markets ({} total)", app.markets.len())
     };
+    if app.liquid_only {
+        title.push_str(&format!(" - {} below ${} hidden", app.hidden_illiquid_markets, app.min_liquidity));
+    }
+    if app.resolving_soon {
+        title.push_str(&format!(" - ending within {RESOLVING_SOON_HOURS}h, soonest first"));
+    }
 
     let list = List::new(items)
         .block(Block::default()
@@ -145,12 +257,8 @@ fn render_events_list(f: &mut Frame, app: &App, area: Rect) {
         return;
     }
     
-    // Calculate scroll offset to keep selected item visible
-    let scroll_offset = if app.selected_event >= visible_height {
-        app.selected_event - visible_height + 1
-    } else {
-        0
-    };
+    let scroll_offset = list_scroll_offset(app.selected_event, app.event_scroll_offset, visible_height);
+    let loaded_token_id = app.orderbook.as_ref().map(|orderbook| orderbook.token_id.as_str());
     
     let visible_start = scroll_offset;
     let visible_end = std::cmp::min(visible_start + visible_height, total_items);
@@ -175,7 +283,10 @@ fn render_events_list(f: &mut Frame, app: &App, area: Rect) {
         // Show event title and number of markets
         let market_count = event.markets.as_ref().map(|m| m.len()).unwrap_or(0);
         let text = format!("{} ({} markets)", event.title, market_count);
-        items.push(ListItem::new(Line::from(vec![Span::styled(text, style)])));
+        let is_loaded = event.markets.as_ref().is_some_and(|markets| {
+            markets.iter().any(|m| m.token_ids.iter().any(|t| Some(t.as_str()) == loaded_token_id))
+        });
+        items.push(ListItem::new(Line::from(vec![loaded_marker(is_loaded), Span::styled(text, style)])));
     }
 
     // Cache the title string
@@ -220,6 +331,62 @@ fn render_events_list(f: &mut Frame, app: &App, area: Rect) {
     }
 }
 
+/// Category names with their market counts, or the markets of the open category
+fn render_categories_list(f: &mut Frame, app: &App, area: Rect) {
+    let visible_height = area.height.saturating_sub(3) as usize; // Account for borders and title
+    let loaded_token_id = app.orderbook.as_ref().map(|orderbook| orderbook.token_id.as_str());
+    let selected_style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+
+    let (title, selected, rows): (String, usize, Vec<(bool, String)>) = match app.selected_category_name() {
+        Some(name) if app.show_category_markets => {
+            let markets = app.category_markets();
+            let rows = markets.iter().map(|&market_idx| {
+                let market = &app.markets[market_idx];
+                let is_loaded = market.token_ids.iter().any(|t| Some(t.as_str()) == loaded_token_id);
+                let text = app.market_row_format.render(market, app.known_spread(market), app.number_format);
+                (is_loaded, truncate_chars(&text, (area.width as usize).saturating_sub(4)))
+            }).collect();
+            (format!("{name} ({} markets)", markets.len()), app.selected_category_market, rows)
+        }
+        _ => {
+            let rows = app.category_names.iter().map(|name| {
+                let count = app.market_categories.get(name).map_or(0, Vec::len);
+                (false, format!("{name} ({count} markets)"))
+            }).collect();
+            (format!("Categories ({} total)", app.category_names.len()), app.selected_category, rows)
+        }
+    };
+
+    if rows.is_empty() {
+        let empty_list = List::new(vec![ListItem::new("No markets found")])
+            .block(Block::default().borders(Borders::ALL).title(title));
+        f.render_widget(empty_list, area);
+        return;
+    }
+
+    let scroll_offset = list_scroll_offset(selected, 0, visible_height);
+    let items: Vec<ListItem> = rows.into_iter()
+        .enumerate()
+        .skip(scroll_offset)
+        .take(visible_height)
+        .map(|(i, (is_loaded, text))| {
+            let style = if i == selected { selected_style } else { Style::default() };
+            ListItem::new(Line::from(vec![loaded_marker(is_loaded), Span::styled(text, style)]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .title_style(if app.search_mode {
+                Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            }));
+    f.render_widget(list, area);
+}
+
 pub fn render_token_selector(f: &mut Frame, app: &App, area: Rect) {
     if app.filtered_markets.is_empty() || app.selected_market >= app.filtered_markets.len() {
         warn!("No market selected or filtered markets are empty");
@@ -248,8 +415,16 @@ pub fn render_token_selector(f: &mut Frame, app: &App, area: Rect) {
             Style::default()
         };
         
+        let price = match market.token_ids.get(i).and_then(|token_id| app.outcome_price(token_id)) {
+            Some(Some(price)) => app.price_format.format(price, 3),
+            Some(None) => "-".to_string(), // Empty book
+            None => "…".to_string(), // Not fetched yet
+        };
         let token_text = format!("► {outcome}");
-        items.push(ListItem::new(Line::from(vec![Span::styled(token_text, style)])));
+        items.push(ListItem::new(Line::from(vec![
+            Span::styled(token_text, style),
+            Span::styled(format!("  {price}"), Style::default().fg(Color::Gray)),
+        ])));
     }
 
     // Format title for binary market
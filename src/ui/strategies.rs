@@ -2,7 +2,7 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem},
+    widgets::{Block, Borders, List, ListItem, ListState},
     Frame,
 };
 
@@ -23,9 +23,17 @@ pub fn render_strategy_selector(f: &mut Frame, app: &App, area: Rect) {
                 Style::default()
             };
 
+            let enabled = app.bot_engine.get_strategy(strategy_type).is_none_or(|strategy| strategy.enabled);
+            let enabled_span = if enabled {
+                Span::styled(" [enabled]", Style::default().fg(Color::Green))
+            } else {
+                Span::styled(" [disabled]", Style::default().fg(Color::DarkGray))
+            };
+
             ListItem::new(vec![
                 Line::from(vec![
                     Span::styled(strategy_type.name(), style),
+                    enabled_span,
                 ]),
                 Line::from(vec![
                     Span::styled(
@@ -130,10 +138,17 @@ pub fn render_strategy_runner(f: &mut Frame, app: &App, area: Rect) {
             }
         };
 
+        let has_entries = match strategy_type.scope() {
+            crate::bot::strategy::StrategyScope::Event => !strategy.selected_event_ids.is_empty(),
+            _ => !strategy.selected_market_ids.is_empty(),
+        };
         let selection_list = List::new(selection_items)
-            .block(Block::default().title(selection_title).borders(Borders::ALL));
+            .block(Block::default().title(selection_title).borders(Borders::ALL))
+            .highlight_style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD));
 
-        f.render_widget(selection_list, chunks[0]);
+        // The state keeps the highlighted entry scrolled into view
+        let mut selection_state = ListState::default().with_selected(has_entries.then_some(app.selected_strategy_entry));
+        f.render_stateful_widget(selection_list, chunks[0], &mut selection_state);
 
         // Alerts/logs
         let alert_items: Vec<ListItem> = if strategy.alerts.is_empty() {
@@ -1,4 +1,14 @@
+use anyhow::Result;
+use cli_log::*;
+use std::{
+    future::Future,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
 use crate::{SimpleOrder};
+use crate::config::{API_RETRY_ATTEMPTS, API_RETRY_BASE_DELAY_MS, RATE_LIMIT_BACKOFF_BASE_MS, RATE_LIMIT_BACKOFF_MAX_MS};
+use crate::data::{sanitize_tick_size, Candle, OrderChangeDirection, PricePoint};
 
 
 #[inline]
@@ -24,8 +34,443 @@ pub fn get_spread(bid: f64, ask: f64) -> f64 {
     ask - bid
 }
 
+/// `value` with `decimals` places and a comma between every three integer digits, e.g. "-1,234,567.89"
+pub fn group_thousands(value: f64, decimals: usize) -> String {
+    let formatted = format!("{:.decimals$}", value.abs());
+    let (integer, fraction) = formatted.split_once('.').map_or((formatted.as_str(), None), |(i, f)| (i, Some(f)));
+    let mut grouped = String::with_capacity(formatted.len() + integer.len() / 3 + 1);
+    // Rounding can turn a tiny negative into zero, which shouldn't keep its sign
+    if value < 0.0 && formatted.chars().any(|c| c.is_ascii_digit() && c != '0') {
+        grouped.push('-');
+    }
+    for (i, digit) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    if let Some(fraction) = fraction {
+        grouped.push('.');
+        grouped.push_str(fraction);
+    }
+    grouped
+}
+
+/// `value` shortened with a k/M suffix from 1,000 up, e.g. "999", "1.0k", "2.35M"; smaller values keep `decimals`
+pub fn format_si(value: f64, decimals: usize) -> String {
+    let magnitude = value.abs();
+    // Compare the rounded value so 999,999 reads "1.00M" rather than "1000.0k"
+    if magnitude >= 999_950.0 {
+        format!("{:.2}M", value / 1_000_000.0)
+    } else if format!("{magnitude:.decimals$}").parse::<f64>().is_ok_and(|rounded| rounded >= 1_000.0) {
+        format!("{:.1}k", value / 1_000.0)
+    } else {
+        format!("{value:.decimals$}")
+    }
+}
+
+/// Decimals that show every price on the tick grid, at most 6; a non-positive tick is treated as MIN_TICK_SIZE
+pub fn price_decimal_places(tick_size: f64) -> usize {
+    let tick_size = crate::data::sanitize_tick_size(tick_size);
+    if tick_size >= 1.0 {
+        0
+    } else {
+        (-tick_size.log10().floor() as usize).min(6)
+    }
+}
+
+/// Shares resting within `bps` basis points of `midpoint` on one side of the book; 0 without a midpoint
+pub fn depth_within_bps(orders: &[SimpleOrder], midpoint: f64, bps: f64) -> f64 {
+    if midpoint <= 0.0 {
+        return 0.0;
+    }
+    // A hair of slack so a level exactly on the boundary isn't lost to float rounding
+    let max_distance = midpoint * bps / 10_000.0 + 1e-9;
+    orders.iter()
+        .filter(|order| (order.price - midpoint).abs() <= max_distance)
+        .map(|order| order.size)
+        .sum()
+}
+
 pub fn get_spread_from_slices(bid: &[SimpleOrder], ask: &[SimpleOrder]) -> f64 {
     let best_bid = bid.first().map_or(0.0, |b| b.price);
     let best_ask = ask.first().map_or(0.0, |a| a.price);
     get_spread(best_bid, best_ask)
 }
+
+/// Prefix of an id for log output that never slices past the end or through a char
+pub fn short_id(id: &str, len: usize) -> &str {
+    match id.char_indices().nth(len) {
+        Some((idx, _)) => &id[..idx],
+        None => id,
+    }
+}
+
+/// At most `max_chars` characters, ending in "…" when cut; never splits a multi-byte char
+pub fn truncate_chars(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(max_chars.saturating_sub(1)).collect();
+    if max_chars > 0 {
+        truncated.push('…');
+    }
+    truncated
+}
+
+/// The HTTP status of a failed request, when the error came from a response rather than the network
+pub fn http_status(error: &anyhow::Error) -> Option<u16> {
+    error.chain()
+        .find_map(|cause| cause.downcast_ref::<reqwest::Error>())
+        .and_then(reqwest::Error::status)
+        .map(|status| status.as_u16())
+}
+
+/// Whether an API error is worth retrying: anything but a client-side HTTP status
+/// (4xx other than 408 timeout and 429 rate limit), so 5xx, timeouts and connection resets are
+pub fn is_transient_error(error: &anyhow::Error) -> bool {
+    !matches!(http_status(error), Some(code) if (400..500).contains(&code) && code != 408 && code != 429)
+}
+
+/// Whether the API rejected a call for exceeding its rate limit (HTTP 429)
+pub fn is_rate_limited_error(error: &anyhow::Error) -> bool {
+    http_status(error) == Some(429)
+}
+
+/// Token bucket shared by every REST call.
+/// Callers wait for a token instead of failing, and a 429 pauses all callers with a doubling backoff.
+pub struct RateLimiter {
+    requests_per_second: f64,
+    state: Mutex<RateLimiterState>,
+}
+
+struct RateLimiterState {
+    tokens: f64,
+    last_refill: Instant,
+    paused_until: Option<Instant>,
+    backoff: Duration,
+}
+
+impl RateLimiter {
+    pub fn new(requests_per_second: f64) -> Self {
+        let requests_per_second = requests_per_second.max(0.1);
+        Self {
+            requests_per_second,
+            state: Mutex::new(RateLimiterState {
+                tokens: requests_per_second.max(1.0),
+                last_refill: Instant::now(),
+                paused_until: None,
+                backoff: Duration::from_millis(RATE_LIMIT_BACKOFF_BASE_MS),
+            }),
+        }
+    }
+
+    /// Bursts of up to one second's worth of requests are allowed
+    fn capacity(&self) -> f64 {
+        self.requests_per_second.max(1.0)
+    }
+
+    /// Waits until a request may be sent
+    pub async fn acquire(&self) {
+        loop {
+            let wait = {
+                let Ok(mut state) = self.state.lock() else { return };
+                let now = Instant::now();
+                match state.paused_until.filter(|until| *until > now) {
+                    Some(until) => until - now,
+                    None => {
+                        let elapsed = now.duration_since(state.last_refill).as_secs_f64();
+                        state.tokens = (state.tokens + elapsed * self.requests_per_second).min(self.capacity());
+                        state.last_refill = now;
+                        if state.tokens >= 1.0 {
+                            state.tokens -= 1.0;
+                            return;
+                        }
+                        Duration::from_secs_f64((1.0 - state.tokens) / self.requests_per_second)
+                    }
+                }
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Sends `request` once a token is available and tracks 429 responses
+    pub async fn run<T>(&self, request: impl Future<Output = Result<T>>) -> Result<T> {
+        self.acquire().await;
+        let result = request.await;
+        if let Ok(mut state) = self.state.lock() {
+            match &result {
+                Err(e) if is_rate_limited_error(e) => {
+                    warn!("Rate limited by the API, pausing requests for {:?}", state.backoff);
+                    state.paused_until = Some(Instant::now() + state.backoff);
+                    state.tokens = 0.0;
+                    state.backoff = (state.backoff * 2).min(Duration::from_millis(RATE_LIMIT_BACKOFF_MAX_MS));
+                }
+                Ok(_) => state.backoff = Duration::from_millis(RATE_LIMIT_BACKOFF_BASE_MS),
+                Err(_) => {}
+            }
+        }
+        result
+    }
+}
+
+/// Runs a fallible async API call, retrying transient failures with exponential backoff
+pub async fn retry_with_backoff<T, F, Fut>(label: &str, mut op: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut delay = Duration::from_millis(API_RETRY_BASE_DELAY_MS);
+    let mut attempt = 1;
+    loop {
+        match op().await {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < API_RETRY_ATTEMPTS && is_transient_error(&e) => {
+                debug!("{label} failed (attempt {attempt}/{API_RETRY_ATTEMPTS}), retrying in {delay:?}: {e}");
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Buckets price levels into bins of `bucket_ticks` ticks, summing sizes within a bin.
+/// Bids round down and asks round up so a bucket never looks better than its best level.
+/// Output keeps the input side's ordering (bids descending, asks ascending).
+pub fn aggregate_orders(orders: &[SimpleOrder], tick_size: f64, bucket_ticks: u32, round_up: bool) -> Vec<SimpleOrder> {
+    if bucket_ticks <= 1 {
+        return orders.to_vec();
+    }
+    // A NaN or non-positive tick would put every level in one bucket
+    let tick_size = sanitize_tick_size(tick_size);
+
+    let bucket_ticks = bucket_ticks as i64;
+    let mut buckets: Vec<(i64, f64, Instant)> = Vec::new();
+    for order in orders {
+        // Work in whole ticks so float noise can't split a level across bins
+        let tick = (order.price / tick_size).round() as i64;
+        let bucket = if round_up {
+            (tick + bucket_ticks - 1).div_euclid(bucket_ticks)
+        } else {
+            tick.div_euclid(bucket_ticks)
+        };
+        match buckets.iter_mut().find(|(b, _, _)| *b == bucket) {
+            // A bin is as old as its longest-resting level
+            Some((_, size, first_seen)) => {
+                *size += order.size;
+                *first_seen = (*first_seen).min(order.first_seen);
+            }
+            None => buckets.push((bucket, order.size, order.first_seen)),
+        }
+    }
+
+    if round_up {
+        buckets.sort_by_key(|(bucket, _, _)| *bucket);
+    } else {
+        buckets.sort_by_key(|(bucket, _, _)| std::cmp::Reverse(*bucket));
+    }
+
+    buckets.into_iter()
+        .map(|(bucket, size, first_seen)| SimpleOrder {
+            price: (bucket * bucket_ticks) as f64 * tick_size,
+            size,
+            previous_size: size,
+            change_direction: OrderChangeDirection::None,
+            change_timestamp: None,
+            highlight_duration: std::time::Duration::ZERO,
+            first_seen,
+        })
+        .collect()
+}
+
+/// Relative-strength oscillator (0-100) over a rolling window of price changes.
+/// Returns one `(timestamp, value)` point per price point once the window is filled.
+pub fn relative_strength_series(points: &[PricePoint], period: usize) -> Vec<(f64, f64)> {
+    if period == 0 || points.len() <= period {
+        return Vec::new();
+    }
+
+    let changes: Vec<f64> = points.windows(2).map(|w| w[1].price - w[0].price).collect();
+
+    changes
+        .windows(period)
+        .enumerate()
+        .map(|(i, window)| {
+            let gains: f64 = window.iter().filter(|c| **c > 0.0).sum();
+            let losses: f64 = window.iter().filter(|c| **c < 0.0).map(|c| -c).sum();
+            let value = if gains + losses == 0.0 {
+                50.0 // Flat window, no momentum either way
+            } else {
+                100.0 * gains / (gains + losses)
+            };
+            (points[i + period].timestamp.timestamp() as f64, value)
+        })
+        .collect()
+}
+
+/// Rolling time-weighted average price over the trailing `window_secs`.
+/// Each price holds until the next point, so it is weighted by that gap rather than counted once;
+/// this keeps bursts of updates from dominating quiet stretches. One point per input point.
+pub fn twap_series(points: &[PricePoint], window_secs: f64) -> Vec<(f64, f64)> {
+    let times: Vec<f64> = points.iter()
+        .map(|p| p.timestamp.timestamp_millis() as f64 / 1000.0)
+        .collect();
+
+    (0..points.len())
+        .map(|i| {
+            let window_start = times[i] - window_secs;
+            let mut weighted_sum = 0.0;
+            let mut total_time = 0.0;
+            for j in (0..i).rev() {
+                if times[j + 1] <= window_start {
+                    break;
+                }
+                let dt = times[j + 1] - times[j].max(window_start);
+                weighted_sum += points[j].price * dt;
+                total_time += dt;
+            }
+            let twap = if total_time > 0.0 { weighted_sum / total_time } else { points[i].price };
+            (times[i], twap)
+        })
+        .collect()
+}
+
+/// OHLC candles of `interval_secs` from a price stream, oldest first; the points may come in any order.
+/// A candle opens at the previous close, and an interval without points (the history skips
+/// unchanged prices) becomes a flat candle at that close, so the series has no gaps.
+pub fn ohlc_candles(points: &[PricePoint], interval_secs: i64) -> Vec<Candle> {
+    let interval_secs = interval_secs.max(1);
+    let mut ordered: Vec<&PricePoint> = points.iter().collect();
+    ordered.sort_by_key(|point| point.timestamp);
+    let mut candles: Vec<Candle> = Vec::new();
+    for point in ordered {
+        let start = point.timestamp.timestamp().div_euclid(interval_secs) * interval_secs;
+        match candles.last_mut() {
+            Some(candle) if candle.start == start => {
+                candle.high = candle.high.max(point.price);
+                candle.low = candle.low.min(point.price);
+                candle.close = point.price;
+            }
+            Some(&mut Candle { start: last_start, close, .. }) => {
+                candles.extend((1..(start - last_start) / interval_secs).map(|i| Candle {
+                    start: last_start + i * interval_secs,
+                    open: close,
+                    high: close,
+                    low: close,
+                    close,
+                }));
+                candles.push(Candle {
+                    start,
+                    open: close,
+                    high: close.max(point.price),
+                    low: close.min(point.price),
+                    close: point.price,
+                });
+            }
+            None => candles.push(Candle { start, open: point.price, high: point.price, low: point.price, close: point.price }),
+        }
+    }
+    candles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn errors_without_an_http_status_are_transient() {
+        let connect = anyhow::anyhow!("error sending request: tcp connect error: 104.18.0.1:443: timed out after 450ms");
+        assert_eq!(http_status(&connect), None);
+        assert!(is_transient_error(&connect));
+        assert!(is_transient_error(&connect.context("Orderbook fetch failed")));
+    }
+
+    #[test]
+    fn a_429_in_the_error_text_is_not_a_rate_limit() {
+        let error = anyhow::anyhow!("No orderbook exists for token 429");
+        assert!(!is_rate_limited_error(&error));
+    }
+
+    #[test]
+    fn aggregate_orders_falls_back_to_the_minimum_tick() {
+        use crate::config::MIN_TICK_SIZE;
+        let orders = vec![SimpleOrder::new(0.50, 10.0), SimpleOrder::new(0.49, 5.0)];
+        let prices = |tick_size| -> Vec<f64> {
+            aggregate_orders(&orders, tick_size, 5, false).iter().map(|order| order.price).collect()
+        };
+        for tick_size in [0.0, -0.01, f64::NAN] {
+            assert_eq!(prices(tick_size), prices(MIN_TICK_SIZE), "tick {tick_size}");
+        }
+        assert_eq!(prices(MIN_TICK_SIZE).len(), 2);
+    }
+
+    #[test]
+    fn group_thousands_at_the_boundaries() {
+        assert_eq!(group_thousands(999.0, 0), "999");
+        assert_eq!(group_thousands(1_000.0, 0), "1,000");
+        assert_eq!(group_thousands(1_000_000.0, 2), "1,000,000.00");
+        assert_eq!(group_thousands(-1_234_567.891, 2), "-1,234,567.89");
+        assert_eq!(group_thousands(-999.0, 0), "-999");
+        assert_eq!(group_thousands(-0.001, 2), "0.00");
+    }
+
+    #[test]
+    fn format_si_at_the_boundaries() {
+        assert_eq!(format_si(999.0, 0), "999");
+        assert_eq!(format_si(999.96, 1), "1.0k");
+        assert_eq!(format_si(1_000.0, 0), "1.0k");
+        assert_eq!(format_si(999_999.0, 0), "1.00M");
+        assert_eq!(format_si(1_000_000.0, 0), "1.00M");
+        assert_eq!(format_si(-999.0, 0), "-999");
+        assert_eq!(format_si(-1_000.0, 0), "-1.0k");
+        assert_eq!(format_si(-1_000_000.0, 0), "-1.00M");
+    }
+
+    fn points(prices: &[(i64, f64)]) -> Vec<PricePoint> {
+        prices.iter()
+            .map(|&(secs, price)| PricePoint { timestamp: chrono::DateTime::from_timestamp(secs, 0).unwrap(), price })
+            .collect()
+    }
+
+    fn candle(start: i64, open: f64, high: f64, low: f64, close: f64) -> Candle {
+        Candle { start, open, high, low, close }
+    }
+
+    #[test]
+    fn ohlc_candles_bucket_points_by_interval() {
+        let candles = ohlc_candles(&points(&[(600, 0.50), (630, 0.55), (650, 0.45), (659, 0.52), (660, 0.53)]), 60);
+
+        assert_eq!(candles, vec![
+            candle(600, 0.50, 0.55, 0.45, 0.52),
+            candle(660, 0.52, 0.53, 0.52, 0.53),
+        ]);
+    }
+
+    #[test]
+    fn ohlc_candles_fill_gaps_with_flat_candles() {
+        let candles = ohlc_candles(&points(&[(600, 0.50), (790, 0.60)]), 60);
+
+        assert_eq!(candles, vec![
+            candle(600, 0.50, 0.50, 0.50, 0.50),
+            candle(660, 0.50, 0.50, 0.50, 0.50),
+            candle(720, 0.50, 0.50, 0.50, 0.50),
+            candle(780, 0.50, 0.60, 0.50, 0.60),
+        ]);
+    }
+
+    #[test]
+    fn ohlc_candles_sort_out_of_order_points() {
+        let in_order = ohlc_candles(&points(&[(600, 0.50), (610, 0.52), (700, 0.48)]), 60);
+        let shuffled = ohlc_candles(&points(&[(700, 0.48), (600, 0.50), (610, 0.52)]), 60);
+
+        assert_eq!(shuffled, in_order);
+        assert!(shuffled.windows(2).all(|pair| pair[0].start < pair[1].start));
+    }
+
+    #[test]
+    fn ohlc_candles_of_no_points_is_empty() {
+        assert!(ohlc_candles(&[], 60).is_empty());
+    }
+}
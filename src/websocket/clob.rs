@@ -2,16 +2,20 @@ use cli_log::{
     info,
     warn,
 };
-use tokio_tungstenite::{
-    connect_async,
-    tungstenite::Message,
-};
+use tokio_tungstenite::tungstenite::Message;
 use futures_util::{SinkExt, StreamExt};
-use std::sync::mpsc::{channel, Sender};
-use std::thread;
+use std::{
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
 use serde_json::json;
 use serde::{Deserialize, Serialize};
 
+use crate::config::WS_PING_INTERVAL_SECS;
+use crate::utils::short_id;
+
 // Structured data types for WebSocket messages
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OrderSummary {
@@ -69,12 +73,48 @@ pub struct LastTradePriceMessage {
     pub timestamp: String,
 }
 
+// User channel messages (own orders and fills)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserOrderMessage {
+    pub event_type: String,
+    pub asset_id: String,
+    pub id: String,
+    pub market: String,
+    #[serde(default)]
+    pub outcome: String,
+    pub price: String,
+    pub side: String,
+    pub original_size: String,
+    pub size_matched: String,
+    pub timestamp: String,
+    #[serde(rename = "type")]
+    pub order_type: String, // PLACEMENT, UPDATE or CANCELLATION
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserTradeMessage {
+    pub event_type: String,
+    pub asset_id: String,
+    pub id: String,
+    pub market: String,
+    #[serde(default)]
+    pub outcome: String,
+    pub price: String,
+    pub side: String,
+    pub size: String,
+    #[serde(default)]
+    pub status: String,
+    pub timestamp: String,
+}
+
 #[derive(Debug, Clone)]
 pub enum PolymarketWebSocketMessage {
     Book(BookMessage),
     PriceChange(PriceChangeMessage),
     TickSizeChange(TickSizeChangeMessage),
     LastTradePrice(LastTradePriceMessage),
+    UserOrder(UserOrderMessage),
+    UserTrade(UserTradeMessage),
     Unknown(String),
 }
 
@@ -82,32 +122,68 @@ pub enum PolymarketWebSocketMessage {
 pub type MessageCallback = Box<dyn Fn(PolymarketWebSocketMessage) + Send>;
 
 pub struct PolymarketWebSocket {
-    sender: Sender<Message>,
+    sender: UnboundedSender<Message>, // Drained into the socket by the connection thread's read loop
     pub thread_handle: thread::JoinHandle<()>,
+    last_frame: Arc<Mutex<Option<Instant>>>, // Any incoming frame, pongs included, so a quiet market still counts as alive
+    started: Instant,
 }
 
 impl PolymarketWebSocket {
-    pub fn send(&self, message: Message) {
-        self.sender.send(message).unwrap();
+    /// Queues a message for the open connection; false once the connection thread has ended
+    pub fn send(&self, message: Message) -> bool {
+        self.sender.send(message).is_ok()
+    }
+
+    /// Time since the last incoming frame, or since connecting if none has arrived yet
+    pub fn silent_for(&self) -> Duration {
+        let last_frame = self.last_frame.lock().ok().and_then(|last_frame| *last_frame);
+        last_frame.unwrap_or(self.started).elapsed()
     }
 
+    pub fn has_received(&self) -> bool {
+        self.last_frame.lock().is_ok_and(|last_frame| last_frame.is_some())
+    }
+
+    /// Adds market channel assets to the live subscription; the server answers with a book snapshot for each
+    pub fn subscribe_assets(&self, asset_ids: &[String]) -> bool {
+        self.send_operation("subscribe", asset_ids)
+    }
+
+    pub fn unsubscribe_assets(&self, asset_ids: &[String]) -> bool {
+        self.send_operation("unsubscribe", asset_ids)
+    }
+
+    fn send_operation(&self, operation: &str, asset_ids: &[String]) -> bool {
+        if asset_ids.is_empty() {
+            return true;
+        }
+        let message = json!({ "assets_ids": asset_ids, "operation": operation });
+        info!("WebSocket {operation} for {} assets", asset_ids.len());
+        self.send(Message::Text(message.to_string().into()))
+    }
+
+    /// `ws_host` is the base URL, e.g. `wss://ws-subscriptions-clob.polymarket.com`
     pub fn connect(
+        ws_host: &str,
         channel_type: String,
         auth: Option<serde_json::Value>,
         filter_ids: Vec<String>,
         callback: MessageCallback,
     ) -> Self {
-        let (tx, _rx) = channel();
+        let (tx, mut rx) = unbounded_channel::<Message>();
         let channel = channel_type.clone();
+        let ws_host = ws_host.trim_end_matches('/').to_string();
         let filter: Vec<String> = filter_ids;
+        let last_frame = Arc::new(Mutex::new(None));
+        let thread_last_frame = Arc::clone(&last_frame);
 
         let handle = thread::spawn(move || {
             let rt = tokio::runtime::Runtime::new().unwrap();
             rt.block_on(async {
-                let url = format!("wss://ws-subscriptions-clob.polymarket.com/ws/{channel}");
+                let url = format!("{ws_host}/ws/{channel}");
                 info!("Connecting to WebSocket at: {url}");
                 
-                let (ws_stream, _) = match connect_async(&url).await {
+                let (ws_stream, _) = match crate::proxy::connect_websocket(&url).await {
                     Ok(stream) => stream,
                     Err(e) => {
                         warn!("Failed to connect to WebSocket: {e:?}");
@@ -145,10 +221,43 @@ impl PolymarketWebSocket {
 
                 info!("✅ Connected and subscribed to {channel_type} channel");
 
-                // Message processing loop
-                while let Some(msg_result) = read.next().await {
+                // Pings make the server answer even when the market is quiet, so silence means a stall
+                let mut ping_interval = tokio::time::interval(Duration::from_secs(WS_PING_INTERVAL_SECS));
+                ping_interval.tick().await;
+
+                // Message processing loop; queued outgoing messages are written between incoming ones
+                loop {
+                    let msg_result = tokio::select! {
+                        incoming = read.next() => match incoming {
+                            Some(msg_result) => msg_result,
+                            None => break,
+                        },
+                        outgoing = rx.recv() => {
+                            let Some(outgoing) = outgoing else {
+                                // The handle was dropped, so nobody reads this connection any more
+                                info!("Closing {channel_type} channel WebSocket");
+                                let _ = write.send(Message::Close(None)).await;
+                                break;
+                            };
+                            if let Err(e) = write.send(outgoing).await {
+                                warn!("Failed to send message: {e:?}");
+                                break;
+                            }
+                            continue;
+                        }
+                        _ = ping_interval.tick() => {
+                            if let Err(e) = write.send(Message::Ping(Vec::new().into())).await {
+                                warn!("Failed to send ping: {e:?}");
+                                break;
+                            }
+                            continue;
+                        }
+                    };
                     match msg_result {
                         Ok(msg) => {
+                            if let Ok(mut last_frame) = thread_last_frame.lock() {
+                                *last_frame = Some(Instant::now());
+                            }
                             // Parse and handle structured messages
                             Self::handle_message(&msg, &callback);
                             
@@ -172,6 +281,8 @@ impl PolymarketWebSocket {
         Self {
             sender: tx,
             thread_handle: handle,
+            last_frame,
+            started: Instant::now(),
         }
     }
 
@@ -217,7 +328,7 @@ impl PolymarketWebSocket {
                         info!("📊 Book Update: {} bids, {} asks for asset {}", 
                                 book_msg.bids.len(), 
                                 book_msg.asks.len(), 
-                                &book_msg.asset_id[..10]);
+                                short_id(&book_msg.asset_id, 10));
                         PolymarketWebSocketMessage::Book(book_msg)
                     }
                     Err(e) => {
@@ -243,7 +354,7 @@ impl PolymarketWebSocket {
                         info!("📏 Tick Size Change: {} -> {} for asset {}", 
                                 tick_msg.old_tick_size, 
                                 tick_msg.new_tick_size, 
-                                &tick_msg.asset_id[..10]);
+                                short_id(&tick_msg.asset_id, 10));
                         PolymarketWebSocketMessage::TickSizeChange(tick_msg)
                     }
                     Err(e) => {
@@ -259,7 +370,7 @@ impl PolymarketWebSocket {
                                 trade_msg.side, 
                                 trade_msg.size, 
                                 trade_msg.price, 
-                                &trade_msg.asset_id[..10]);
+                                short_id(&trade_msg.asset_id, 10));
                         PolymarketWebSocketMessage::LastTradePrice(trade_msg)
                     }
                     Err(e) => {
@@ -268,6 +379,39 @@ impl PolymarketWebSocket {
                     }
                 }
             }
+            "order" => {
+                match serde_json::from_value::<UserOrderMessage>(value) {
+                    Ok(order_msg) => {
+                        info!("📝 Order {}: {} {} @ {} for asset {}", 
+                                order_msg.order_type, 
+                                order_msg.side, 
+                                order_msg.original_size, 
+                                order_msg.price, 
+                                short_id(&order_msg.asset_id, 10));
+                        PolymarketWebSocketMessage::UserOrder(order_msg)
+                    }
+                    Err(e) => {
+                        warn!("Failed to parse user order message: {e:?}");
+                        PolymarketWebSocketMessage::Unknown(value_string)
+                    }
+                }
+            }
+            "trade" => {
+                match serde_json::from_value::<UserTradeMessage>(value) {
+                    Ok(trade_msg) => {
+                        info!("✅ Fill: {} {} @ {} for asset {}", 
+                                trade_msg.side, 
+                                trade_msg.size, 
+                                trade_msg.price, 
+                                short_id(&trade_msg.asset_id, 10));
+                        PolymarketWebSocketMessage::UserTrade(trade_msg)
+                    }
+                    Err(e) => {
+                        warn!("Failed to parse user trade message: {e:?}");
+                        PolymarketWebSocketMessage::Unknown(value_string)
+                    }
+                }
+            }
             _ => {
                 info!("❓ Unknown message type: {event_type}");
                 PolymarketWebSocketMessage::Unknown(value_string)
@@ -275,3 +419,36 @@ impl PolymarketWebSocket {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_asset_ids_parse_without_panicking() {
+        let book = json!({
+            "event_type": "book",
+            "asset_id": "1234",
+            "market": "0xabc",
+            "timestamp": "1700000000000",
+            "hash": "0x0",
+            "bids": [{ "price": "0.48", "size": "100" }],
+            "asks": [{ "price": "0.52", "size": "80" }],
+        });
+        match PolymarketWebSocket::parse_polymarket_message(book) {
+            PolymarketWebSocketMessage::Book(book_msg) => assert_eq!(book_msg.asset_id, "1234"),
+            other => panic!("expected a book message, got {other:?}"),
+        }
+
+        let tick = json!({
+            "event_type": "tick_size_change",
+            "asset_id": "1234",
+            "market": "0xabc",
+            "old_tick_size": "0.01",
+            "new_tick_size": "0.001",
+            "timestamp": "1700000000000",
+        });
+        assert!(matches!(PolymarketWebSocket::parse_polymarket_message(tick), PolymarketWebSocketMessage::TickSizeChange(_)));
+        assert_eq!(short_id("1234", 10), "1234");
+    }
+}
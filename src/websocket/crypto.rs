@@ -1,12 +1,15 @@
 #![allow(dead_code)]
 
-use binance::websockets::*;
+use binance::{config::Config, websockets::*};
+use tokio_tungstenite::tungstenite;
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread;
 use cli_log::*;
 
+use crate::config::{BINANCE_WS_ENDPOINT, COINBASE_WS_URL};
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum CryptoSymbol {
     Bitcoin,
@@ -38,12 +41,185 @@ impl CryptoSymbol {
             CryptoSymbol::Solana => "SOL",
         }
     }
+
+    /// Coinbase product id
+    pub fn product_id(&self) -> &'static str {
+        match self {
+            CryptoSymbol::Bitcoin => "BTC-USD",
+            CryptoSymbol::Ethereum => "ETH-USD",
+            CryptoSymbol::Solana => "SOL-USD",
+        }
+    }
+
+    pub fn all() -> [CryptoSymbol; 3] {
+        [CryptoSymbol::Bitcoin, CryptoSymbol::Ethereum, CryptoSymbol::Solana]
+    }
+
+    /// Matches the symbol (`BTC`) or the name (`bitcoin`), ignoring case
+    pub fn parse(value: &str) -> Option<CryptoSymbol> {
+        Self::all().into_iter().find(|symbol| {
+            symbol.symbol().eq_ignore_ascii_case(value) || symbol.name().eq_ignore_ascii_case(value)
+        })
+    }
+}
+
+/// Exchanges the crypto price charts can stream from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CryptoSourceKind {
+    #[default]
+    Binance,
+    Coinbase,
+}
+
+impl std::str::FromStr for CryptoSourceKind {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "binance" => Ok(Self::Binance),
+            "coinbase" => Ok(Self::Coinbase),
+            _ => Err(format!("unknown crypto source '{value}', expected one of: binance, coinbase")),
+        }
+    }
+}
+
+/// An exchange feed; implement this to add a new source
+pub trait CryptoPriceSource: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    fn default_stream_url(&self, symbol: &CryptoSymbol) -> String;
+
+    /// Streams mid prices for `symbol` on the calling thread until `keep_running` is cleared or the connection ends
+    fn stream(&self, symbol: &CryptoSymbol, url: &str, keep_running: &AtomicBool, on_price: &mut dyn FnMut(f64)) -> anyhow::Result<()>;
+}
+
+/// Which source to use and any per-symbol stream URL overrides
+#[derive(Debug, Clone, Default)]
+pub struct CryptoFeed {
+    pub source: CryptoSourceKind,
+    pub stream_urls: HashMap<CryptoSymbol, String>,
+}
+
+impl CryptoFeed {
+    pub fn source(&self) -> Arc<dyn CryptoPriceSource> {
+        match self.source {
+            CryptoSourceKind::Binance => Arc::new(BinanceSource),
+            CryptoSourceKind::Coinbase => Arc::new(CoinbaseSource),
+        }
+    }
+
+    pub fn stream_url(&self, symbol: &CryptoSymbol) -> String {
+        self.stream_urls.get(symbol).cloned()
+            .unwrap_or_else(|| self.source().default_stream_url(symbol))
+    }
+}
+
+/// Binance book ticker stream; the URL is the websocket endpoint plus the stream name
+pub struct BinanceSource;
+
+impl CryptoPriceSource for BinanceSource {
+    fn name(&self) -> &'static str {
+        "Binance"
+    }
+
+    fn default_stream_url(&self, symbol: &CryptoSymbol) -> String {
+        format!("{BINANCE_WS_ENDPOINT}/{}", symbol.ticker())
+    }
+
+    fn stream(&self, symbol: &CryptoSymbol, url: &str, keep_running: &AtomicBool, on_price: &mut dyn FnMut(f64)) -> anyhow::Result<()> {
+        let name = symbol.name();
+        // binance-rs joins the endpoint and the stream name itself
+        let (endpoint, subscription) = url.rsplit_once('/')
+            .ok_or_else(|| anyhow::anyhow!("Binance stream URL '{url}' has no stream name"))?;
+        let config = Config::default().set_ws_endpoint(endpoint);
+
+        let mut web_socket = WebSockets::new(move |event: WebsocketEvent| {
+            match event {
+                WebsocketEvent::BookTicker(ticker_data) => {
+                    // Parse the best bid and ask prices and average them
+                    if let (Ok(bid), Ok(ask)) = (ticker_data.best_bid.parse::<f64>(), ticker_data.best_ask.parse::<f64>()) {
+                        on_price((bid + ask) / 2.0);
+                    }
+                }
+                WebsocketEvent::DayTicker(ticker_data) => {
+                   warn!("Received DayTicker event for {name}: {ticker_data:?}");
+                }
+                _ => {
+                    warn!("Unexpected event received for {name}: {event:?}");
+                    if let Ok(json) = serde_json::to_string(&event) {
+                        warn!("Raw JSON event: {json}");
+                    } else {
+                        warn!("Failed to serialize event to JSON");
+                    }
+                }
+            }
+
+            Ok(())
+        });
+
+        web_socket.connect_with_config(subscription, &config)
+            .map_err(|e| anyhow::anyhow!("failed to connect: {e}"))?;
+        info!("{name} WebSocket connected successfully");
+
+        let result = web_socket.event_loop(keep_running)
+            .map_err(|e| anyhow::anyhow!("event loop error: {e}"));
+        if let Err(e) = web_socket.disconnect() {
+            warn!("Failed to disconnect {name} WebSocket: {e}");
+        }
+        result
+    }
+}
+
+/// Coinbase Exchange ticker channel, for networks where Binance is blocked
+pub struct CoinbaseSource;
+
+impl CryptoPriceSource for CoinbaseSource {
+    fn name(&self) -> &'static str {
+        "Coinbase"
+    }
+
+    fn default_stream_url(&self, _symbol: &CryptoSymbol) -> String {
+        COINBASE_WS_URL.to_string()
+    }
+
+    fn stream(&self, symbol: &CryptoSymbol, url: &str, keep_running: &AtomicBool, on_price: &mut dyn FnMut(f64)) -> anyhow::Result<()> {
+        let (mut socket, _) = tungstenite::connect(url)?;
+        let subscribe = serde_json::json!({
+            "type": "subscribe",
+            "product_ids": [symbol.product_id()],
+            "channels": ["ticker"],
+        });
+        socket.send(tungstenite::Message::Text(subscribe.to_string().into()))?;
+        info!("{} WebSocket connected successfully", symbol.name());
+
+        // Tickers arrive several times a second, so the flag is checked often enough between reads
+        while keep_running.load(Ordering::Relaxed) {
+            let tungstenite::Message::Text(text) = socket.read()? else { continue };
+            let Ok(value) = serde_json::from_str::<serde_json::Value>(&text) else { continue };
+            match value.get("type").and_then(|kind| kind.as_str()) {
+                Some("ticker") => {
+                    let field = |key: &str| value.get(key).and_then(|v| v.as_str()).and_then(|v| v.parse::<f64>().ok());
+                    if let (Some(bid), Some(ask)) = (field("best_bid"), field("best_ask")) {
+                        on_price((bid + ask) / 2.0);
+                    } else if let Some(price) = field("price") {
+                        on_price(price);
+                    }
+                }
+                Some("error") => anyhow::bail!("Coinbase rejected the subscription: {text}"),
+                _ => {}
+            }
+        }
+
+        socket.close(None).ok();
+        Ok(())
+    }
 }
 
 pub struct CryptoWebSocket {
     pub thread_handles: HashMap<CryptoSymbol, thread::JoinHandle<()>>,
     pub keep_running: Arc<AtomicBool>,
     pub prices: Arc<Mutex<HashMap<CryptoSymbol, f64>>>,
+    pub feed: CryptoFeed,
 }
 
 impl Default for CryptoWebSocket {
@@ -54,10 +230,15 @@ impl Default for CryptoWebSocket {
 
 impl CryptoWebSocket {
     pub fn new() -> Self {
+        Self::with_feed(CryptoFeed::default())
+    }
+
+    pub fn with_feed(feed: CryptoFeed) -> Self {
         Self {
             thread_handles: HashMap::new(),
             keep_running: Arc::new(AtomicBool::new(false)),
             prices: Arc::new(Mutex::new(HashMap::new())),
+            feed,
         }
     }
 
@@ -83,52 +264,25 @@ impl CryptoWebSocket {
     fn start_symbol(&mut self, symbol: CryptoSymbol) {
         let keep_running = Arc::clone(&self.keep_running);
         let prices = Arc::clone(&self.prices);
-        let ticker = symbol.ticker().to_string();
+        let source = self.feed.source();
+        let url = self.feed.stream_url(&symbol);
         let name = symbol.name();
         let symbol_clone = symbol.clone();
 
+        if crate::proxy::proxy_url().is_some() {
+            // Both exchange clients open their own blocking sockets with no hook for a connector
+            warn!("{name} feed connects directly, the configured proxy is not applied to {}", source.name());
+        }
+
         let handle = thread::spawn(move || {
-            let mut web_socket = WebSockets::new(move |event: WebsocketEvent| {
-                match event {
-                    WebsocketEvent::BookTicker(ticker_data) => {
-                        // Parse the best bid and ask prices and average them
-                        if let (Ok(bid), Ok(ask)) = (ticker_data.best_bid.parse::<f64>(), ticker_data.best_ask.parse::<f64>()) {
-                            let mid_price = (bid + ask) / 2.0;
-                            
-                            if let Ok(mut price_map) = prices.lock() {
-                                price_map.insert(symbol_clone.clone(), mid_price);
-                            }
-                        }
-                    }
-                    WebsocketEvent::DayTicker(ticker_data) => {
-                       warn!("Received DayTicker event for {name}: {ticker_data:?}");
-                    }
-                    _ => {
-                        warn!("Unexpected event received for {name}: {event:?}");
-                        if let Ok(json) = serde_json::to_string(&event) {
-                            warn!("Raw JSON event: {json}");
-                        } else {
-                            warn!("Failed to serialize event to JSON");
-                        }
-                    }
+            info!("Connecting {name} feed to {} at {url}", source.name());
+            let mut on_price = |mid_price: f64| {
+                if let Ok(mut price_map) = prices.lock() {
+                    price_map.insert(symbol_clone.clone(), mid_price);
                 }
-                
-                Ok(())
-            });
-
-            if let Err(e) = web_socket.connect(&ticker) {
-                warn!("Failed to connect {name} WebSocket: {e}");
-                return;
-            }
-
-            info!("{name} WebSocket connected successfully");
-
-            if let Err(e) = web_socket.event_loop(&keep_running) {
-                warn!("{name} WebSocket event loop error: {e}");
-            }
-
-            if let Err(e) = web_socket.disconnect() {
-                warn!("Failed to disconnect {name} WebSocket: {e}");
+            };
+            if let Err(e) = source.stream(&symbol_clone, &url, &keep_running, &mut on_price) {
+                warn!("{name} {} WebSocket failed: {e}", source.name());
             }
 
             info!("{name} WebSocket disconnected");
@@ -8,7 +8,9 @@ pub use clob::{
     PolymarketWebSocket, 
     PolymarketWebSocketMessage,
     PriceChangeMessage,
+    UserOrderMessage,
+    UserTradeMessage,
     MessageCallback,
 };
 
-pub use crypto::{CryptoWebSocket, CryptoSymbol};
+pub use crypto::{CryptoWebSocket, CryptoSymbol, CryptoFeed, CryptoPriceSource, CryptoSourceKind};
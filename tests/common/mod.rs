@@ -0,0 +1,62 @@
+//! Fixtures shared by the integration tests; each test binary uses a different subset
+#![allow(dead_code)]
+
+use polymarket::app::Endpoints;
+use polymarket::data_source::MockMarketData;
+use polymarket::App;
+use polymarket_rs_client::{Event, GammaMarket, OrderBookSummary, OrderSummary};
+use rust_decimal::Decimal;
+
+/// Hosts nothing listens on, so a test that opens a market never reaches the real API
+pub fn offline_endpoints() -> Endpoints {
+    Endpoints {
+        clob_host: "http://127.0.0.1:1".to_string(),
+        gamma_host: "http://127.0.0.1:1".to_string(),
+        ws_host: "ws://127.0.0.1:1".to_string(),
+    }
+}
+
+/// An app reading from `mock`, with the selector open as on startup
+pub fn app(mock: MockMarketData) -> App {
+    App::with_data_source(1.0, 10, Box::new(mock), offline_endpoints())
+}
+
+/// An active binary-style market with one outcome per token
+pub fn market(question: &str, token_ids: &[&str], volume: i64) -> GammaMarket {
+    GammaMarket {
+        question: question.to_string(),
+        description: None,
+        end_date: None,
+        category: None,
+        resolution_source: None,
+        token_ids: token_ids.iter().map(|id| id.to_string()).collect(),
+        outcomes: (1..=token_ids.len()).map(|n| format!("Outcome {n}")).collect(),
+        volume: Some(Decimal::from(volume)),
+        active: true,
+        closed: false,
+        uma_resolution_statuses: None,
+    }
+}
+
+pub fn event(id: &str, title: &str, markets: Vec<GammaMarket>) -> Event {
+    Event {
+        id: id.to_string(),
+        title: title.to_string(),
+        description: String::new(),
+        category: None,
+        end_date: None,
+        volume: None,
+        markets: Some(markets),
+    }
+}
+
+/// A REST book from `(price, size)` levels, in the order given
+pub fn book(bids: &[(f64, f64)], asks: &[(f64, f64)]) -> OrderBookSummary {
+    let levels = |levels: &[(f64, f64)]| levels.iter()
+        .map(|&(price, size)| OrderSummary {
+            price: Decimal::try_from(price).unwrap(),
+            size: Decimal::try_from(size).unwrap(),
+        })
+        .collect();
+    OrderBookSummary { bids: levels(bids), asks: levels(asks) }
+}
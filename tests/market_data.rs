@@ -0,0 +1,69 @@
+mod common;
+
+use common::{book, event, market};
+use polymarket::data_source::MockMarketData;
+
+#[tokio::test]
+async fn market_listed_under_two_events_appears_once() {
+    let shared = market("Will it rain?", &["yes", "no"], 500);
+    let mock = MockMarketData::default().with_events(vec![
+        event("1", "Weather", vec![shared.clone(), market("Will it snow?", &["snow-yes", "snow-no"], 100)]),
+        event("2", "Rain", vec![market("Will it rain?", &["no", "yes"], 50)]),
+    ]);
+    let mut app = common::app(mock);
+
+    app.load_markets().await.unwrap();
+
+    let questions: Vec<&str> = app.markets.iter().map(|market| market.question.as_str()).collect();
+    assert_eq!(questions, vec!["Will it rain?", "Will it snow?"]);
+    assert_eq!(app.markets[0].volume, shared.volume);
+}
+
+#[tokio::test]
+async fn filtered_market_loads_into_a_sorted_book() {
+    let mock = MockMarketData::default()
+        .with_events(vec![event("1", "Weather", vec![
+            market("Will it rain?", &["rain-yes", "rain-no"], 500),
+            market("Will it snow?", &["snow-yes", "snow-no"], 100),
+        ])])
+        .with_book("rain-yes", book(&[(0.40, 50.0), (0.45, 10.0), (0.45, 5.0)], &[(0.55, 20.0), (0.50, 30.0)]))
+        .with_book("rain-no", book(&[(0.49, 30.0)], &[(0.56, 10.0)]));
+    let mut app = common::app(mock);
+    app.load_markets().await.unwrap();
+
+    app.search_query = "rain".to_string();
+    app.update_filtered_markets();
+    assert_eq!(app.filtered_markets.len(), 1);
+    let market = &app.markets[app.filtered_markets[0]];
+    let token_id = market.token_ids[0].clone();
+
+    app.load_orderbook(&token_id).await.unwrap();
+
+    assert!(app.error_message.is_none());
+    let orderbook = app.orderbook.as_ref().unwrap();
+    assert_eq!(orderbook.market_question, "Will it rain? - Outcome 1");
+    assert_eq!(orderbook.tick_size, 0.01);
+    let bids: Vec<(f64, f64)> = orderbook.bids.iter().map(|level| (level.price, level.size)).collect();
+    let asks: Vec<(f64, f64)> = orderbook.asks.iter().map(|level| (level.price, level.size)).collect();
+    assert_eq!(bids, vec![(0.45, 15.0), (0.40, 50.0)]);
+    assert_eq!(asks, vec![(0.50, 30.0), (0.55, 20.0)]);
+    assert!((orderbook.get_midpoint() - 0.475).abs() < 1e-9);
+    assert_eq!(orderbook.price_history.points.len(), 1);
+}
+
+#[tokio::test]
+async fn outcomes_shorter_than_token_ids_label_tokens_by_position() {
+    let mut short = market("Who wins?", &["first", "second", "third"], 100);
+    short.outcomes = vec!["Alice".to_string()];
+    let mock = MockMarketData::default()
+        .with_events(vec![event("1", "Election", vec![short])])
+        .with_book("third", book(&[(0.20, 10.0)], &[(0.25, 10.0)]));
+    let mut app = common::app(mock);
+    app.load_markets().await.unwrap();
+
+    assert_eq!(app.market_question_for_token("first"), "Who wins? - Outcome 1");
+    assert_eq!(app.market_question_for_token("third"), "Who wins? - Outcome 3");
+
+    app.load_orderbook("third").await.unwrap();
+    assert_eq!(app.orderbook.as_ref().unwrap().market_question, "Who wins? - Outcome 3");
+}
@@ -0,0 +1,28 @@
+//! Its own test binary: the file logger and the panic hook are process-global
+
+use std::{fs, panic, thread};
+
+#[test]
+fn forced_panic_is_written_to_the_log_file() {
+    let path = std::env::temp_dir().join(format!("polymarket-panic-{}.log", std::process::id()));
+    let _ = fs::remove_file(&path);
+    polymarket::logging::init_file_logging(Some("info"), path.to_str()).unwrap();
+    polymarket::logging::install_panic_hook();
+
+    let result = panic::catch_unwind(|| panic!("boom"));
+    let worker = thread::Builder::new()
+        .name("ws-worker".to_string())
+        .spawn(|| panic!("worker down"))
+        .unwrap()
+        .join();
+
+    let _ = panic::take_hook(); // Later failures print normally again
+    assert!(result.is_err());
+    assert!(worker.is_err());
+    let log = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).ok();
+    assert!(log.contains("Application panicked"), "log was: {log}");
+    assert!(log.contains("boom"), "log was: {log}");
+    assert!(log.contains("thread 'ws-worker'"), "log was: {log}");
+    assert!(log.contains("worker down"), "log was: {log}");
+}
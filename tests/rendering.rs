@@ -0,0 +1,53 @@
+mod common;
+
+use polymarket::data::{OrderBookData, SimpleOrder};
+use polymarket::data_source::MockMarketData;
+use polymarket::render_ui;
+use polymarket::ui::charts::render_orderbook_plot;
+use polymarket::ui::layout::{MIN_TERMINAL_HEIGHT, MIN_TERMINAL_WIDTH};
+use polymarket::ui::orderbook::render_orderbook;
+use ratatui::{backend::TestBackend, Terminal};
+
+fn buffer_text(terminal: &Terminal<TestBackend>) -> String {
+    terminal.backend().buffer().content().iter().map(|cell| cell.symbol()).collect()
+}
+
+#[test]
+fn tiny_terminal_shows_the_size_message() {
+    let mut app = common::app(MockMarketData::default());
+    let mut terminal = Terminal::new(TestBackend::new(10, 3)).unwrap();
+
+    terminal.draw(|f| render_ui(f, &mut app)).unwrap();
+
+    assert!(buffer_text(&terminal).contains("Terminal"));
+}
+
+#[test]
+fn minimum_size_renders_the_normal_layout() {
+    let mut app = common::app(MockMarketData::default());
+    let mut terminal = Terminal::new(TestBackend::new(MIN_TERMINAL_WIDTH, MIN_TERMINAL_HEIGHT)).unwrap();
+
+    terminal.draw(|f| render_ui(f, &mut app)).unwrap();
+
+    assert!(!buffer_text(&terminal).contains("too small"));
+}
+
+#[test]
+fn pathological_tick_sizes_render_without_panicking() {
+    for tick_size in [0.0, -0.01, f64::NAN, 1e-12] {
+        let mut app = common::app(MockMarketData::default());
+        let mut orderbook = OrderBookData::empty("token".to_string(), "Will it rain? - Yes".to_string());
+        orderbook.bids = vec![SimpleOrder::new(0.45, 100.0), SimpleOrder::new(0.44, 50.0)];
+        orderbook.asks = vec![SimpleOrder::new(0.55, 80.0), SimpleOrder::new(0.56, 20.0)];
+        orderbook.tick_size = tick_size;
+        orderbook.record_midpoint();
+        app.orderbook = Some(orderbook.clone());
+        let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+
+        terminal.draw(|f| render_orderbook(f, &mut app, f.area())).unwrap();
+        terminal.draw(|f| render_orderbook_plot(
+            f, &mut orderbook, app.price_format, app.number_format, app.show_notional,
+            app.aggregation_ticks, app.depth_chart_ticks, app.depth_chart_mode, f.area(),
+        )).unwrap();
+    }
+}
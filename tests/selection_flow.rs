@@ -0,0 +1,103 @@
+mod common;
+
+use common::{book, event, market};
+use crossterm::event::KeyCode;
+use polymarket::app::MarketSelectorTab;
+use polymarket::data_source::MockMarketData;
+use polymarket::App;
+
+async fn press(app: &mut App, key: KeyCode) {
+    assert!(app.handle_key_input(key).await.unwrap(), "{key:?} quit the app");
+}
+
+/// Two events of two binary markets each, largest volume first
+async fn loaded_app() -> App {
+    let mock = MockMarketData::default()
+        .with_events(vec![
+            event("1", "Weather", vec![
+                market("Will it rain?", &["rain-yes", "rain-no"], 500),
+                market("Will it snow?", &["snow-yes", "snow-no"], 400),
+            ]),
+            event("2", "Football", vec![
+                market("Will the home side win?", &["home-yes", "home-no"], 300),
+                market("Will there be a goal?", &["goal-yes", "goal-no"], 200),
+            ]),
+        ])
+        .with_book("goal-no", book(&[(0.30, 100.0)], &[(0.32, 80.0)]))
+        .with_book("snow-yes", book(&[(0.10, 50.0)], &[(0.12, 40.0)]));
+    let mut app = common::app(mock);
+    app.load_markets().await.unwrap();
+    app
+}
+
+#[tokio::test]
+async fn keys_walk_event_market_token_and_back() {
+    let mut app = loaded_app().await;
+
+    // A place in All Markets that the event walk must not lose
+    press(&mut app, KeyCode::Down).await;
+    press(&mut app, KeyCode::Down).await;
+    assert_eq!(app.selected_market, 2);
+
+    press(&mut app, KeyCode::Right).await;
+    assert!(app.market_selector_tab == MarketSelectorTab::Events);
+
+    // Second event, its second market, then its second token
+    press(&mut app, KeyCode::Down).await;
+    press(&mut app, KeyCode::Enter).await;
+    assert!(app.show_event_market_selector);
+    press(&mut app, KeyCode::Down).await;
+    press(&mut app, KeyCode::Enter).await;
+    assert!(app.show_token_selector);
+    assert_eq!(app.get_current_token_id().as_deref(), Some("goal-yes"));
+    press(&mut app, KeyCode::Down).await;
+    assert_eq!(app.get_current_token_id().as_deref(), Some("goal-no"));
+
+    press(&mut app, KeyCode::Backspace).await;
+    assert!(!app.show_token_selector);
+    assert!(app.show_event_market_selector);
+    assert!(!app.show_market_selector);
+
+    press(&mut app, KeyCode::Backspace).await;
+    assert!(!app.show_event_market_selector);
+    assert!(app.show_market_selector);
+    assert!(app.market_selector_tab == MarketSelectorTab::Events);
+    assert_eq!(app.selected_event, 1);
+    assert_eq!(app.selected_market, 2);
+
+    // Back in, this time opening the token
+    press(&mut app, KeyCode::Enter).await;
+    press(&mut app, KeyCode::Down).await;
+    press(&mut app, KeyCode::Enter).await;
+    press(&mut app, KeyCode::Down).await;
+    press(&mut app, KeyCode::Enter).await;
+    assert!(!app.show_token_selector);
+    assert_eq!(app.orderbook.as_ref().unwrap().token_id, "goal-no");
+}
+
+#[tokio::test]
+async fn keys_walk_all_markets_token_and_back() {
+    let mut app = loaded_app().await;
+    assert!(app.market_selector_tab == MarketSelectorTab::AllMarkets);
+
+    press(&mut app, KeyCode::Down).await;
+    press(&mut app, KeyCode::Enter).await;
+    assert!(app.show_token_selector);
+    assert!(!app.show_market_selector);
+    assert_eq!(app.get_current_token_id().as_deref(), Some("snow-yes"));
+
+    press(&mut app, KeyCode::Backspace).await;
+    assert!(!app.show_token_selector);
+    assert!(app.show_market_selector);
+    assert_eq!(app.selected_market, 1);
+
+    // Nothing further back than the selector itself
+    press(&mut app, KeyCode::Backspace).await;
+    assert!(app.show_market_selector);
+    assert!(app.market_selector_tab == MarketSelectorTab::AllMarkets);
+    assert_eq!(app.selected_market, 1);
+
+    press(&mut app, KeyCode::Enter).await;
+    press(&mut app, KeyCode::Enter).await;
+    assert_eq!(app.orderbook.as_ref().unwrap().token_id, "snow-yes");
+}
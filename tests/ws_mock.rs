@@ -0,0 +1,163 @@
+//! The market channel client against a local server sending canned frames
+
+use futures_util::{SinkExt, StreamExt};
+use polymarket::app::websocket::{apply_book_update_static, apply_price_changes_static};
+use polymarket::data::OrderBookData;
+use polymarket::websocket::{PolymarketWebSocket, PolymarketWebSocketMessage};
+use serde_json::{json, Value};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
+use tokio_tungstenite::{accept_async, tungstenite::Message};
+
+const ASSET: &str = "71321045679252212594626385532706912750332728571942532289631379312455583992563";
+const HIGHLIGHT: Duration = Duration::from_millis(500);
+
+fn levels(levels: &[(&str, &str)]) -> Value {
+    levels.iter().map(|&(price, size)| json!({ "price": price, "size": size })).collect()
+}
+
+fn book_frame(bids: &[(&str, &str)], asks: &[(&str, &str)]) -> Value {
+    json!({
+        "event_type": "book", "asset_id": ASSET, "market": "0xmarket", "timestamp": "1700000000000", "hash": "0xbook",
+        "bids": levels(bids), "asks": levels(asks),
+    })
+}
+
+fn price_change_frame(changes: &[(&str, &str, &str)]) -> Value {
+    let changes: Value = changes.iter().map(|&(side, price, size)| json!({ "side": side, "price": price, "size": size })).collect();
+    json!({
+        "event_type": "price_change", "asset_id": ASSET, "market": "0xmarket", "timestamp": "1700000000001", "hash": "0xchange",
+        "changes": changes,
+    })
+}
+
+/// Accepts one client, checks its subscription and sends `frames` in order before closing
+async fn serve(frames: Vec<Value>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let host = format!("ws://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = accept_async(stream).await.unwrap();
+        let subscription = loop {
+            match ws.next().await.unwrap().unwrap() {
+                Message::Text(text) => break serde_json::from_str::<Value>(&text).unwrap(),
+                _ => continue,
+            }
+        };
+        assert_eq!(subscription, json!({ "type": "market", "assets_ids": [ASSET] }));
+        for frame in frames {
+            ws.send(Message::Text(frame.to_string().into())).await.unwrap();
+        }
+        let _ = ws.close(None).await;
+    });
+    host
+}
+
+fn connect(host: &str) -> (PolymarketWebSocket, UnboundedReceiver<PolymarketWebSocketMessage>) {
+    let (tx, rx) = unbounded_channel();
+    let websocket = PolymarketWebSocket::connect(host, "market".to_string(), None, vec![ASSET.to_string()], Box::new(move |message| {
+        let _ = tx.send(message);
+    }));
+    (websocket, rx)
+}
+
+async fn next_message(rx: &mut UnboundedReceiver<PolymarketWebSocketMessage>) -> PolymarketWebSocketMessage {
+    tokio::time::timeout(Duration::from_secs(5), rx.recv()).await
+        .expect("no message from the mock server")
+        .expect("connection closed early")
+}
+
+/// Applies the next message, which must be a book or a price change, and returns the book's levels
+async fn apply_next(rx: &mut UnboundedReceiver<PolymarketWebSocketMessage>, orderbook: &mut OrderBookData) -> (Vec<(f64, f64)>, Vec<(f64, f64)>) {
+    match next_message(rx).await {
+        PolymarketWebSocketMessage::Book(book) => {
+            assert_eq!(book.asset_id, ASSET);
+            apply_book_update_static(orderbook, &book, 10, HIGHLIGHT).unwrap();
+        }
+        PolymarketWebSocketMessage::PriceChange(change) => {
+            assert_eq!(change.asset_id, ASSET);
+            let epsilon = orderbook.price_epsilon();
+            apply_price_changes_static(orderbook, &change, 10, epsilon, HIGHLIGHT).unwrap();
+        }
+        other => panic!("unexpected message {other:?}"),
+    }
+    let sizes = |orders: &[polymarket::data::SimpleOrder]| orders.iter().map(|order| (order.price, order.size)).collect();
+    (sizes(&orderbook.bids), sizes(&orderbook.asks))
+}
+
+fn new_orderbook() -> OrderBookData {
+    let mut orderbook = OrderBookData::empty(ASSET.to_string(), "Will it rain?".to_string());
+    orderbook.set_tick_size(0.01);
+    orderbook
+}
+
+#[tokio::test]
+async fn book_and_price_changes_arrive_as_typed_messages() {
+    let host = serve(vec![
+        json!([book_frame(&[("0.48", "100")], &[("0.52", "80")])]),
+        price_change_frame(&[("BUY", "0.49", "30")]),
+        json!({ "event_type": "tick_size_change", "asset_id": ASSET, "market": "0xmarket",
+                "old_tick_size": "0.01", "new_tick_size": "0.001", "timestamp": "1700000000002" }),
+        json!({ "event_type": "something_new" }),
+    ]).await;
+    let (_websocket, mut rx) = connect(&host);
+
+    assert!(matches!(next_message(&mut rx).await, PolymarketWebSocketMessage::Book(book) if book.bids.len() == 1 && book.asks.len() == 1));
+    assert!(matches!(next_message(&mut rx).await, PolymarketWebSocketMessage::PriceChange(change) if change.changes.len() == 1));
+    assert!(matches!(next_message(&mut rx).await, PolymarketWebSocketMessage::TickSizeChange(tick) if tick.new_tick_size == "0.001"));
+    assert!(matches!(next_message(&mut rx).await, PolymarketWebSocketMessage::Unknown(_)));
+}
+
+#[tokio::test]
+async fn book_converges_through_snapshot_and_deltas() {
+    let host = serve(vec![
+        json!([book_frame(&[("0.48", "100"), ("0.47", "50")], &[("0.52", "80"), ("0.53", "40")])]),
+        price_change_frame(&[("BUY", "0.49", "30"), ("SELL", "0.52", "0")]),
+        json!([price_change_frame(&[("BUY", "0.48", "60")]), price_change_frame(&[("SELL", "0.51", "15")])]),
+    ]).await;
+    let (_websocket, mut rx) = connect(&host);
+    let mut orderbook = new_orderbook();
+
+    let (bids, asks) = apply_next(&mut rx, &mut orderbook).await;
+    assert_eq!(bids, vec![(0.48, 100.0), (0.47, 50.0)]);
+    assert_eq!(asks, vec![(0.52, 80.0), (0.53, 40.0)]);
+
+    let (bids, asks) = apply_next(&mut rx, &mut orderbook).await;
+    assert_eq!(bids, vec![(0.49, 30.0), (0.48, 100.0), (0.47, 50.0)]);
+    assert_eq!(asks, vec![(0.53, 40.0)]);
+
+    apply_next(&mut rx, &mut orderbook).await;
+    let (bids, asks) = apply_next(&mut rx, &mut orderbook).await;
+    assert_eq!(bids, vec![(0.49, 30.0), (0.48, 60.0), (0.47, 50.0)]);
+    assert_eq!(asks, vec![(0.51, 15.0), (0.53, 40.0)]);
+    assert!(!orderbook.is_crossed());
+    assert!((orderbook.get_midpoint() - 0.50).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn dropped_delta_crosses_the_book_until_the_next_snapshot() {
+    // The server cancels the 0.49 bid and then offers at 0.49; the cancel never reaches the client
+    let host = serve(vec![
+        json!([book_frame(&[("0.49", "30"), ("0.48", "100")], &[("0.52", "80")])]),
+        price_change_frame(&[("SELL", "0.49", "25")]),
+        json!([book_frame(&[("0.48", "100")], &[("0.49", "25"), ("0.52", "80")])]),
+    ]).await;
+    let (_websocket, mut rx) = connect(&host);
+    let mut orderbook = new_orderbook();
+
+    apply_next(&mut rx, &mut orderbook).await;
+    let recorded = orderbook.price_history.points.len();
+
+    let (bids, asks) = apply_next(&mut rx, &mut orderbook).await;
+    assert_eq!(bids[0], (0.49, 30.0));
+    assert_eq!(asks[0], (0.49, 25.0));
+    assert!(orderbook.is_crossed());
+    assert!(orderbook.crossed);
+    assert_eq!(orderbook.price_history.points.len(), recorded);
+
+    let (bids, asks) = apply_next(&mut rx, &mut orderbook).await;
+    assert_eq!(bids, vec![(0.48, 100.0)]);
+    assert_eq!(asks, vec![(0.49, 25.0), (0.52, 80.0)]);
+    assert!(!orderbook.crossed);
+}